@@ -0,0 +1,285 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::io::BufRead;
+use std::mem;
+use std::num::IntErrorKind;
+
+use aoc_common::{read_lines, Answer, ParseError, Rng, Solution};
+
+/// Items carried by an elf, `index` counts elves from zero in input order.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Elf {
+    pub index: usize,
+    pub items: Vec<u32>,
+    pub total: u32,
+}
+
+/// Calls `f` with each elf as soon as its items are read.
+///
+/// Lines with only whitespace separate elves, so do multiple blank lines.
+/// Whitespace around calories is ignored, including "\r" of CRLF line breaks.
+fn read_elves(reader: impl BufRead, mut f: impl FnMut(Elf)) -> Result<(), ParseError> {
+    let mut elf = Elf { index: 0, items: Vec::new(), total: 0 };
+    let mut finish = |elf: &mut Elf| {
+        if !elf.items.is_empty() {
+            let next = Elf { index: elf.index + 1, items: Vec::new(), total: 0 };
+            f(mem::replace(elf, next));
+        }
+    };
+    read_lines(Day01::DAY, reader, |source, line| {
+        let line = line.trim();
+        if line.is_empty() {
+            finish(&mut elf);
+            return Ok(());
+        }
+        let calories = line.parse::<u32>().map_err(|e| match e.kind() {
+            IntErrorKind::PosOverflow => source.error(line, "Too many calories"),
+            _ => source.error(line, format!("Expected calories, got {:?}", line)),
+        })?;
+        elf.total = elf.total.checked_add(calories)
+            .ok_or_else(|| source.error(line, format!("Too many calories for elf {}", elf.index + 1)))?;
+        elf.items.push(calories);
+        Ok(())
+    })?;
+    finish(&mut elf);
+    Ok(())
+}
+
+/// Elves with their items, in input order.
+pub fn inventory(input: &str) -> Result<Vec<Elf>, ParseError> {
+    inventory_from(input.as_bytes())
+}
+
+pub fn inventory_from(reader: impl BufRead) -> Result<Vec<Elf>, ParseError> {
+    let mut elves = Vec::new();
+    read_elves(reader, |elf| elves.push(elf))?;
+    Ok(elves)
+}
+
+/// Keeps the `n` greatest values, pushing a value is O(log n).
+struct Top<T> {
+    n: usize,
+    heap: BinaryHeap<Reverse<T>>,
+}
+
+impl<T: Ord> Top<T> {
+    fn new(n: usize) -> Self {
+        Top { n, heap: BinaryHeap::with_capacity(n + 1) }
+    }
+
+    fn push(&mut self, value: T) {
+        if self.heap.len() < self.n {
+            self.heap.push(Reverse(value));
+        } else if self.heap.peek().is_some_and(|Reverse(min)| value > *min) {
+            self.heap.pop();
+            self.heap.push(Reverse(value));
+        }
+    }
+
+    /// Values from the greatest.
+    fn into_sorted_vec(self) -> Vec<T> {
+        self.heap.into_sorted_vec().into_iter().map(|Reverse(value)| value).collect()
+    }
+}
+
+/// Ranking order, more calories first and earlier elves first on ties.
+fn rank_key(elf: &Elf) -> (u32, Reverse<usize>) {
+    (elf.total, Reverse(elf.index))
+}
+
+/// Elves carrying the most calories.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Ranking<'a> {
+    /// At most N elves, in ranking order.
+    pub top: Vec<&'a Elf>,
+    /// Elves left out of `top` which carry as much as its last elf.
+    pub tied: Vec<&'a Elf>,
+}
+
+impl Ranking<'_> {
+    pub fn total(&self) -> u64 {
+        self.top.iter().map(|elf| u64::from(elf.total)).sum()
+    }
+}
+
+/// Top `n` elves, all of them if there are fewer, in O(e log n) time for
+/// `e` elves.
+pub fn rank(elves: &[Elf], n: usize) -> Ranking<'_> {
+    let mut top = Top::new(n);
+    for (i, elf) in elves.iter().enumerate() {
+        top.push((rank_key(elf), Reverse(i)));
+    }
+    let top = top.into_sorted_vec();
+    let tied = match top.last() {
+        Some(&last) if top.len() == n => elves.iter()
+            .enumerate()
+            .filter(|&(i, elf)| elf.total == last.0.0 && (rank_key(elf), Reverse(i)) < last)
+            .map(|(_, elf)| elf)
+            .collect(),
+        _ => Vec::new(),
+    };
+    Ranking { top: top.into_iter().map(|(_, Reverse(i))| &elves[i]).collect(), tied }
+}
+
+/// All elves carrying the most calories, in input order.
+pub fn carrying_most(elves: &[Elf]) -> Vec<&Elf> {
+    let max = elves.iter().map(|elf| elf.total).max();
+    elves.iter().filter(|elf| Some(elf.total) == max).collect()
+}
+
+/// Calories carried by the top `n` elves, keeping only their totals.
+fn top_calories_from(reader: impl BufRead, n: usize) -> Result<u64, ParseError> {
+    let mut top = Top::new(n);
+    read_elves(reader, |elf| top.push(elf.total))?;
+    Ok(top.into_sorted_vec().into_iter().map(u64::from).sum())
+}
+
+/// Random calorie groups, at least three elves.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(3))
+        .map(|_| {
+            (0..rng.range(1..=15))
+                .map(|_| format!("{}\n", rng.range(1000..=60000)))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn solution1(input: &str) -> Result<u64, ParseError> {
+    solution1_from(input.as_bytes())
+}
+
+pub fn solution2(input: &str) -> Result<u64, ParseError> {
+    solution2_from(input.as_bytes())
+}
+
+pub fn solution1_from(reader: impl BufRead) -> Result<u64, ParseError> {
+    top_calories_from(reader, 1)
+}
+
+pub fn solution2_from(reader: impl BufRead) -> Result<u64, ParseError> {
+    top_calories_from(reader, 3)
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+
+    type Input = Vec<Elf>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        inventory(input)
+    }
+
+    fn parse_from(reader: impl BufRead) -> Result<Self::Input, ParseError> {
+        inventory_from(reader)
+    }
+
+    fn part1(elves: &Self::Input, _params: &()) -> Result<Answer, String> {
+        Ok(rank(elves, 1).total().into())
+    }
+
+    fn part2(elves: &Self::Input, _params: &()) -> Result<Answer, String> {
+        Ok(rank(elves, 3).total().into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &()) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::example;
+
+    fn indexes(elves: &[&Elf]) -> Vec<usize> {
+        elves.iter().map(|elf| elf.index).collect()
+    }
+
+    #[test]
+    fn test_solution1() {
+        let input = example!("test.txt");
+        assert_eq!(solution1(input), Ok(24000));
+    }
+
+    #[test]
+    fn test_solution2() {
+        let input = example!("test.txt");
+        assert_eq!(solution2(input), Ok(45000));
+    }
+
+    #[test]
+    fn test_reader() {
+        let input = example!("test.txt");
+        let reader = std::io::BufReader::with_capacity(4, input.as_bytes());
+        assert_eq!(solution2_from(reader), Ok(45000));
+        assert_eq!(solution2("1\n\n2\n"), Ok(3));
+        let e = solution1_from("1\n\n2\nx\n".as_bytes()).unwrap_err();
+        assert_eq!((e.line, e.column), (4, 1));
+    }
+
+    #[test]
+    fn test_inventory() {
+        let elves = inventory(example!("test.txt")).unwrap();
+        assert_eq!(elves.len(), 5);
+        assert_eq!(elves[1], Elf { index: 1, items: vec![4000], total: 4000 });
+        assert_eq!(elves[3].items, [7000, 8000, 9000]);
+        assert_eq!(inventory("\n\n").unwrap(), []);
+    }
+
+    #[test]
+    fn test_rank() {
+        let elves = inventory(example!("test.txt")).unwrap();
+        let ranking = rank(&elves, 3);
+        assert_eq!(indexes(&ranking.top), [3, 2, 4]);
+        assert_eq!(ranking.total(), 45000);
+        assert!(ranking.tied.is_empty());
+
+        assert_eq!(indexes(&rank(&elves, 10).top), [3, 2, 4, 0, 1]);
+        assert_eq!(rank(&elves, 0).top, Vec::<&Elf>::new());
+        assert_eq!(rank(&[], 3).total(), 0);
+    }
+
+    #[test]
+    fn test_tolerant_input() {
+        let input = "1000\r\n2000\r\n\r\n4000 \n \t\n\n\n5000\n6000\n  \n";
+        let elves = inventory(input).unwrap();
+        assert_eq!(elves.iter().map(|elf| elf.total).collect::<Vec<_>>(), [3000, 4000, 11000]);
+        assert_eq!(elves[2].index, 2);
+        assert_eq!(solution2(input), Ok(18000));
+
+        let big = "4294967295\n\n4294967295\n\n4294967295\n";
+        assert_eq!(solution2(big), Ok(3 * 4294967295));
+
+        let e = inventory("1\n\n4294967295\n1\n").unwrap_err();
+        assert_eq!((e.line, e.column), (4, 1));
+        assert_eq!(e.message, "Too many calories for elf 2");
+        let e = inventory("4294967296\n").unwrap_err();
+        assert_eq!(e.message, "Too many calories");
+        let e = inventory("1\r\n  -2\r\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.width), (2, 3, 2));
+        assert_eq!(e.snippet, "  -2");
+    }
+
+    #[test]
+    fn test_rank_ties() {
+        let elves = inventory("5\n\n3\n\n2\n3\n\n1\n\n4\n1\n").unwrap();
+        let ranking = rank(&elves, 2);
+        assert_eq!(indexes(&ranking.top), [0, 2]);
+        assert_eq!(indexes(&ranking.tied), [4]);
+        assert_eq!(indexes(&carrying_most(&elves)), [0, 2, 4]);
+
+        let ranking = rank(&elves, 4);
+        assert_eq!(indexes(&ranking.top), [0, 2, 4, 1]);
+        assert!(ranking.tied.is_empty());
+
+        let elves = inventory("2\n\n3\n\n2\n\n2\n").unwrap();
+        let ranking = rank(&elves, 2);
+        assert_eq!(indexes(&ranking.top), [1, 0]);
+        assert_eq!(indexes(&ranking.tied), [2, 3]);
+    }
+}
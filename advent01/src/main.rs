@@ -1,66 +1,28 @@
-use std::fs::File;
-use std::io::prelude::*;
+use std::fmt::Write;
+use std::process::ExitCode;
 
-fn solution1(input: &String) -> u32 {
-    input
-        .split("\n\n")
-        .map(|lines| {
-            lines
-                .split('\n')
-                .filter(|line| !line.is_empty())
-                .map(|x| x.parse::<u32>().unwrap())
-                .sum()
-        })
-        .max().unwrap()
-}
-
-fn solution2(input: &String) -> u32 {
-    let mut elves : Vec<u32> = input
-        .split("\n\n")
-        .map(|lines| {
-            lines
-                .split('\n')
-                .filter(|line| !line.is_empty())
-                .map(|x| x.parse::<u32>().unwrap())
-                .sum()
-        }).collect();
-    elves.sort_unstable();
-    elves.reverse();
-    elves[0..3].iter().sum()
-}
-
-fn read_file(file_path: &str) -> String {
-    let mut input_file = File::open(file_path).expect("file not found");
-
-    let mut input = String::new();
-    input_file.read_to_string(&mut input)
-        .expect("something went wrong reading the file");
+use advent2022_01::{inventory_from, rank, Day01, Elf};
+use aoc_common::ModeError;
 
-    return input;
+fn describe(elf: &Elf) -> String {
+    format!("elf {}: {} calories in {} items", elf.index + 1, elf.total, elf.items.len())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_solution1() {
-        let input = read_file("test.txt");
-        assert_eq!(solution1(&input), 24000);
-    }
-
-    #[test]
-    fn test_solution2() {
-        let input = read_file("test.txt");
-        assert_eq!(solution2(&input), 45000);
-    }
-}
-
-fn main() {
-    let input = read_file("input");
-    let s1 = solution1(&input);
-    println!("solution 1: {}", s1);
-
-    let s2 = solution2(&input);
-    println!("solution 2: {}", s2);
+fn main() -> ExitCode {
+    // With "--top N", print the N elves carrying the most calories.
+    aoc_common::main_with_mode::<Day01>("--top", 1, |values, input, _params| {
+        let n = values[0].parse::<usize>()
+            .map_err(|_| ModeError::Usage("Expected a number after --top".to_string()))?;
+        let elves = inventory_from(input)?;
+        let ranking = rank(&elves, n);
+        let mut out = String::new();
+        for (place, elf) in ranking.top.iter().enumerate() {
+            writeln!(out, "{}. {}", place + 1, describe(elf)).unwrap();
+        }
+        for elf in &ranking.tied {
+            writeln!(out, "   tied: {}", describe(elf)).unwrap();
+        }
+        writeln!(out, "Total: {}", ranking.total()).unwrap();
+        Ok(out)
+    })
 }
@@ -0,0 +1,687 @@
+use std::cmp::Reverse;
+use std::mem;
+use std::io::BufRead;
+
+use aoc_common::{config, read_lines, Answer, Config, ParseError, Rng, Solution, Source};
+
+/// Result of a round for the player.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Outcome {
+    Loss,
+    Draw,
+    Win,
+}
+
+impl Outcome {
+    const ALL: [Outcome; 3] = [Outcome::Loss, Outcome::Draw, Outcome::Win];
+}
+
+/// Shape given by its position in `Rules`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Shape(pub usize);
+
+/// Shapes, which shape beats which, and scoring.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Rules {
+    names: Vec<String>,
+    scores: Vec<u32>,
+    /// `beats[a][b]` if shape `a` beats `b`, shapes where neither wins draw.
+    beats: Vec<Vec<bool>>,
+    outcome_scores: [u32; 3],
+}
+
+impl Rules {
+    pub fn shapes(&self) -> impl Iterator<Item = Shape> {
+        (0..self.names.len()).map(Shape)
+    }
+
+    pub fn name(&self, shape: Shape) -> &str {
+        &self.names[shape.0]
+    }
+
+    pub fn outcome(&self, player: Shape, opponent: Shape) -> Outcome {
+        if self.beats[player.0][opponent.0] {
+            Outcome::Win
+        } else if self.beats[opponent.0][player.0] {
+            Outcome::Loss
+        } else {
+            Outcome::Draw
+        }
+    }
+
+    pub fn score(&self, player: Shape, opponent: Shape) -> u32 {
+        self.scores[player.0] + self.outcome_scores[self.outcome(player, opponent) as usize]
+    }
+
+    /// Highest scoring shape with the outcome against the opponent, the
+    /// first one on ties.
+    pub fn shape_for(&self, opponent: Shape, outcome: Outcome) -> Option<Shape> {
+        self.shapes()
+            .filter(|&shape| self.outcome(shape, opponent) == outcome)
+            .max_by_key(|&shape| (self.scores[shape.0], Reverse(shape.0)))
+    }
+}
+
+/// Rules with the codes used in the strategy guide.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Game {
+    pub rules: Rules,
+    opponent: Vec<String>,
+    response: Vec<String>,
+    outcomes: Vec<String>,
+}
+
+/// Reads the second column of the strategy guide as the shape to play
+/// against the opponent's shape.
+pub type Decoder = fn(&Game, Shape, &str) -> Result<Shape, String>;
+
+fn position(codes: &[String], code: &str) -> Option<usize> {
+    codes.iter().position(|x| x == code)
+}
+
+impl Game {
+    pub fn opponent(&self, code: &str) -> Result<Shape, String> {
+        position(&self.opponent, code)
+            .map(Shape)
+            .ok_or_else(|| format!("Unexpected opponent shape: {:?}", code))
+    }
+
+    /// The first reading of the guide, the second column is the shape.
+    pub fn decode_shape(&self, _opponent: Shape, code: &str) -> Result<Shape, String> {
+        position(&self.response, code)
+            .map(Shape)
+            .ok_or_else(|| format!("Unexpected response: {:?}", code))
+    }
+
+    /// The second reading of the guide, the second column is the outcome.
+    pub fn decode_outcome(&self, opponent: Shape, code: &str) -> Result<Shape, String> {
+        let outcome = position(&self.outcomes, code)
+            .map(|i| Outcome::ALL[i])
+            .ok_or_else(|| format!("Unexpected outcome: {:?}", code))?;
+        self.rules.shape_for(opponent, outcome).ok_or_else(|| {
+            format!("No shape with outcome {:?} against {}", outcome, self.rules.name(opponent))
+        })
+    }
+
+    fn round_score(&self, round: &Round, decode: Decoder) -> Result<u32, String> {
+        let opponent = self.opponent(&round.opponent)?;
+        let player = decode(self, opponent, &round.response)?;
+        Ok(self.rules.score(player, opponent))
+    }
+
+    pub fn total_score(&self, rounds: &[Round], decode: Decoder) -> Result<u32, String> {
+        rounds.iter().enumerate().try_fold(0, |total, (i, round)| {
+            self.round_score(round, decode)
+                .map(|score| total + score)
+                .map_err(|e| format!("Round {}: {}", i + 1, e))
+        })
+    }
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Params::default().game().expect("Classic rules should be valid")
+    }
+}
+
+impl Game {
+    /// Strategy guide playing the plan, e.g. "A Y\nB X\n".
+    pub fn guide(&self, opponents: &[Shape], plan: &Plan) -> String {
+        opponents.iter()
+            .zip(&plan.shapes)
+            .map(|(opponent, shape)| format!("{} {}\n", self.opponent[opponent.0], self.response[shape.0]))
+            .collect()
+    }
+}
+
+/// Shape to play in each round and the total score.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Plan {
+    pub shapes: Vec<Shape>,
+    pub score: u32,
+}
+
+impl Plan {
+    fn new(rules: &Rules, opponents: &[Shape], shapes: Vec<Shape>) -> Self {
+        let score = shapes.iter().zip(opponents).map(|(&shape, &opponent)| rules.score(shape, opponent)).sum();
+        Plan { shapes, score }
+    }
+}
+
+/// Plan with the highest score, preferring earlier shapes on ties.
+pub fn max_plan(rules: &Rules, opponents: &[Shape]) -> Plan {
+    let shapes = opponents.iter()
+        .map(|&opponent| {
+            rules.shapes()
+                .max_by_key(|&shape| (rules.score(shape, opponent), Reverse(shape.0)))
+                .unwrap()
+        })
+        .collect();
+    Plan::new(rules, opponents, shapes)
+}
+
+/// Plan with the lowest score, preferring earlier shapes on ties.
+pub fn min_plan(rules: &Rules, opponents: &[Shape]) -> Plan {
+    let shapes = opponents.iter()
+        .map(|&opponent| rules.shapes().min_by_key(|&shape| rules.score(shape, opponent)).unwrap())
+        .collect();
+    Plan::new(rules, opponents, shapes)
+}
+
+/// Sets of sums up to a limit.
+#[derive(Clone)]
+struct Sums(Vec<u64>);
+
+impl Sums {
+    fn new(limit: usize) -> Self {
+        Sums(vec![0; limit / 64 + 1])
+    }
+
+    fn contains(&self, sum: usize) -> bool {
+        self.0[sum / 64] >> (sum % 64) & 1 == 1
+    }
+
+    /// Adds all sums from `other` increased by `x`.
+    fn add_shifted(&mut self, other: &Sums, x: usize) {
+        let (words, bits) = (x / 64, x % 64);
+        for i in words..self.0.len() {
+            let j = i - words;
+            let mut word = other.0[j] << bits;
+            if bits > 0 && j > 0 {
+                word |= other.0[j - 1] >> (64 - bits);
+            }
+            self.0[i] |= word;
+        }
+    }
+}
+
+/// Plan with exactly the target score, `None` if no plan reaches it.
+///
+/// Takes O(r * s * t / 64) time for `r` rounds, `s` shapes and target `t`.
+pub fn exact_plan(rules: &Rules, opponents: &[Shape], target: u32) -> Option<Plan> {
+    if target < min_plan(rules, opponents).score || target > max_plan(rules, opponents).score {
+        return None;
+    }
+
+    // reachable[i] are the scores reachable after the first i rounds.
+    let limit = target as usize;
+    let mut start = Sums::new(limit);
+    start.0[0] = 1;
+    let mut reachable = vec![start];
+    for &opponent in opponents {
+        let mut next = Sums::new(limit);
+        for shape in rules.shapes() {
+            let score = rules.score(shape, opponent) as usize;
+            if score <= limit {
+                next.add_shifted(reachable.last().unwrap(), score);
+            }
+        }
+        reachable.push(next);
+    }
+    if !reachable[opponents.len()].contains(limit) {
+        return None;
+    }
+
+    let mut shapes = Vec::with_capacity(opponents.len());
+    let mut sum = limit;
+    for (i, &opponent) in opponents.iter().enumerate().rev() {
+        let shape = rules.shapes()
+            .find(|&shape| {
+                let score = rules.score(shape, opponent) as usize;
+                score <= sum && reachable[i].contains(sum - score)
+            })
+            .unwrap();
+        sum -= rules.score(shape, opponent) as usize;
+        shapes.push(shape);
+    }
+    shapes.reverse();
+    Some(Plan::new(rules, opponents, shapes))
+}
+
+/// Rules as configured, checked when building the `Game`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Params {
+    /// Shape names.
+    pub shapes: Vec<String>,
+    /// Score for playing each shape.
+    pub shape_scores: Vec<u32>,
+    /// Pairs of winning and losing shape, "rock>scissors" in the config.
+    pub beats: Vec<(String, String)>,
+    /// Scores for a loss, a draw and a win.
+    pub outcome_scores: Vec<u32>,
+    /// Codes of the opponent's shapes in the first column.
+    pub opponent: Vec<String>,
+    /// Codes of the shapes in the second column, for part 1.
+    pub response: Vec<String>,
+    /// Codes of a loss, a draw and a win in the second column, for part 2.
+    pub outcomes: Vec<String>,
+    /// Keys set other than "rules", applied again over a newly set preset.
+    changed: Vec<(String, String)>,
+}
+
+fn names(values: &str) -> Vec<String> {
+    values.split(',').map(str::to_string).collect()
+}
+
+fn beat(key: &str, value: &str) -> Result<(String, String), String> {
+    value.split_once('>')
+        .map(|(winner, loser)| (winner.trim().to_string(), loser.trim().to_string()))
+        .ok_or_else(|| format!("Invalid value for {}: {:?}", key, value))
+}
+
+fn check_count(key: &str, values: usize, expected: usize) -> Result<(), String> {
+    if values != expected {
+        return Err(format!("Expected {} values for {}, got {}", expected, key, values));
+    }
+    Ok(())
+}
+
+fn check_codes(key: &str, codes: &[String]) -> Result<(), String> {
+    for (i, code) in codes.iter().enumerate() {
+        if code.is_empty() || code.contains(char::is_whitespace) {
+            return Err(format!("Invalid code in {}: {:?}", key, code));
+        }
+        if codes[..i].contains(code) {
+            return Err(format!("Duplicate code in {}: {:?}", key, code));
+        }
+    }
+    Ok(())
+}
+
+impl Params {
+    pub fn classic() -> Self {
+        let beats = ["rock>scissors", "paper>rock", "scissors>paper"];
+        Params {
+            shapes: names("rock,paper,scissors"),
+            shape_scores: vec![1, 2, 3],
+            beats: beats.iter().map(|x| beat("beats", x).unwrap()).collect(),
+            outcome_scores: vec![0, 3, 6],
+            opponent: names("A,B,C"),
+            response: names("X,Y,Z"),
+            outcomes: names("X,Y,Z"),
+            changed: Vec::new(),
+        }
+    }
+
+    /// Rock-paper-scissors-lizard-Spock, opponent plays A to E and the
+    /// response is V to Z.
+    pub fn lizard_spock() -> Self {
+        let beats = [
+            "scissors>paper", "paper>rock", "rock>lizard", "lizard>spock", "spock>scissors",
+            "scissors>lizard", "lizard>paper", "paper>spock", "spock>rock", "rock>scissors",
+        ];
+        Params {
+            shapes: names("rock,paper,scissors,lizard,spock"),
+            shape_scores: vec![1, 2, 3, 4, 5],
+            beats: beats.iter().map(|x| beat("beats", x).unwrap()).collect(),
+            outcome_scores: vec![0, 3, 6],
+            opponent: names("A,B,C,D,E"),
+            response: names("V,W,X,Y,Z"),
+            outcomes: names("X,Y,Z"),
+            changed: Vec::new(),
+        }
+    }
+
+    pub fn preset(name: &str) -> Option<Self> {
+        match name {
+            "classic" => Some(Self::classic()),
+            "lizard-spock" => Some(Self::lizard_spock()),
+            _ => None,
+        }
+    }
+
+    pub fn game(&self) -> Result<Game, String> {
+        let n = self.shapes.len();
+        check_codes("shapes", &self.shapes)?;
+        check_count("shape_scores", self.shape_scores.len(), n)?;
+        check_count("opponent", self.opponent.len(), n)?;
+        check_count("response", self.response.len(), n)?;
+        check_count("outcome_scores", self.outcome_scores.len(), 3)?;
+        check_count("outcomes", self.outcomes.len(), 3)?;
+        check_codes("opponent", &self.opponent)?;
+        check_codes("response", &self.response)?;
+        check_codes("outcomes", &self.outcomes)?;
+
+        let shape = |name: &str| {
+            position(&self.shapes, name).ok_or_else(|| format!("Unknown shape in beats: {:?}", name))
+        };
+        let mut beats = vec![vec![false; n]; n];
+        for (winner, loser) in &self.beats {
+            let (w, l) = (shape(winner)?, shape(loser)?);
+            if w == l || beats[l][w] {
+                return Err(format!("Contradicting rule in beats: {}>{}", winner, loser));
+            }
+            beats[w][l] = true;
+        }
+
+        let rules = Rules {
+            names: self.shapes.clone(),
+            scores: self.shape_scores.clone(),
+            beats,
+            outcome_scores: [self.outcome_scores[0], self.outcome_scores[1], self.outcome_scores[2]],
+        };
+        Ok(Game {
+            rules,
+            opponent: self.opponent.clone(),
+            response: self.response.clone(),
+            outcomes: self.outcomes.clone(),
+        })
+    }
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
+
+impl Config for Params {
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            // The preset is the base for other keys, whichever comes first.
+            "rules" => {
+                let preset = Self::preset(value).ok_or_else(|| {
+                    format!("Unknown rules {:?}, expected \"classic\" or \"lizard-spock\"", value)
+                })?;
+                let changed = mem::replace(self, preset).changed;
+                for (key, value) in &changed {
+                    self.set(key, value)?;
+                }
+                return Ok(());
+            },
+            "shapes" => self.shapes = config::list(key, value)?,
+            "shape_scores" => self.shape_scores = config::list(key, value)?,
+            "beats" => self.beats = value.split(',').map(|x| beat(key, x)).collect::<Result<_, _>>()?,
+            "outcome_scores" => self.outcome_scores = config::list(key, value)?,
+            "opponent" => self.opponent = config::list(key, value)?,
+            "response" => self.response = config::list(key, value)?,
+            "outcomes" => self.outcomes = config::list(key, value)?,
+            _ => return Err(config::unknown(key)),
+        }
+        self.changed.push((key.to_string(), value.to_string()));
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        self.game().map(drop)
+    }
+}
+
+/// Line of the strategy guide, codes are known only with the rules.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Round {
+    pub opponent: String,
+    pub response: String,
+}
+
+/// Calls `f` with both columns of each round, e.g. "A" and "Y".
+fn read_rounds(
+    reader: impl BufRead,
+    mut f: impl FnMut(&Source, &str, &str) -> Result<(), ParseError>,
+) -> Result<(), ParseError> {
+    read_lines(Day02::DAY, reader, |source, line| {
+        if line.is_empty() {
+            return Ok(());
+        }
+        let mut it = line.split(' ');
+        let opponent = source.next(&mut it, line, "opponent shape")?;
+        let response = source.next(&mut it, opponent, "response")?;
+        if let Some(rest) = it.next() {
+            return Err(source.error(rest, "Unexpected text after response"));
+        }
+        f(source, opponent, response)
+    })
+}
+
+fn parse(input: &str) -> Result<Vec<Round>, ParseError> {
+    parse_from(input.as_bytes())
+}
+
+fn parse_from(reader: impl BufRead) -> Result<Vec<Round>, ParseError> {
+    let mut rounds = Vec::new();
+    read_rounds(reader, |_, opponent, response| {
+        rounds.push(Round { opponent: opponent.to_string(), response: response.to_string() });
+        Ok(())
+    })?;
+    Ok(rounds)
+}
+
+fn score(rounds: &[Round], params: &Params, decode: Decoder) -> Result<Answer, String> {
+    params.game()?.total_score(rounds, decode).map(Answer::from)
+}
+
+/// Total score of the guide read incrementally, unknown codes are errors.
+pub fn score_from(reader: impl BufRead, game: &Game, decode: Decoder) -> Result<u32, ParseError> {
+    let mut total = 0;
+    read_rounds(reader, |source, opponent, response| {
+        let opponent_shape = game.opponent(opponent).map_err(|e| source.error(opponent, e))?;
+        let player = decode(game, opponent_shape, response).map_err(|e| source.error(response, e))?;
+        total += game.rules.score(player, opponent_shape);
+        Ok(())
+    })?;
+    Ok(total)
+}
+
+/// Opponent's shapes in the guide read incrementally, the responses are
+/// ignored.
+pub fn opponents_from(reader: impl BufRead, game: &Game) -> Result<Vec<Shape>, ParseError> {
+    let mut opponents = Vec::new();
+    read_rounds(reader, |source, opponent, _| {
+        opponents.push(game.opponent(opponent).map_err(|e| source.error(opponent, e))?);
+        Ok(())
+    })?;
+    Ok(opponents)
+}
+
+/// Random rounds readable by both parts if the response and outcome codes
+/// overlap.
+fn generate(rng: &mut Rng, size: usize, params: &Params) -> String {
+    let both: Vec<_> = params.response.iter().filter(|x| params.outcomes.contains(x)).collect();
+    let responses: Vec<_> = if both.is_empty() { params.response.iter().collect() } else { both };
+    (0..size.max(1))
+        .map(|_| format!("{} {}\n", rng.choose(&params.opponent), rng.choose(&responses)))
+        .collect()
+}
+
+pub fn solution1(input: &str) -> Result<u32, ParseError> {
+    solution1_from(input.as_bytes())
+}
+
+pub fn solution2(input: &str) -> Result<u32, ParseError> {
+    solution2_from(input.as_bytes())
+}
+
+pub fn solution1_from(reader: impl BufRead) -> Result<u32, ParseError> {
+    score_from(reader, &Game::default(), Game::decode_shape)
+}
+
+pub fn solution2_from(reader: impl BufRead) -> Result<u32, ParseError> {
+    score_from(reader, &Game::default(), Game::decode_outcome)
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+
+    type Input = Vec<Round>;
+    type Params = Params;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn parse_from(reader: impl BufRead) -> Result<Self::Input, ParseError> {
+        parse_from(reader)
+    }
+
+    fn part1(rounds: &Self::Input, params: &Params) -> Result<Answer, String> {
+        score(rounds, params, Game::decode_shape)
+    }
+
+    fn part2(rounds: &Self::Input, params: &Params) -> Result<Answer, String> {
+        score(rounds, params, Game::decode_outcome)
+    }
+
+    fn generate(rng: &mut Rng, size: usize, params: &Params) -> String {
+        generate(rng, size, params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::example;
+
+    fn configure(params: &[(&str, &str)]) -> Result<Params, String> {
+        let mut result = Params::default();
+        for (key, value) in params {
+            result.set(key, value)?;
+        }
+        result.validate()?;
+        Ok(result)
+    }
+
+    #[test]
+    fn test_solution1() {
+        let input = example!("test.txt");
+        assert_eq!(solution1(input), Ok(15));
+    }
+
+    #[test]
+    fn test_solution2() {
+        let input = example!("test.txt");
+        assert_eq!(solution2(input), Ok(12));
+    }
+
+    #[test]
+    fn test_reader() {
+        let input = example!("test.txt").repeat(1000);
+        assert_eq!(solution1_from(std::io::BufReader::new(input.as_bytes())), Ok(15000));
+        let e = solution2_from("A Y\nB X\nC W\n".as_bytes()).unwrap_err();
+        assert_eq!((e.line, e.column), (3, 3));
+        assert_eq!(e.message, "Unexpected outcome: \"W\"");
+        let e = solution1_from("A Y\nD X\n".as_bytes()).unwrap_err();
+        assert_eq!((e.line, e.column), (2, 1));
+    }
+
+    #[test]
+    fn test_rules() {
+        let rules = Game::default().rules;
+        let [rock, paper, scissors] = [Shape(0), Shape(1), Shape(2)];
+        assert_eq!(rules.outcome(paper, rock), Outcome::Win);
+        assert_eq!(rules.outcome(paper, scissors), Outcome::Loss);
+        assert_eq!(rules.outcome(scissors, scissors), Outcome::Draw);
+        assert_eq!(rules.score(rock, scissors), 7);
+        assert_eq!(rules.shape_for(rock, Outcome::Loss), Some(scissors));
+        assert_eq!(rules.name(paper), "paper");
+    }
+
+    #[test]
+    fn test_lizard_spock() {
+        let params = configure(&[("rules", "lizard-spock")]).unwrap();
+        let game = params.game().unwrap();
+        let rounds = parse("A Y\nD Z\nE X\n").unwrap();
+        assert_eq!(game.total_score(&rounds, Game::decode_shape), Ok(4 + 5 + 3));
+        // Rock and scissors beat lizard, scissors score more.
+        assert_eq!(game.total_score(&rounds, Game::decode_outcome), Ok(4 + 9 + 3));
+        assert_eq!(Day02::part1(&parse("A V\n").unwrap(), &params), Ok(Answer::from(4)));
+        assert_eq!(Day02::part2(&parse("A V\n").unwrap(), &params), Err("Round 1: Unexpected outcome: \"V\"".to_string()));
+    }
+
+    #[test]
+    fn test_params() {
+        let params = configure(&[
+            ("shapes", "a,b"),
+            ("shape_scores", "1,1"),
+            ("beats", "a>b"),
+            ("outcome_scores", "0,1,2"),
+            ("opponent", "P,Q"),
+            ("response", "p,q"),
+            ("outcomes", "l,d,w"),
+        ]).unwrap();
+        let game = params.game().unwrap();
+        let rounds = parse("P q\nQ q\n").unwrap();
+        assert_eq!(game.total_score(&rounds, Game::decode_shape), Ok(1 + 2));
+        let rounds = parse("P w\n").unwrap();
+        assert_eq!(
+            game.total_score(&rounds, Game::decode_outcome),
+            Err("Round 1: No shape with outcome Win against a".to_string()),
+        );
+
+        assert_eq!(
+            configure(&[("shapes", "a,b,c,d")]),
+            Err("Expected 4 values for shape_scores, got 3".to_string()),
+        );
+        assert_eq!(
+            configure(&[("beats", "rock>paper,paper>rock")]),
+            Err("Contradicting rule in beats: paper>rock".to_string()),
+        );
+        assert!(configure(&[("beats", "rock>stone")]).is_err());
+        assert!(configure(&[("opponent", "A,A,B")]).is_err());
+        assert!(configure(&[("rules", "chess")]).is_err());
+
+        // Preset is applied first regardless of the order of keys.
+        let scores = [("outcome_scores", "0,1,2"), ("rules", "lizard-spock")];
+        assert_eq!(configure(&scores), configure(&[scores[1], scores[0]]));
+        assert_eq!(configure(&scores).unwrap().outcome_scores, [0, 1, 2]);
+        assert!(configure(&[("shape_scores", "1,2,3"), ("rules", "lizard-spock")]).is_err());
+    }
+
+    #[test]
+    fn test_plans() {
+        let game = Game::default();
+        let opponents = opponents_from(example!("test.txt").as_bytes(), &game).unwrap();
+        let max = max_plan(&game.rules, &opponents);
+        assert_eq!(max.score, 8 + 9 + 7);
+        assert_eq!(game.guide(&opponents, &max), "A Y\nB Z\nC X\n");
+        let min = min_plan(&game.rules, &opponents);
+        assert_eq!(min.score, 3 + 1 + 2);
+        assert_eq!(game.guide(&opponents, &min), "A Z\nB X\nC Y\n");
+
+        let plan = exact_plan(&game.rules, &opponents, 15).unwrap();
+        assert_eq!(plan.score, 15);
+        let guide = game.guide(&opponents, &plan);
+        assert_eq!(solution1(&guide), Ok(15));
+        assert_eq!(exact_plan(&game.rules, &opponents, 24), Some(max));
+        assert_eq!(exact_plan(&game.rules, &opponents, 25), None);
+        // Against rock only scores 3, 4 and 8 are possible.
+        assert_eq!(exact_plan(&game.rules, &opponents[..1], 5), None);
+        assert_eq!(exact_plan(&game.rules, &[], 0), Some(Plan { shapes: vec![], score: 0 }));
+
+        let lizard_spock = Params::lizard_spock().game().unwrap();
+        assert_eq!(opponents_from("E Q\n".as_bytes(), &lizard_spock), Ok(vec![Shape(4)]));
+        let e = opponents_from("A Y\nE Y\n".as_bytes(), &game).unwrap_err();
+        assert_eq!((e.line, e.column, e.message.as_str()), (2, 1, "Unexpected opponent shape: \"E\""));
+    }
+
+    #[test]
+    fn test_exact_plan_all_targets() {
+        let game = Params::lizard_spock().game().unwrap();
+        let rules = &game.rules;
+        let opponents: Vec<_> = [4, 0, 3, 3, 1].into_iter().map(Shape).collect();
+        let mut scores = vec![0];
+        for &opponent in &opponents {
+            scores = scores.iter()
+                .flat_map(|score| rules.shapes().map(move |shape| score + rules.score(shape, opponent)))
+                .collect();
+        }
+        for target in 0..=60 {
+            let plan = exact_plan(rules, &opponents, target);
+            assert_eq!(plan.is_some(), scores.contains(&target), "target {}", target);
+            assert!(plan.is_none_or(|plan| plan.score == target));
+        }
+        // Large scores need more than one word of sums.
+        let opponents = vec![Shape(0); 40];
+        assert_eq!(exact_plan(rules, &opponents, 320).map(|plan| plan.score), Some(320));
+    }
+
+    #[test]
+    fn test_unknown_code() {
+        let rounds = parse("A Y\nA W\n").unwrap();
+        assert_eq!(Day02::part1(&rounds, &Params::default()), Err("Round 2: Unexpected response: \"W\"".to_string()));
+        assert_eq!(Day02::part2(&rounds, &Params::default()), Err("Round 2: Unexpected outcome: \"W\"".to_string()));
+        let rounds = parse("A Y\nD Z\n").unwrap();
+        assert_eq!(Day02::part1(&rounds, &Params::default()), Err("Round 2: Unexpected opponent shape: \"D\"".to_string()));
+    }
+}
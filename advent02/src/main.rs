@@ -1,80 +1,26 @@
-use std::fs::File;
-use std::io::prelude::*;
-
-fn solution1(input: &String) -> u32 {
-    input
-        .split("\n")
-        .filter(|line| !line.is_empty())
-        .map(|line| {
-            match line {
-                "A X" => 3 + 1,
-                "A Y" => 6 + 2,
-                "A Z" => 0 + 3,
-                "B X" => 0 + 1,
-                "B Y" => 3 + 2,
-                "B Z" => 6 + 3,
-                "C X" => 6 + 1,
-                "C Y" => 0 + 2,
-                "C Z" => 3 + 3,
-                _ => panic!("Unexpected")
-            }
-        })
-        .sum()
-}
-
-fn solution2(input: &String) -> u32 {
-    input
-        .split("\n")
-        .filter(|line| !line.is_empty())
-        .map(|line| {
-            match line {
-                "A X" => 0 + 3,
-                "A Y" => 3 + 1,
-                "A Z" => 6 + 2,
-                "B X" => 0 + 1,
-                "B Y" => 3 + 2,
-                "B Z" => 6 + 3,
-                "C X" => 0 + 2,
-                "C Y" => 3 + 3,
-                "C Z" => 6 + 1,
-                _ => panic!("Unexpected")
-            }
+use std::process::ExitCode;
+
+use advent2022_02::{exact_plan, max_plan, min_plan, opponents_from, Day02};
+use aoc_common::ModeError;
+
+fn main() -> ExitCode {
+    // With "--plan max|min|SCORE", print a strategy guide against the
+    // opponent's shapes instead of the answers.
+    aoc_common::main_with_mode::<Day02>("--plan", 1, |values, input, params| {
+        let goal = values[0].as_str();
+        if !matches!(goal, "max" | "min") && goal.parse::<u32>().is_err() {
+            return Err(ModeError::Usage("Expected max, min or a score after --plan".to_string()));
+        }
+        let game = params.game().map_err(ModeError::Usage)?;
+        let opponents = opponents_from(input, &game)?;
+        let plan = match goal {
+            "max" => Some(max_plan(&game.rules, &opponents)),
+            "min" => Some(min_plan(&game.rules, &opponents)),
+            target => exact_plan(&game.rules, &opponents, target.parse().unwrap()),
+        };
+        Ok(match plan {
+            Some(plan) => format!("{}Score: {}\n", game.guide(&opponents, &plan), plan.score),
+            None => format!("No plan scores {}\n", goal),
         })
-        .sum()
-}
-
-fn read_file(file_path: &str) -> String {
-    let mut input_file = File::open(file_path).expect("file not found");
-
-    let mut input = String::new();
-    input_file.read_to_string(&mut input)
-        .expect("something went wrong reading the file");
-
-    return input;
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_solution1() {
-        let input = read_file("test.txt");
-        assert_eq!(solution1(&input), 15);
-    }
-
-    #[test]
-    fn test_solution2() {
-        let input = read_file("test.txt");
-        assert_eq!(solution2(&input), 12);
-    }
-}
-
-fn main() {
-    let input = read_file("input");
-    let s1 = solution1(&input);
-    println!("solution 1: {}", s1);
-
-    let s2 = solution2(&input);
-    println!("solution 2: {}", s2);
+    })
 }
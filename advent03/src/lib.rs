@@ -0,0 +1,716 @@
+use std::fmt;
+use std::io::BufRead;
+use std::ops::{BitAnd, BitOr};
+
+use aoc_common::{config, read_lines, report, Answer, Config, ParseError, Rng, Solution, Source};
+
+/// Priority of an item, 1 to 26 for "a" to "z" and 27 to 52 for "A" to "Z".
+pub fn priority(item: char) -> Option<u32> {
+    match item {
+        'a'..='z' => Some(item as u32 - 'a' as u32 + 1),
+        'A'..='Z' => Some(item as u32 - 'A' as u32 + 27),
+        _ => None,
+    }
+}
+
+/// Item with the given priority.
+fn item(priority: u32) -> char {
+    if priority <= 26 {
+        (b'a' + priority as u8 - 1) as char
+    } else {
+        (b'A' + priority as u8 - 27) as char
+    }
+}
+
+/// Set of items, bit `p - 1` is the item with priority `p`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Items(u64);
+
+impl Items {
+    /// Items with a priority, others are skipped.
+    pub fn new(items: &str) -> Self {
+        Items(items.chars().filter_map(priority).fold(0, |mask, p| mask | 1 << (p - 1)))
+    }
+
+    pub fn len(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Priorities from the lowest.
+    pub fn priorities(self) -> impl Iterator<Item = u32> {
+        (1..=52).filter(move |p| self.0 >> (p - 1) & 1 == 1)
+    }
+
+    pub fn priority_sum(self) -> u32 {
+        self.priorities().sum()
+    }
+}
+
+impl BitAnd for Items {
+    type Output = Items;
+
+    fn bitand(self, other: Items) -> Items {
+        Items(self.0 & other.0)
+    }
+}
+
+impl BitOr for Items {
+    type Output = Items;
+
+    fn bitor(self, other: Items) -> Items {
+        Items(self.0 | other.0)
+    }
+}
+
+impl fmt::Display for Items {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.priorities().try_for_each(|p| write!(f, "{}", item(p)))
+    }
+}
+
+/// Items in the first and the second compartment.
+pub fn compartments(items: &str) -> (&str, &str) {
+    items.split_at(items.len() / 2)
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Rucksack {
+    pub left: Items,
+    pub right: Items,
+}
+
+impl Rucksack {
+    pub fn new(items: &str) -> Self {
+        let (left, right) = compartments(items);
+        Rucksack { left: Items::new(left), right: Items::new(right) }
+    }
+
+    pub fn items(&self) -> Items {
+        self.left | self.right
+    }
+
+    /// Items in both compartments.
+    pub fn duplicates(&self) -> Items {
+        self.left & self.right
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BadgeError {
+    None,
+    Multiple(Items),
+}
+
+impl fmt::Display for BadgeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BadgeError::None => write!(f, "No badge"),
+            BadgeError::Multiple(items) => write!(f, "Multiple badges: {}", items),
+        }
+    }
+}
+
+/// The only item carried by every rucksack in the group.
+pub fn badge(group: &[Rucksack]) -> Result<Items, BadgeError> {
+    single_badge(group.iter().map(Rucksack::items).fold(Items(!0), |a, b| a & b))
+}
+
+fn single_badge(common: Items) -> Result<Items, BadgeError> {
+    match common.len() {
+        0 => Err(BadgeError::None),
+        1 => Ok(common),
+        _ => Err(BadgeError::Multiple(common)),
+    }
+}
+
+pub struct Params {
+    /// Rucksacks in a group sharing a badge.
+    pub group_size: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { group_size: 3 }
+    }
+}
+
+impl Config for Params {
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "group_size" => {
+                self.group_size = config::value(key, value)?;
+                if self.group_size == 0 {
+                    return Err(format!("Invalid value for {}: {:?}", key, value));
+                }
+            },
+            _ => return Err(config::unknown(key)),
+        }
+        Ok(())
+    }
+}
+
+/// Calls `f` with each valid rucksack.
+fn read_rucksacks(
+    reader: impl BufRead,
+    mut f: impl FnMut(&Source, &str, Rucksack) -> Result<(), ParseError>,
+) -> Result<(), ParseError> {
+    read_lines(Day03::DAY, reader, |source, line| {
+        if line.is_empty() {
+            return Ok(());
+        }
+        if let Some((i, c)) = line.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+            return Err(source.error(&line[i..i + c.len_utf8()], format!("Unexpected item: {:?}", c)));
+        }
+        if line.len() % 2 != 0 {
+            return Err(source.error(line, "Odd number of items in rucksack"));
+        }
+        f(source, line, Rucksack::new(line))
+    })
+}
+
+fn parse(input: &str) -> Result<Vec<Rucksack>, ParseError> {
+    parse_from(input.as_bytes())
+}
+
+fn parse_from(reader: impl BufRead) -> Result<Vec<Rucksack>, ParseError> {
+    let mut rucksacks = Vec::new();
+    read_rucksacks(reader, |_, _, rucksack| {
+        rucksacks.push(rucksack);
+        Ok(())
+    })?;
+    Ok(rucksacks)
+}
+
+/// Priorities of items in both compartments, warns unless there is exactly
+/// one such item.
+fn misplaced_priority(index: usize, rucksack: &Rucksack) -> u32 {
+    let duplicates = rucksack.duplicates();
+    match duplicates.len() {
+        0 => report::warn(format!("Rucksack {}: No misplaced item", index + 1)),
+        1 => {},
+        _ => report::warn(format!("Rucksack {}: Multiple misplaced items: {}", index + 1, duplicates)),
+    }
+    duplicates.priority_sum()
+}
+
+fn misplaced_priorities(rucksacks: &[Rucksack]) -> u32 {
+    rucksacks.iter().enumerate().map(|(i, rucksack)| misplaced_priority(i, rucksack)).sum()
+}
+
+/// Badge priorities, a group without a single badge is an error. The last
+/// group can be smaller.
+fn badge_priorities(rucksacks: &[Rucksack], params: &Params) -> Result<u32, String> {
+    rucksacks.chunks(params.group_size)
+        .enumerate()
+        .map(|(i, group)| {
+            badge(group)
+                .map(Items::priority_sum)
+                .map_err(|e| format!("Group {}: {}", i + 1, e))
+        })
+        .sum()
+}
+
+/// Items for a rucksack, with `pool[0]` in both compartments and `badge` in
+/// one of them.
+fn generate_rucksack(rng: &mut Rng, badge: char, pool: &[char], out: &mut String) {
+    let misplaced = pool[0];
+    let (left, right) = if pool.len() > 2 {
+        pool[1..].split_at((pool.len() - 1) / 2)
+    } else {
+        (&pool[..1], &pool[..1])
+    };
+    let len = rng.below(12) + 2;
+    let (first, second) = if rng.chance(0.5) {
+        (vec![misplaced, badge], vec![misplaced])
+    } else {
+        (vec![misplaced], vec![misplaced, badge])
+    };
+    for (mut compartment, items) in [(first, left), (second, right)] {
+        while compartment.len() < len {
+            compartment.push(*rng.choose(items));
+        }
+        rng.shuffle(&mut compartment);
+        out.extend(compartment);
+    }
+    out.push('\n');
+}
+
+/// Groups of rucksacks, each with a single misplaced item and the badge as
+/// the only item common to the group.
+fn generate(rng: &mut Rng, size: usize, params: &Params) -> String {
+    let group_size = params.group_size;
+    let mut out = String::new();
+    for _ in 0..size.div_ceil(group_size).max(1) {
+        let mut items: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+        rng.shuffle(&mut items);
+        let badge = items[0];
+        if group_size == 1 {
+            generate_rucksack(rng, badge, &[badge], &mut out);
+            continue;
+        }
+        // Items other than the badge are split into pools and rucksacks
+        // take turns with them, so no other item is in all of them.
+        let pools: Vec<_> = items[1..].chunks((51 / group_size).max(3)).collect();
+        for i in 0..group_size {
+            generate_rucksack(rng, badge, pools[i % pools.len()], &mut out);
+        }
+    }
+    out
+}
+
+/// Items of one type moved to the other compartment.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Move {
+    pub item: char,
+    pub count: usize,
+    pub to_left: bool,
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let to = if self.to_left { "left" } else { "right" };
+        write!(f, "move {} {} to {}", self.count, self.item, to)
+    }
+}
+
+fn counts(items: &str) -> [usize; 52] {
+    let mut counts = [0; 52];
+    for p in items.chars().filter_map(priority) {
+        counts[p as usize - 1] += 1;
+    }
+    counts
+}
+
+/// Fewest moves so that no item type is in both compartments and both
+/// compartments keep half of the items, `None` if the types cannot be split
+/// evenly.
+///
+/// Each type ends up whole in one compartment, so the types going to the
+/// left compartment have to fill exactly its size. The fewest items moved
+/// for each filled size are found type by type, moving a type to the left
+/// costs its items on the right and the other way around.
+pub fn moves(items: &str) -> Option<Vec<Move>> {
+    let (left, right) = compartments(items);
+    let (left_counts, right_counts) = (counts(left), counts(right));
+    let size = left.len();
+    let mut best = vec![None; size + 1];
+    best[0] = Some(0);
+    // Whether the type goes to the left for each filled size.
+    let mut to_left = Vec::new();
+    for (&l, &r) in left_counts.iter().zip(&right_counts) {
+        let mut next: Vec<Option<usize>> = vec![None; size + 1];
+        let mut left_here = vec![false; size + 1];
+        for (filled, cost) in best.iter().enumerate() {
+            let Some(cost) = *cost else { continue };
+            if next[filled].is_none_or(|c| cost + l < c) {
+                (next[filled], left_here[filled]) = (Some(cost + l), false);
+            }
+            if filled + l + r <= size && next[filled + l + r].is_none_or(|c| cost + r < c) {
+                (next[filled + l + r], left_here[filled + l + r]) = (Some(cost + r), true);
+            }
+        }
+        best = next;
+        to_left.push(left_here);
+    }
+    best[size]?;
+
+    let mut filled = size;
+    let mut moves = Vec::new();
+    for p in (1..=52).rev() {
+        let (l, r) = (left_counts[p - 1], right_counts[p - 1]);
+        let to_left = to_left[p - 1][filled];
+        if to_left {
+            filled -= l + r;
+        }
+        let count = if to_left { r } else { l };
+        if count > 0 {
+            moves.push(Move { item: item(p as u32), count, to_left });
+        }
+    }
+    moves.reverse();
+    Some(moves)
+}
+
+/// Item which should be the badge of a group.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeSuggestion {
+    pub badge: char,
+    /// Positions of rucksacks in the group without the badge.
+    pub missing: Vec<usize>,
+    /// Other items in all rucksacks of the group.
+    pub others: Items,
+}
+
+/// Suggests the item carried by most rucksacks, the one with the lowest
+/// priority on ties, `None` if the group has no items.
+pub fn suggest_badge(group: &[Rucksack]) -> Option<BadgeSuggestion> {
+    let all = group.iter().fold(Items(0), |a, b| a | b.items());
+    let carrying = |p: u32| group.iter().filter(|r| r.items() & Items(1 << (p - 1)) != Items(0)).count();
+    let best = all.priorities().max_by_key(|&p| (carrying(p), std::cmp::Reverse(p)))?;
+    let badge = Items(1 << (best - 1));
+    let common = group.iter().fold(Items(!0), |a, b| a & b.items());
+    Some(BadgeSuggestion {
+        badge: item(best),
+        missing: (0..group.len()).filter(|&i| group[i].items() & badge == Items(0)).collect(),
+        others: Items(common.0 & !badge.0),
+    })
+}
+
+/// Moves for each rucksack and badges for each group.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Reorganisation {
+    /// Line number and moves for each rucksack, `None` if it cannot be
+    /// split evenly.
+    pub rucksacks: Vec<(usize, Option<Vec<Move>>)>,
+    /// Line numbers and the suggested badge for each group.
+    pub groups: Vec<(Vec<usize>, Option<BadgeSuggestion>)>,
+}
+
+impl Reorganisation {
+    pub fn moved_items(&self) -> usize {
+        self.rucksacks.iter().flat_map(|(_, moves)| moves.iter().flatten()).map(|m| m.count).sum()
+    }
+}
+
+impl fmt::Display for Reorganisation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (line, moves) in &self.rucksacks {
+            let Some(moves) = moves else {
+                writeln!(f, "line {}: no even split", line)?;
+                continue;
+            };
+            let moves: Vec<_> = moves.iter().map(Move::to_string).collect();
+            let moves = if moves.is_empty() { "no moves".to_string() } else { moves.join(", ") };
+            writeln!(f, "line {}: {}", line, moves)?;
+        }
+        for (i, (lines, suggestion)) in self.groups.iter().enumerate() {
+            write!(f, "group {}: ", i + 1)?;
+            let Some(suggestion) = suggestion else {
+                writeln!(f, "no items")?;
+                continue;
+            };
+            write!(f, "badge {}", suggestion.badge)?;
+            if !suggestion.missing.is_empty() {
+                let missing: Vec<_> = suggestion.missing.iter().map(|&i| lines[i].to_string()).collect();
+                write!(f, ", add to lines {}", missing.join(", "))?;
+            }
+            if !suggestion.others.is_empty() {
+                write!(f, ", also common {}", suggestion.others)?;
+            }
+            writeln!(f)?;
+        }
+        let rucksacks = self.rucksacks.iter()
+            .filter(|(_, moves)| moves.as_ref().is_some_and(|moves| !moves.is_empty()))
+            .count();
+        let unsplit = self.rucksacks.iter().filter(|(_, moves)| moves.is_none()).count();
+        let groups = self.groups.iter()
+            .filter(|(_, s)| s.as_ref().is_none_or(|s| !s.missing.is_empty() || !s.others.is_empty()))
+            .count();
+        write!(f, "total: {} items moved in {} rucksacks, {} groups without a single badge",
+            self.moved_items(), rucksacks, groups)?;
+        if unsplit > 0 {
+            write!(f, ", {} rucksacks without an even split", unsplit)?;
+        }
+        writeln!(f)
+    }
+}
+
+/// Plans moves for each rucksack and badges for groups of rucksacks.
+pub fn reorganise(input: &str, params: &Params) -> Result<Reorganisation, ParseError> {
+    reorganise_from(input.as_bytes(), params)
+}
+
+pub fn reorganise_from(
+    reader: impl BufRead,
+    params: &Params,
+) -> Result<Reorganisation, ParseError> {
+    let mut plan = Reorganisation::default();
+    let mut group = Vec::new();
+    let mut lines = Vec::new();
+    let add_group = |group: &mut Vec<Rucksack>, lines: &mut Vec<usize>, plan: &mut Reorganisation| {
+        plan.groups.push((std::mem::take(lines), suggest_badge(group)));
+        group.clear();
+    };
+    read_rucksacks(reader, |source, line, rucksack| {
+        plan.rucksacks.push((source.line, moves(line)));
+        group.push(rucksack);
+        lines.push(source.line);
+        if group.len() == params.group_size {
+            add_group(&mut group, &mut lines, &mut plan);
+        }
+        Ok(())
+    })?;
+    if !group.is_empty() {
+        add_group(&mut group, &mut lines, &mut plan);
+    }
+    Ok(plan)
+}
+
+pub fn solution1(input: &str) -> Result<u32, ParseError> {
+    solution1_from(input.as_bytes())
+}
+
+pub fn solution2(input: &str) -> Result<u32, ParseError> {
+    solution2_from(input.as_bytes())
+}
+
+pub fn solution1_from(reader: impl BufRead) -> Result<u32, ParseError> {
+    let mut total = 0;
+    let mut index = 0;
+    read_rucksacks(reader, |_, _, rucksack| {
+        total += misplaced_priority(index, &rucksack);
+        index += 1;
+        Ok(())
+    })?;
+    Ok(total)
+}
+
+pub fn solution2_from(reader: impl BufRead) -> Result<u32, ParseError> {
+    badge_priorities_from(reader, &Params::default())
+}
+
+/// Sum of badge priorities read incrementally, a group without a single
+/// badge is an error at its last rucksack. The last group can be smaller.
+pub fn badge_priorities_from(reader: impl BufRead, params: &Params) -> Result<u32, ParseError> {
+    let mut total = 0;
+    let mut groups = 0;
+    let mut count = 0;
+    let mut common = Items(!0);
+    let mut last = Ok(0);
+    read_rucksacks(reader, |source, line, rucksack| {
+        if count == 0 {
+            groups += 1;
+        }
+        count += 1;
+        common = common & rucksack.items();
+        last = single_badge(common)
+            .map(Items::priority_sum)
+            .map_err(|e| source.error(line, format!("Group {}: {}", groups, e)));
+        if count == params.group_size {
+            total += last.clone()?;
+            last = Ok(0);
+            count = 0;
+            common = Items(!0);
+        }
+        Ok(())
+    })?;
+    Ok(total + last?)
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+
+    type Input = Vec<Rucksack>;
+    type Params = Params;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn parse_from(reader: impl BufRead) -> Result<Self::Input, ParseError> {
+        parse_from(reader)
+    }
+
+    fn part1(rucksacks: &Self::Input, _params: &Params) -> Result<Answer, String> {
+        Ok(misplaced_priorities(rucksacks).into())
+    }
+
+    fn part2(rucksacks: &Self::Input, params: &Params) -> Result<Answer, String> {
+        badge_priorities(rucksacks, params).map(Answer::from)
+    }
+
+    fn generate(rng: &mut Rng, size: usize, params: &Params) -> String {
+        generate(rng, size, params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::{example, property};
+
+    #[test]
+    fn test_solution1() {
+        let input = example!("test.txt");
+        assert_eq!(solution1(input), Ok(157));
+    }
+
+    #[test]
+    fn test_solution2() {
+        let input = example!("test.txt");
+        assert_eq!(solution2(input), Ok(70));
+    }
+
+    #[test]
+    fn test_reader() {
+        let input = generate(&mut Rng::new(3), 300, &Params::default());
+        let rucksacks = parse(&input).unwrap();
+        let reader = std::io::BufReader::with_capacity(16, input.as_bytes());
+        assert_eq!(solution1_from(reader), Ok(misplaced_priorities(&rucksacks)));
+        let reader = std::io::BufReader::with_capacity(16, input.as_bytes());
+        assert_eq!(solution2_from(reader), Ok(badge_priorities(&rucksacks, &Params::default()).unwrap()));
+    }
+
+    #[test]
+    fn test_items() {
+        assert_eq!((priority('a'), priority('z'), priority('A'), priority('Z')), (Some(1), Some(26), Some(27), Some(52)));
+        assert_eq!(priority('1'), None);
+        let items = Items::new("ZaBba");
+        assert_eq!(items.len(), 4);
+        assert_eq!(items.priorities().collect::<Vec<_>>(), [1, 2, 28, 52]);
+        assert_eq!(items.to_string(), "abBZ");
+        assert_eq!(Rucksack::new("abcCbA").duplicates().to_string(), "b");
+        assert_eq!(Rucksack::new("abcbaC").duplicates().to_string(), "ab");
+    }
+
+    #[test]
+    fn test_badge_errors() {
+        let group = parse("abcd\nabef\n").unwrap();
+        assert_eq!(badge(&group), Err(BadgeError::Multiple(Items::new("ab"))));
+        let group = parse("abcd\nefgh\n").unwrap();
+        assert_eq!(badge(&group), Err(BadgeError::None));
+
+        let input = "abcd\nabef\naxax\nxyxz\n";
+        let rucksacks = parse(input).unwrap();
+        let params = Params { group_size: 2 };
+        assert_eq!(badge_priorities(&rucksacks, &params), Err("Group 1: Multiple badges: ab".to_string()));
+        let e = badge_priorities_from(input.as_bytes(), &params).unwrap_err();
+        assert_eq!((e.line, e.message.as_str()), (2, "Group 1: Multiple badges: ab"));
+        assert_eq!(badge_priorities(&rucksacks[2..], &params), Ok(24));
+        assert_eq!(badge_priorities_from("axax\n\nxyxz\n".as_bytes(), &params), Ok(24));
+
+        report::take_warnings();
+        assert_eq!(misplaced_priorities(&rucksacks), 1 + 24 + 24);
+        assert_eq!(report::take_warnings(), [
+            "Rucksack 1: No misplaced item",
+            "Rucksack 2: No misplaced item",
+            "Rucksack 3: Multiple misplaced items: ax",
+        ]);
+
+        let e = solution2_from("abcd\nabef\nabgh\nxyxz\n".as_bytes()).unwrap_err();
+        assert_eq!(e.line, 3);
+        assert_eq!(e.message, "Group 1: Multiple badges: ab");
+        let e = solution2_from("abcd\nabcd\nabcd\nxyxz\n\n".as_bytes()).unwrap_err();
+        assert_eq!(e.line, 3);
+        let e = solution2_from("aBaB\nBcdB\nBexB\nxyxz\nyzzy\n".as_bytes()).unwrap_err();
+        assert_eq!((e.line, e.message.as_str()), (5, "Group 2: Multiple badges: yz"));
+    }
+
+    /// Rucksack with the moves applied, left compartment first.
+    fn rearrange(items: &str, moves: &[Move]) -> String {
+        let (left, _) = compartments(items);
+        let goes_left = |c: char| moves.iter().find(|m| m.item == c).map_or(left.contains(c), |m| m.to_left);
+        let (left, right): (String, String) = items.chars().partition(|&c| goes_left(c));
+        assert_eq!(left.len(), right.len(), "{}", items);
+        left + &right
+    }
+
+    #[test]
+    fn test_moves() {
+        assert_eq!(moves("vJrwpWtwJgWrhcsFMMfFFhFp"), Some(vec![
+            Move { item: 'p', count: 1, to_left: false },
+            Move { item: 's', count: 1, to_left: true },
+        ]));
+        assert_eq!(moves("aabcaBdd"), Some(vec![
+            Move { item: 'a', count: 1, to_left: true },
+            Move { item: 'b', count: 1, to_left: false },
+        ]));
+        assert_eq!(moves("abab"), Some(vec![
+            Move { item: 'a', count: 1, to_left: false },
+            Move { item: 'b', count: 1, to_left: true },
+        ]));
+        assert_eq!(moves("abcd"), Some(vec![]));
+        assert_eq!(moves("aaab"), None);
+        assert_eq!(Move { item: 'L', count: 2, to_left: false }.to_string(), "move 2 L to right");
+
+        for items in example!("test.txt").lines() {
+            let fixed = rearrange(items, &moves(items).unwrap());
+            assert!(Rucksack::new(&fixed).duplicates().is_empty(), "{}", fixed);
+        }
+    }
+
+    #[test]
+    fn test_fewest_moves() {
+        // Against trying every set of types for the left compartment.
+        for seed in 0..property::cases(300) {
+            let mut rng = Rng::new(seed);
+            let items: String = (0..2 * rng.range(1..=6)).map(|_| *rng.choose(&['a', 'b', 'c', 'd', 'e'])).collect();
+            let (left, right) = compartments(&items);
+            let fewest = (0..1 << 5)
+                .filter_map(|set: u32| {
+                    let goes_left = |c: char| set >> (c as u8 - b'a') & 1 == 1;
+                    let filled = items.chars().filter(|&c| goes_left(c)).count();
+                    let moved = left.chars().filter(|&c| !goes_left(c)).count()
+                        + right.chars().filter(|&c| goes_left(c)).count();
+                    Some(moved).filter(|_| filled == left.len())
+                })
+                .min();
+            let found = moves(&items);
+            assert_eq!(found.as_ref().map(|moves| moves.iter().map(|m| m.count).sum()), fewest, "{}", items);
+            if let Some(moves) = found {
+                assert!(Rucksack::new(&rearrange(&items, &moves)).duplicates().is_empty(), "{}", items);
+            }
+        }
+    }
+
+    #[test]
+    fn test_suggest_badge() {
+        let group = parse("abcd\nabef\nghab\n").unwrap();
+        let suggestion = suggest_badge(&group).unwrap();
+        assert_eq!((suggestion.badge, suggestion.missing.len()), ('a', 0));
+        assert_eq!(suggestion.others.to_string(), "b");
+
+        let group = parse("abcd\ncdef\nghij\n").unwrap();
+        let suggestion = suggest_badge(&group).unwrap();
+        assert_eq!((suggestion.badge, suggestion.missing), ('c', vec![2]));
+        assert!(suggestion.others.is_empty());
+        assert_eq!(suggest_badge(&[]), None);
+    }
+
+    #[test]
+    fn test_reorganise() {
+        let plan = reorganise(example!("test.txt"), &Params::default()).unwrap();
+        assert_eq!(plan.rucksacks.len(), 6);
+        assert_eq!(plan.moved_items(), 2 + 4 + 2 + 4 + 4 + 2);
+        let badges: Vec<_> = plan.groups.iter().map(|(_, s)| s.as_ref().unwrap().badge).collect();
+        assert_eq!(badges, ['r', 'Z']);
+
+        let plan = reorganise("abab\n\nabcd\ncdef\n", &Params { group_size: 2 }).unwrap();
+        assert_eq!(plan.groups[0].0, [1, 3]);
+        assert_eq!(plan.to_string(), "\
+line 1: move 1 a to right, move 1 b to left
+line 3: no moves
+line 4: no moves
+group 1: badge a, also common b
+group 2: badge c, also common def
+total: 2 items moved in 1 rucksacks, 2 groups without a single badge
+");
+
+        let plan = reorganise("aaab\n", &Params { group_size: 1 }).unwrap();
+        assert_eq!(plan.to_string(), "\
+line 1: no even split
+group 1: badge a, also common b
+total: 0 items moved in 0 rucksacks, 1 groups without a single badge, 1 rucksacks without an even split
+");
+    }
+
+    #[test]
+    fn test_group_size() {
+        for group_size in [1, 2, 5, 30] {
+            let params = Params { group_size };
+            let input = generate(&mut Rng::new(7), 60, &params);
+            let rucksacks = parse(&input).unwrap();
+            assert!(rucksacks.chunks(group_size).all(|group| badge(group).is_ok()));
+            assert!(rucksacks.iter().all(|rucksack| rucksack.duplicates().len() == 1));
+        }
+        let mut params = Params::default();
+        assert!(params.set("group_size", "0").is_err());
+        assert_eq!(params.set("group_size", "4"), Ok(()));
+    }
+}
@@ -0,0 +1,322 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::io::BufRead;
+
+use aoc_common::{read_lines, Answer, ParseError, Rng, Solution};
+
+/// Sections from `start` to `end`, both included.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Interval {
+    pub start: u32,
+    pub end: u32,
+}
+
+impl Interval {
+    pub fn new(start: u32, end: u32) -> Option<Self> {
+        (start <= end).then_some(Interval { start, end })
+    }
+
+    /// Number of sections, an interval is never empty.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> u64 {
+        (self.end - self.start) as u64 + 1
+    }
+
+    pub fn contains(&self, other: &Interval) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    pub fn contains_section(&self, section: u32) -> bool {
+        self.start <= section && section <= self.end
+    }
+
+    pub fn overlaps(&self, other: &Interval) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        Interval::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// Single interval with sections of both, `None` if there is a gap
+    /// between them.
+    pub fn union(&self, other: &Interval) -> Option<Interval> {
+        let (first, second) = if self.start <= other.start { (self, other) } else { (other, self) };
+        (second.start as u64 <= first.end as u64 + 1)
+            .then(|| Interval { start: first.start, end: first.end.max(second.end) })
+    }
+}
+
+pub type Pair = [Interval; 2];
+
+/// Calls `f` with each pair of section ranges.
+fn read_pairs(reader: impl BufRead, mut f: impl FnMut(Pair)) -> Result<(), ParseError> {
+    read_lines(Day04::DAY, reader, |source, line| {
+        if line.is_empty() {
+            return Ok(());
+        }
+        let mut it = line.split([',', '-']);
+        let mut interval = || -> Result<Interval, ParseError> {
+            let start = source.next(&mut it, line, "section")?;
+            let end = source.next(&mut it, line, "section")?;
+            Interval::new(source.parse(start)?, source.parse(end)?)
+                .ok_or_else(|| source.error(end, "Range ends before it starts"))
+        };
+        let pair = [interval()?, interval()?];
+        if let Some(rest) = it.next() {
+            return Err(source.error(rest, "Unexpected section"));
+        }
+        f(pair);
+        Ok(())
+    })
+}
+
+fn parse(input: &str) -> Result<Vec<Pair>, ParseError> {
+    parse_from(input.as_bytes())
+}
+
+fn parse_from(reader: impl BufRead) -> Result<Vec<Pair>, ParseError> {
+    let mut pairs = Vec::new();
+    read_pairs(reader, |pair| pairs.push(pair))?;
+    Ok(pairs)
+}
+
+fn contains([a, b]: &Pair) -> bool {
+    a.contains(b) || b.contains(a)
+}
+
+fn overlaps([a, b]: &Pair) -> bool {
+    a.overlaps(b)
+}
+
+fn count(pairs: &[Pair], filter: fn(&Pair) -> bool) -> usize {
+    pairs
+        .iter()
+        .filter(|x| filter(x))
+        .count()
+}
+
+fn count_from(reader: impl BufRead, filter: fn(&Pair) -> bool) -> Result<usize, ParseError> {
+    let mut count = 0;
+    read_pairs(reader, |pair| count += filter(&pair) as usize)?;
+    Ok(count)
+}
+
+fn fully_contained(pairs: &[Pair]) -> usize {
+    count(pairs, contains)
+}
+
+fn overlapping(pairs: &[Pair]) -> usize {
+    count(pairs, overlaps)
+}
+
+/// Assignments of all elves, the pair `i` gives assignments `2 * i` and
+/// `2 * i + 1`.
+pub fn assignments(pairs: &[Pair]) -> Vec<Interval> {
+    pairs.iter().flatten().copied().collect()
+}
+
+/// Number of sections assigned to at least one elf.
+pub fn coverage(intervals: &[Interval]) -> u64 {
+    let mut sorted = intervals.to_vec();
+    sorted.sort_unstable();
+    let mut total = 0;
+    let mut current: Option<Interval> = None;
+    for interval in sorted {
+        current = match current.and_then(|c| c.union(&interval)) {
+            Some(merged) => Some(merged),
+            None => {
+                total += current.map_or(0, |c| c.len());
+                Some(interval)
+            },
+        };
+    }
+    total + current.map_or(0, |c| c.len())
+}
+
+/// The first section assigned to the most elves and the number of elves.
+pub fn most_assigned(intervals: &[Interval]) -> Option<(u32, usize)> {
+    // Assignments start at the section and end after the section.
+    let mut events: Vec<(u64, bool)> = intervals.iter()
+        .flat_map(|x| [(x.start as u64, true), (x.end as u64 + 1, false)])
+        .collect();
+    events.sort_unstable_by_key(|&(section, starts)| (section, starts));
+    let mut best = None;
+    let mut count = 0;
+    for (section, starts) in events {
+        if starts {
+            count += 1;
+            if best.is_none_or(|(_, max)| count > max) {
+                best = Some((section as u32, count));
+            }
+        } else {
+            count -= 1;
+        }
+    }
+    best
+}
+
+/// Indexes of all overlapping intervals, each pair once with the lower index
+/// first, in O(n log n + k) time for `k` pairs.
+///
+/// Pairs are in sweep order, grouped by the interval starting later.
+pub fn overlapping_pairs(intervals: &[Interval]) -> Vec<(usize, usize)> {
+    let mut order: Vec<usize> = (0..intervals.len()).collect();
+    order.sort_unstable_by_key(|&i| intervals[i].start);
+    // Intervals which started, the one ending first on top.
+    let mut active = BinaryHeap::new();
+    let mut pairs = Vec::new();
+    for i in order {
+        let start = intervals[i].start;
+        while active.peek().is_some_and(|&Reverse((end, _))| end < start) {
+            active.pop();
+        }
+        pairs.extend(active.iter().map(|&Reverse((_, j))| (i.min(j), i.max(j))));
+        active.push(Reverse((intervals[i].end, i)));
+    }
+    pairs
+}
+
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut range = || {
+        let start = rng.range(1..=99);
+        (start, rng.range(start..=99))
+    };
+    (0..size.max(1))
+        .map(|_| {
+            let ((a, b), (c, d)) = (range(), range());
+            format!("{}-{},{}-{}\n", a, b, c, d)
+        })
+        .collect()
+}
+
+pub fn solution1(input: &str) -> Result<usize, ParseError> {
+    solution1_from(input.as_bytes())
+}
+
+pub fn solution2(input: &str) -> Result<usize, ParseError> {
+    solution2_from(input.as_bytes())
+}
+
+pub fn solution1_from(reader: impl BufRead) -> Result<usize, ParseError> {
+    count_from(reader, contains)
+}
+
+pub fn solution2_from(reader: impl BufRead) -> Result<usize, ParseError> {
+    count_from(reader, overlaps)
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+
+    type Input = Vec<Pair>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn parse_from(reader: impl BufRead) -> Result<Self::Input, ParseError> {
+        parse_from(reader)
+    }
+
+    fn part1(pairs: &Self::Input, _params: &()) -> Result<Answer, String> {
+        Ok(fully_contained(pairs).into())
+    }
+
+    fn part2(pairs: &Self::Input, _params: &()) -> Result<Answer, String> {
+        Ok(overlapping(pairs).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &()) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::example;
+
+    #[test]
+    fn test_solution1() {
+        let input = example!("test.txt");
+        assert_eq!(solution1(input), Ok(2));
+    }
+
+    #[test]
+    fn test_solution2() {
+        let input = example!("test.txt");
+        assert_eq!(solution2(input), Ok(4));
+    }
+
+    fn interval(start: u32, end: u32) -> Interval {
+        Interval::new(start, end).unwrap()
+    }
+
+    #[test]
+    fn test_interval() {
+        let (a, b, c) = (interval(2, 6), interval(4, 8), interval(9, 9));
+        assert_eq!(Interval::new(3, 2), None);
+        assert_eq!((a.len(), c.len()), (5, 1));
+        assert!(a.overlaps(&b) && !a.overlaps(&c));
+        assert!(interval(3, 5).contains(&interval(4, 5)) && !a.contains(&b));
+        assert!(a.contains_section(6) && !a.contains_section(7));
+        assert_eq!(a.intersection(&b), Some(interval(4, 6)));
+        assert_eq!(a.intersection(&c), None);
+        assert_eq!(b.union(&a), Some(interval(2, 8)));
+        assert_eq!(b.union(&c), Some(interval(4, 9)));
+        assert_eq!(a.union(&c), None);
+        assert_eq!(interval(0, u32::MAX).union(&interval(5, u32::MAX)), Some(interval(0, u32::MAX)));
+    }
+
+    #[test]
+    fn test_sweep() {
+        let intervals = assignments(&parse(example!("test.txt")).unwrap());
+        assert_eq!(coverage(&intervals), 8);
+        assert_eq!(most_assigned(&intervals), Some((6, 8)));
+        assert_eq!(overlapping_pairs(&intervals).len(), 49);
+        assert_eq!(coverage(&[]), 0);
+        assert_eq!(most_assigned(&[]), None);
+        assert_eq!(most_assigned(&[interval(1, 2), interval(3, 4)]), Some((1, 1)));
+    }
+
+    #[test]
+    fn test_sweep_oracle() {
+        let pairs = parse(&generate(&mut Rng::new(4), 60)).unwrap();
+        let intervals = assignments(&pairs);
+        let mut expected = Vec::new();
+        for i in 0..intervals.len() {
+            for j in i + 1..intervals.len() {
+                if intervals[i].overlaps(&intervals[j]) {
+                    expected.push((i, j));
+                }
+            }
+        }
+        let mut pairs = overlapping_pairs(&intervals);
+        pairs.sort_unstable();
+        assert_eq!(pairs, expected);
+
+        let counts: Vec<usize> = (0..=100)
+            .map(|s| intervals.iter().filter(|x| x.contains_section(s)).count())
+            .collect();
+        let max = *counts.iter().max().unwrap();
+        let section = counts.iter().position(|&c| c == max).unwrap() as u32;
+        assert_eq!(most_assigned(&intervals), Some((section, max)));
+        assert_eq!(coverage(&intervals), counts.iter().filter(|&&c| c > 0).count() as u64);
+    }
+
+    #[test]
+    fn test_reader() {
+        let input = example!("test.txt");
+        let reader = std::io::BufReader::with_capacity(3, input.as_bytes());
+        assert_eq!(solution1_from(reader), Ok(2));
+        let e = solution2_from("1-2,3-4\n1-2,3\n".as_bytes()).unwrap_err();
+        assert_eq!(e.line, 2);
+        assert_eq!(e.message, "Missing section");
+        let e = solution1("1-2,4-3\n").unwrap_err();
+        assert_eq!((e.column, e.message.as_str()), (7, "Range ends before it starts"));
+    }
+}
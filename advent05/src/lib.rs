@@ -0,0 +1,380 @@
+use std::str::FromStr;
+
+use aoc_common::{config, Answer, Config, ParseError, Rng, Solution, Source};
+
+struct Move {
+    amount: usize,
+    from: usize,
+    to: usize,
+}
+
+pub type Crates = Vec<char>;
+type Stacks = Vec<Crates>;
+
+/// Stacks of crates and moves which never take more crates than a stack has.
+pub struct Plan {
+    stacks: Stacks,
+    moves: Vec<Move>,
+}
+
+/// Number of stacks given by the last line, e.g. " 1   2   3 ".
+fn parse_header(source: &Source, header: &str) -> Result<usize, ParseError> {
+    let mut count = 0;
+    for number in header.split_whitespace() {
+        count += 1;
+        if number != count.to_string() {
+            return Err(source.error(number, format!("Expected stack number {}", count)));
+        }
+    }
+    Ok(count)
+}
+
+fn parse_stacks(source: &Source, input: &str) -> Result<Stacks, ParseError> {
+    let crate_lines: Vec<&str> = input
+        .split('\n')
+        .collect();
+    let (header, crate_lines) = crate_lines.split_last().unwrap();
+    let count = parse_header(source, header)?;
+
+    let mut stacks: Stacks = vec![Vec::new(); count];
+    for crate_line in crate_lines {
+        let crates = crate_line.chars()
+            .skip(1)
+            .step_by(4)
+            .enumerate()
+            .filter(|(_i, c)| *c != ' ');
+        for (stack, c) in crates {
+            if stack >= count {
+                let at = crate_line.get(stack * 4..).unwrap_or(crate_line);
+                return Err(source.error(at, format!("Expected at most {} stacks", count)));
+            }
+            stacks[stack].insert(0, c)
+        }
+    }
+    Ok(stacks)
+}
+
+fn parse_stack_index(source: &Source, input: &str, count: usize) -> Result<usize, ParseError> {
+    let index: usize = source.parse(input)?;
+    if index == 0 || index > count {
+        return Err(source.error(input, format!("Stack must be between 1 and {}", count)));
+    }
+    Ok(index - 1)
+}
+
+/// Parses moves and checks that each takes at most the crates in its stack.
+fn parse_moves(source: &Source, input: &str, stacks: &Stacks) -> Result<Vec<Move>, ParseError> {
+    let mut heights: Vec<usize> = stacks.iter().map(Vec::len).collect();
+    input
+        .split('\n')
+        .map(|line| {
+            let mut it = line.split(' ')
+                .skip(1)
+                .step_by(2);
+            let amount_text = source.next(&mut it, line, "amount")?;
+            let m = Move {
+                amount: source.parse(amount_text)?,
+                from: parse_stack_index(source, source.next(&mut it, line, "source stack")?, stacks.len())?,
+                to: parse_stack_index(source, source.next(&mut it, line, "target stack")?, stacks.len())?,
+            };
+            if m.amount > heights[m.from] {
+                return Err(source.error(amount_text, format!(
+                    "Cannot move {} crates from stack {} with {}", m.amount, m.from + 1, heights[m.from])));
+            }
+            heights[m.from] -= m.amount;
+            heights[m.to] += m.amount;
+            Ok(m)
+        })
+        .collect()
+}
+
+impl Plan {
+    pub fn from_string(input: &str) -> Result<Plan, ParseError> {
+        let source = Source::new(Day05::DAY, input);
+        let mut parts = input
+            .trim_end()
+            .split("\n\n");
+        let stacks = source.next(&mut parts, input, "stacks")?;
+        let moves = source.next(&mut parts, stacks, "moves")?;
+
+        let stacks = parse_stacks(&source, stacks)?;
+        let moves = parse_moves(&source, moves, &stacks)?;
+        Ok(Plan { stacks, moves })
+    }
+}
+
+/// Crane model moving crates between stacks.
+pub trait Crane {
+    /// Removes `amount` crates from the stack, it has at least that many,
+    /// and returns them in the order they end up on the target stack.
+    fn lift(&self, stack: &mut Crates, amount: usize) -> Crates;
+}
+
+/// Moves one crate at a time.
+pub struct CrateMover9000;
+
+/// Moves all crates at once.
+pub struct CrateMover9001;
+
+/// Moves at most `capacity` crates at once.
+pub struct LimitedCrane {
+    pub capacity: usize,
+}
+
+/// Pulls crates from the bottom of the stack, all at once.
+pub struct BottomCrane;
+
+impl Crane for CrateMover9000 {
+    fn lift(&self, stack: &mut Crates, amount: usize) -> Crates {
+        stack.drain(stack.len() - amount..).rev().collect()
+    }
+}
+
+impl Crane for CrateMover9001 {
+    fn lift(&self, stack: &mut Crates, amount: usize) -> Crates {
+        stack.drain(stack.len() - amount..).collect()
+    }
+}
+
+impl Crane for LimitedCrane {
+    fn lift(&self, stack: &mut Crates, amount: usize) -> Crates {
+        let crates: Crates = stack.drain(stack.len() - amount..).collect();
+        crates.rchunks(self.capacity.max(1)).flatten().copied().collect()
+    }
+}
+
+impl Crane for BottomCrane {
+    fn lift(&self, stack: &mut Crates, amount: usize) -> Crates {
+        stack.drain(..amount).collect()
+    }
+}
+
+/// Crane by name: "9000", "9001", "limited:N" or "bottom".
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Model {
+    Mover9000,
+    Mover9001,
+    Limited(usize),
+    Bottom,
+}
+
+impl Model {
+    pub fn crane(self) -> Box<dyn Crane> {
+        match self {
+            Model::Mover9000 => Box::new(CrateMover9000),
+            Model::Mover9001 => Box::new(CrateMover9001),
+            Model::Limited(capacity) => Box::new(LimitedCrane { capacity }),
+            Model::Bottom => Box::new(BottomCrane),
+        }
+    }
+}
+
+impl FromStr for Model {
+    type Err = ();
+
+    fn from_str(name: &str) -> Result<Self, ()> {
+        match name {
+            "9000" => Ok(Model::Mover9000),
+            "9001" => Ok(Model::Mover9001),
+            "bottom" => Ok(Model::Bottom),
+            _ => name.strip_prefix("limited:")
+                .and_then(|capacity| capacity.parse().ok())
+                .filter(|&capacity| capacity > 0)
+                .map(Model::Limited)
+                .ok_or(()),
+        }
+    }
+}
+
+pub struct Params {
+    /// Crane for part 1.
+    pub crane1: Model,
+    /// Crane for part 2.
+    pub crane2: Model,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { crane1: Model::Mover9000, crane2: Model::Mover9001 }
+    }
+}
+
+impl Config for Params {
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "crane1" => self.crane1 = config::value(key, value)?,
+            "crane2" => self.crane2 = config::value(key, value)?,
+            _ => return Err(config::unknown(key)),
+        }
+        Ok(())
+    }
+}
+
+fn top(stacks: &Stacks) -> String {
+    stacks.iter()
+        .filter_map(|x| x.last())
+        .collect()
+}
+
+/// Crates on top of the stacks after all moves.
+pub fn move_crates(plan: &Plan, crane: &dyn Crane) -> String {
+    let mut stacks = plan.stacks.clone();
+    for m in &plan.moves {
+        let crates = crane.lift(&mut stacks[m.from], m.amount);
+        stacks[m.to].extend(crates);
+    }
+    top(&stacks)
+}
+
+/// Random stacks and moves which never take more crates than there are.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let stack_count = rng.below(11) + 2;
+    let mut heights: Vec<usize> = (0..stack_count).map(|_| rng.below(8) + 1).collect();
+    let mut out = String::new();
+    for level in (0..*heights.iter().max().unwrap()).rev() {
+        let crates: Vec<String> = heights.iter()
+            .map(|&height| if height > level {
+                format!("[{}]", (b'A' + rng.below(26) as u8) as char)
+            } else {
+                "   ".to_string()
+            })
+            .collect();
+        out += &crates.join(" ");
+        out.push('\n');
+    }
+    let numbers: Vec<String> = (1..=stack_count).map(|i| format!("{:^3}", i)).collect();
+    out += &numbers.join(" ");
+    out += "\n\n";
+
+    for _ in 0..size.max(1) {
+        let from = loop {
+            let from = rng.below(stack_count);
+            if heights[from] > 0 { break from; }
+        };
+        let to = (from + 1 + rng.below(stack_count - 1)) % stack_count;
+        let amount = rng.below(heights[from]) + 1;
+        heights[from] -= amount;
+        heights[to] += amount;
+        out += &format!("move {} from {} to {}\n", amount, from + 1, to + 1);
+    }
+    out
+}
+
+pub fn solution1(plan: &Plan) -> String {
+    move_crates(plan, &CrateMover9000)
+}
+
+pub fn solution2(plan: &Plan) -> String {
+    move_crates(plan, &CrateMover9001)
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+
+    type Input = Plan;
+    type Params = Params;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Plan::from_string(input)
+    }
+
+    fn part1(plan: &Self::Input, params: &Params) -> Result<Answer, String> {
+        Ok(move_crates(plan, params.crane1.crane().as_ref()).into())
+    }
+
+    fn part2(plan: &Self::Input, params: &Params) -> Result<Answer, String> {
+        Ok(move_crates(plan, params.crane2.crane().as_ref()).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::example;
+
+    #[test]
+    fn test_solution1() {
+        let input = example!("test.txt");
+        let plan = Plan::from_string(input).unwrap();
+        assert_eq!(solution1(&plan), "CMZ");
+    }
+
+    #[test]
+    fn test_solution2() {
+        let input = example!("test.txt");
+        let plan = Plan::from_string(input).unwrap();
+        assert_eq!(solution2(&plan), "MCD");
+    }
+
+    #[test]
+    fn test_stack_count() {
+        let plan = Plan::from_string("[A]\n 1 \n\nmove 1 from 1 to 1\n").unwrap();
+        assert_eq!(solution2(&plan), "A");
+
+        let header: Vec<_> = (1..=12).map(|i| format!("{:^3}", i)).collect();
+        let input = format!("[A]{}[L]\n{}\n\nmove 1 from 12 to 2\n", " ".repeat(41), header.join(" "));
+        let plan = Plan::from_string(&input).unwrap();
+        assert_eq!(plan.stacks.len(), 12);
+        assert_eq!(solution1(&plan), "AL");
+
+        for seed in 0..20 {
+            let plan = Plan::from_string(&generate(&mut Rng::new(seed), 30)).unwrap();
+            assert!((2..=12).contains(&plan.stacks.len()));
+            assert_eq!(solution1(&plan).len(), solution2(&plan).len());
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let e = Plan::from_string("    [D]\n[N] [C] [P]\n 1   2 \n\nmove 1 from 2 to 1\n").err().unwrap();
+        assert_eq!((e.line, e.message.as_str()), (2, "Expected at most 2 stacks"));
+        let e = Plan::from_string("[A] [B]\n 1   3 \n\nmove 1 from 2 to 1\n").err().unwrap();
+        assert_eq!((e.line, e.column, e.message.as_str()), (2, 6, "Expected stack number 2"));
+        let e = Plan::from_string("[A] [B]\n 1   2 \n\nmove 1 from 3 to 1\n").err().unwrap();
+        assert_eq!(e.message, "Stack must be between 1 and 2");
+
+        let input = example!("test.txt").replace("move 3 from 1 to 3", "move 4 from 1 to 3");
+        let e = Plan::from_string(&input).err().unwrap();
+        assert_eq!((e.line, e.column, e.width), (7, 6, 1));
+        assert_eq!(e.message, "Cannot move 4 crates from stack 1 with 3");
+    }
+
+    #[test]
+    fn test_cranes() {
+        let input = "[C]    \n[B]    \n[A]    \n 1   2 \n\nmove 2 from 1 to 2\n";
+        let plan = Plan::from_string(input).unwrap();
+        assert_eq!(move_crates(&plan, &CrateMover9000), "AB");
+        assert_eq!(move_crates(&plan, &CrateMover9001), "AC");
+        assert_eq!(move_crates(&plan, &BottomCrane), "CB");
+        assert_eq!(move_crates(&plan, &LimitedCrane { capacity: 1 }), "AB");
+
+        let plan = Plan::from_string(&input.replace("move 2", "move 3")).unwrap();
+        // Lifts B and C first, then A.
+        assert_eq!(move_crates(&plan, &LimitedCrane { capacity: 2 }), "A");
+        let mut stack = vec!['A', 'B', 'C'];
+        assert_eq!(LimitedCrane { capacity: 2 }.lift(&mut stack, 3), ['B', 'C', 'A']);
+        assert_eq!(LimitedCrane { capacity: 3 }.lift(&mut vec!['A', 'B', 'C'], 3), ['A', 'B', 'C']);
+    }
+
+    #[test]
+    fn test_params() {
+        let mut params = Params::default();
+        params.set("crane2", "limited:2").unwrap();
+        assert_eq!(params.crane2, Model::Limited(2));
+        params.set("crane1", "bottom").unwrap();
+        assert_eq!(params.crane1, Model::Bottom);
+        assert!(params.set("crane1", "limited:0").is_err());
+        assert!(params.set("crane1", "9002").is_err());
+
+        let plan = Plan::from_string(example!("test.txt")).unwrap();
+        let params = Params { crane1: Model::Limited(1), crane2: Model::Limited(3) };
+        assert_eq!(Day05::part1(&plan, &params), Ok(Answer::from("CMZ")));
+        assert_eq!(Day05::part2(&plan, &params), Ok(Answer::from("MCD")));
+    }
+}
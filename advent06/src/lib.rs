@@ -0,0 +1,110 @@
+
+use aoc_common::{Answer, ParseError, Rng, Solution, Source};
+
+fn solution(input: &str, n: usize) -> usize {
+    let bytes = input.as_bytes();
+    for i in 0..=bytes.len() - n {
+        let xs = &bytes[i..i + n];
+        if xs.iter().enumerate().all(|(i, x)| !xs[i+1..].contains(x)) {
+            return i + n
+        }
+    }
+    0
+}
+
+fn parse(input: &str) -> Result<String, ParseError> {
+    let source = Source::new(Day06::DAY, input);
+    let signal = input.trim_end();
+    if let Some((i, c)) = signal.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+        return Err(source.error(&signal[i..i + c.len_utf8()], format!("Unexpected character: {:?}", c)));
+    }
+    Ok(signal.to_string())
+}
+
+/// Noise from too few letters for a message marker, then the marker and
+/// more random letters.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut letters: Vec<char> = ('a'..='z').collect();
+    rng.shuffle(&mut letters);
+    let noise = letters[..rng.below(10) + 4].to_vec();
+    let mut signal: String = (0..size).map(|_| *rng.choose(&noise)).collect();
+    rng.shuffle(&mut letters);
+    signal.extend(&letters[..14]);
+    signal.extend((0..size).map(|_| *rng.choose(&letters)));
+    signal.push('\n');
+    signal
+}
+
+pub fn solution1(input: &str) -> usize {
+    solution(input, 4)
+}
+
+pub fn solution2(input: &str) -> usize {
+    solution(input, 14)
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+
+    type Input = String;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _params: &()) -> Result<Answer, String> {
+        Ok(solution1(input).into())
+    }
+
+    fn part2(input: &Self::Input, _params: &()) -> Result<Answer, String> {
+        Ok(solution2(input).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &()) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solution1() {
+        let input = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+        assert_eq!(solution1(input), 7);
+
+        let input = "bvwbjplbgvbhsrlpgdmjqwftvncz";
+        assert_eq!(solution1(input), 5);
+
+        let input = "nppdvjthqldpwncqszvftbrmjlhg";
+        assert_eq!(solution1(input), 6);
+
+        let input = "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg";
+        assert_eq!(solution1(input), 10);
+
+        let input = "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw";
+        assert_eq!(solution1(input), 11);
+    }
+
+    #[test]
+    fn test_solution2() {
+        let input = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+        assert_eq!(solution2(input), 19);
+
+        let input = "bvwbjplbgvbhsrlpgdmjqwftvncz";
+        assert_eq!(solution2(input), 23);
+
+        let input = "nppdvjthqldpwncqszvftbrmjlhg";
+        assert_eq!(solution2(input), 23);
+
+        let input = "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg";
+        assert_eq!(solution2(input), 29);
+
+        let input = "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw";
+        assert_eq!(solution2(input), 26);
+    }
+}
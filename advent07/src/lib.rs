@@ -0,0 +1,204 @@
+use std::collections::HashSet;
+
+use aoc_common::{config, Answer, Config, ParseError, Rng, Solution, Source};
+
+fn sizes(input: &str) -> Result<Vec<u32>, ParseError> {
+    let source = Source::new(Day07::DAY, input);
+    let mut sizes = Vec::new();
+    let mut path_length = 0;
+    for line in input.lines() {
+        if line.starts_with(|c: char| c.is_ascii_digit()) {
+            if path_length == 0 {
+                return Err(source.error(line, "File listed outside of any directory"));
+            }
+            let size = source.parse::<u32>(line.split(' ').next().unwrap_or(line))?;
+            sizes[path_length - 1] += size;
+        } else if line == "$ cd .." {
+            if path_length < 2 {
+                return Err(source.error(line, "Cannot leave the outermost directory"));
+            }
+            path_length -= 1;
+            sizes[path_length - 1] += sizes[path_length];
+        } else if line.starts_with("$ cd ") {
+            sizes.insert(path_length, 0);
+            path_length += 1;
+        }
+    }
+    if sizes.is_empty() {
+        return Err(source.error(input.trim_end(), "No directory entered"));
+    }
+    for i in 1..path_length {
+        sizes[path_length - i - 1] += sizes[path_length - i];
+    }
+    Ok(sizes)
+}
+
+fn small_dirs_size(sizes: &[u32], params: &Params) -> u32 {
+    sizes.iter()
+        .filter(|&&size| size <= params.max_dir_size)
+        .sum()
+}
+
+fn dir_to_delete_size(sizes: &[u32], params: &Params) -> Result<u32, String> {
+    let free = params.total_size.saturating_sub(sizes.iter().copied().max().unwrap_or(0));
+    let to_free = params.needed_size.saturating_sub(free);
+
+    sizes.iter().copied()
+        .filter(|&size| size >= to_free)
+        .min()
+        .ok_or_else(|| format!("No directory to delete frees {} more", to_free))
+}
+
+fn generate_name(rng: &mut Rng, names: &mut HashSet<String>) -> String {
+    loop {
+        let mut name: String = (0..rng.below(6) + 3).map(|_| (b'a' + rng.below(26) as u8) as char).collect();
+        if rng.chance(0.3) {
+            name += *rng.choose(&[".txt", ".dat", ".log"]);
+        }
+        if names.insert(name.clone()) {
+            return name;
+        }
+    }
+}
+
+/// Listing of a directory followed by its subdirectories, the last file
+/// fills the directory up to `fill_to`.
+fn generate_dir(rng: &mut Rng, depth: usize, dirs: &mut usize, total: &mut u32, fill_to: Option<u32>, budget: u32) -> String {
+    let mut names = HashSet::new();
+    let mut listing = Vec::new();
+    let mut subdirs = String::new();
+    let count = if depth < 8 { (rng.below(4) + (depth == 0) as usize).min(*dirs) } else { 0 };
+    *dirs -= count;
+    for _ in 0..count {
+        let name = generate_name(rng, &mut names);
+        listing.push(format!("dir {}", name));
+        subdirs += &format!("$ cd {}\n", name);
+        subdirs += &generate_dir(rng, depth + 1, dirs, total, None, budget);
+        subdirs += "$ cd ..\n";
+    }
+    for _ in 0..rng.below(5) {
+        let size = rng.range(1000..=250000) as u32;
+        // Keep enough space for the last file.
+        if *total + size <= budget {
+            *total += size;
+            listing.push(format!("{} {}", size, generate_name(rng, &mut names)));
+        }
+    }
+    if let Some(fill_to) = fill_to {
+        listing.push(format!("{} {}", fill_to - *total, generate_name(rng, &mut names)));
+        *total = fill_to;
+    }
+    rng.shuffle(&mut listing);
+    let listing: String = listing.iter().map(|line| format!("{}\n", line)).collect();
+    format!("$ ls\n{}{}", listing, subdirs)
+}
+
+/// Terminal session listing a directory tree which is big enough so that
+/// something needs to be deleted.
+fn generate(rng: &mut Rng, size: usize, params: &Params) -> String {
+    let free = params.total_size.saturating_sub(params.needed_size);
+    let fill_to = rng.range((free + 1).into()..=params.total_size.max(free + 1).into()) as u32;
+    // Files before the last one take at most three quarters of the space
+    // which is free after deleting something.
+    let budget = free / 4 * 3;
+    let mut dirs = size;
+    format!("$ cd /\n{}", generate_dir(rng, 0, &mut dirs, &mut 0, Some(fill_to), budget))
+}
+
+pub fn solution1(input: &str) -> Result<u32, ParseError> {
+    Ok(small_dirs_size(&sizes(input)?, &Params::default()))
+}
+
+pub fn solution2(input: &str) -> Result<u32, ParseError> {
+    let source = Source::new(Day07::DAY, input);
+    dir_to_delete_size(&sizes(input)?, &Params::default()).map_err(|e| source.error(input.trim_end(), e))
+}
+
+pub struct Params {
+    /// Maximum size of directories summed in part 1.
+    pub max_dir_size: u32,
+    /// Disk size.
+    pub total_size: u32,
+    /// Free space needed for the update.
+    pub needed_size: u32,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { max_dir_size: 100000, total_size: 70000000, needed_size: 30000000 }
+    }
+}
+
+impl Config for Params {
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "max_dir_size" => self.max_dir_size = config::value(key, value)?,
+            "total_size" => self.total_size = config::value(key, value)?,
+            "needed_size" => self.needed_size = config::value(key, value)?,
+            _ => return Err(config::unknown(key)),
+        }
+        Ok(())
+    }
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+
+    type Input = Vec<u32>;
+    type Params = Params;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        sizes(input)
+    }
+
+    fn part1(sizes: &Self::Input, params: &Params) -> Result<Answer, String> {
+        Ok(small_dirs_size(sizes, params).into())
+    }
+
+    fn part2(sizes: &Self::Input, params: &Params) -> Result<Answer, String> {
+        dir_to_delete_size(sizes, params).map(Answer::from)
+    }
+
+    fn generate(rng: &mut Rng, size: usize, params: &Params) -> String {
+        generate(rng, size, params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::example;
+
+    #[test]
+    fn test_solution1() {
+        let input = example!("test.txt");
+        assert_eq!(solution1(input), Ok(95437));
+    }
+
+    #[test]
+    fn test_solution2() {
+        let input = example!("test.txt");
+        assert_eq!(solution2(input), Ok(24933642));
+    }
+
+    #[test]
+    fn test_params() {
+        let sizes = sizes(example!("test.txt")).unwrap();
+        let mut params = Params::default();
+        params.set("max_dir_size", "1000").unwrap();
+        params.set("total_size", "50000000").unwrap();
+        assert_eq!(small_dirs_size(&sizes, &params), 584);
+        assert_eq!(dir_to_delete_size(&sizes, &params), Ok(48381165));
+        params.set("needed_size", "80000000").unwrap();
+        assert_eq!(dir_to_delete_size(&sizes, &params), Err("No directory to delete frees 78381165 more".to_string()));
+        assert!(params.set("max_dir_size", "-1").is_err());
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let e = sizes("$ ls\n").unwrap_err();
+        assert_eq!(e.message, "No directory entered");
+    }
+}
@@ -0,0 +1,114 @@
+
+use aoc_common::{Answer, Direction, Grid, ParseError, Point, Rng, Solution, Source};
+
+pub type Map = Grid<u8>;
+
+fn parse_map(input: &str) -> Result<Map, ParseError> {
+    let source = Source::new(Day08::DAY, input);
+    Grid::parse(&source, input, "tree height", |c| c.to_digit(10).map(|h| h as u8))
+}
+
+struct View {
+    from: u8,
+    count: usize,
+    max: u8,
+}
+
+impl View {
+    fn new(from: u8) -> Self {
+        View {from, count: 0, max: 0}
+    }
+}
+
+fn view(v: View, y: &u8) -> View {
+    if v.from <= v.max {
+        v
+    } else {
+        View {
+             from: v.from,
+             count: v.count + 1,
+             max: v.max.max(*y),
+        }
+    }
+}
+
+/// Trees from given position (excluding) to the edge of the map.
+fn line_of_sight(map: &Map, p: Point<usize>, direction: Direction) -> impl Iterator<Item = &u8> {
+    std::iter::successors(map.step(p, direction), move |&p| map.step(p, direction))
+        .map(|p| &map[p])
+}
+
+fn visible_trees(map: &Map) -> usize {
+    map.iter()
+        .filter(|&(p, x)| {
+            Direction::ALL.iter().any(|&d| line_of_sight(map, p, d).all(|y| y < x))
+        })
+        .count()
+}
+
+fn max_scenic_score(map: &Map) -> usize {
+    map.iter()
+        .map(|(p, x)| {
+            Direction::ALL.iter()
+                .map(|&d| line_of_sight(map, p, d).fold(View::new(*x), view).count)
+                .product()
+        })
+        .max().unwrap_or(0)
+}
+
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let side = size.max(1);
+    Grid::from_fn(side, side, |_| rng.below(10) as u8)
+        .render(|_, &height| (b'0' + height) as char)
+}
+
+pub fn solution1(input: &str) -> Result<usize, ParseError> {
+    Ok(visible_trees(&parse_map(input)?))
+}
+
+pub fn solution2(input: &str) -> Result<usize, ParseError> {
+    Ok(max_scenic_score(&parse_map(input)?))
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+
+    type Input = Map;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_map(input)
+    }
+
+    fn part1(map: &Self::Input, _params: &()) -> Result<Answer, String> {
+        Ok(visible_trees(map).into())
+    }
+
+    fn part2(map: &Self::Input, _params: &()) -> Result<Answer, String> {
+        Ok(max_scenic_score(map).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &()) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::example;
+
+    #[test]
+    fn test_solution1() {
+        let input = example!("test.txt");
+        assert_eq!(solution1(input), Ok(21));
+    }
+
+    #[test]
+    fn test_solution2() {
+        let input = example!("test.txt");
+        assert_eq!(solution2(input), Ok(8));
+    }
+}
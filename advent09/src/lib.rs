@@ -0,0 +1,183 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::io::BufRead;
+
+use aoc_common::{read_lines, Answer, Direction, ParseError, Point, Rng, Solution};
+
+type Pos = Point<i64>;
+
+struct Rope {
+    knots: Vec<Pos>,
+    visited: HashSet<Pos>,
+}
+
+impl Rope {
+    fn new(n: usize) -> Self {
+        Rope {knots: vec![Pos::default(); n], visited: HashSet::from([Pos::default()])}
+    }
+}
+
+impl fmt::Debug for Rope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Rope {{")?;
+
+        write!(f, "  knots:   {{")?;
+        for v in &self.knots {
+            write!(f, " {},{} ", v.x, v.y)?;
+        }
+        writeln!(f, "}}")?;
+
+        write!(f, "  visited: {{")?;
+        let mut vv: Vec<Pos> = self.visited.iter().cloned().collect();
+        vv.sort();
+        for v in &vv {
+            write!(f, " {},{} ", v.x, v.y)?;
+        }
+        writeln!(f, "}}")?;
+
+        write!(f, "}}")
+    }
+}
+
+fn move_head(rope: &mut Rope, direction: Direction, steps: i64) {
+    for _i in 0..steps {
+        rope.knots[0] += direction.delta();
+        for i in 0..rope.knots.len()-1 {
+            let d = rope.knots[i] - rope.knots[i + 1];
+            if d.x.abs() > 1 || d.y.abs() > 1 {
+                rope.knots[i + 1] += Point::new(d.x.signum(), d.y.signum());
+            }
+        }
+        rope.visited.insert(rope.knots[rope.knots.len() - 1]);
+    }
+}
+
+type Motion = (Direction, i64);
+
+/// Calls `f` with each motion of the head.
+fn read_motions(reader: impl BufRead, mut f: impl FnMut(Motion)) -> Result<(), ParseError> {
+    read_lines(Day09::DAY, reader, |source, line| {
+        if line.is_empty() {
+            return Ok(());
+        }
+        let mut it = line.split(' ');
+        let dir = source.next(&mut it, line, "direction")?;
+        let n = source.parse::<i64>(source.next(&mut it, line, "step count")?)?;
+        let direction = match dir {
+            "U" => Direction::Up,
+            "D" => Direction::Down,
+            "R" => Direction::Right,
+            "L" => Direction::Left,
+            _ => return Err(source.error(dir, format!("Unexpected direction: {:?}", dir))),
+        };
+        f((direction, n));
+        Ok(())
+    })
+}
+
+fn parse(input: &str) -> Result<Vec<Motion>, ParseError> {
+    parse_from(input.as_bytes())
+}
+
+fn parse_from(reader: impl BufRead) -> Result<Vec<Motion>, ParseError> {
+    let mut motions = Vec::new();
+    read_motions(reader, |motion| motions.push(motion))?;
+    Ok(motions)
+}
+
+fn tail_visited(motions: &[Motion], n: usize) -> usize {
+    let mut rope = Rope::new(n);
+    for &(direction, steps) in motions {
+        move_head(&mut rope, direction, steps);
+    }
+    rope.visited.len()
+}
+
+/// Only the positions visited by the tail are kept, not the motions.
+fn tail_visited_from(reader: impl BufRead, n: usize) -> Result<usize, ParseError> {
+    let mut rope = Rope::new(n);
+    read_motions(reader, |(direction, steps)| move_head(&mut rope, direction, steps))?;
+    Ok(rope.visited.len())
+}
+
+fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| format!("{} {}\n", rng.choose(&["U", "D", "L", "R"]), rng.range(1..=20)))
+        .collect()
+}
+
+pub fn solution1(input: &str) -> Result<usize, ParseError> {
+    solution1_from(input.as_bytes())
+}
+
+pub fn solution2(input: &str) -> Result<usize, ParseError> {
+    solution2_from(input.as_bytes())
+}
+
+pub fn solution1_from(reader: impl BufRead) -> Result<usize, ParseError> {
+    tail_visited_from(reader, 2)
+}
+
+pub fn solution2_from(reader: impl BufRead) -> Result<usize, ParseError> {
+    tail_visited_from(reader, 10)
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+
+    type Input = Vec<Motion>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn parse_from(reader: impl BufRead) -> Result<Self::Input, ParseError> {
+        parse_from(reader)
+    }
+
+    fn part1(motions: &Self::Input, _params: &()) -> Result<Answer, String> {
+        Ok(tail_visited(motions, 2).into())
+    }
+
+    fn part2(motions: &Self::Input, _params: &()) -> Result<Answer, String> {
+        Ok(tail_visited(motions, 10).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &()) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::example;
+
+    #[test]
+    fn test_solution1() {
+        let input = example!("test.txt");
+        assert_eq!(solution1(input), Ok(13));
+    }
+
+    #[test]
+    fn test_solution2() {
+        let input = example!("test.txt");
+        assert_eq!(solution2(input), Ok(1));
+
+        let input = example!("test2.txt");
+        assert_eq!(solution2(input), Ok(36));
+    }
+
+    #[test]
+    fn test_reader() {
+        let input = generate(&mut Rng::new(9), 500);
+        let motions = parse(&input).unwrap();
+        let reader = std::io::BufReader::with_capacity(8, input.as_bytes());
+        assert_eq!(solution2_from(reader), Ok(tail_visited(&motions, 10)));
+        let e = solution1_from("R 4\nU 4\nX 1\n".as_bytes()).unwrap_err();
+        assert_eq!((e.line, e.column), (3, 1));
+    }
+}
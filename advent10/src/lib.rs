@@ -0,0 +1,220 @@
+use std::io::BufRead;
+
+use aoc_common::{config, read_lines, Answer, Config, ParseError, Rng, Solution};
+
+/// Calls `f` with the value of X during each cycle.
+fn read_xs(reader: impl BufRead, mut f: impl FnMut(i64)) -> Result<(), ParseError> {
+    let mut x = 1;
+    read_lines(Day10::DAY, reader, |source, line| {
+        if line.is_empty() {
+            return Ok(());
+        }
+        let mut it = line.split(' ');
+        match source.next(&mut it, line, "instruction")? {
+            "addx" => {
+                f(x);
+                f(x);
+                x += source.parse::<i64>(source.next(&mut it, line, "value")?)?;
+            },
+            "noop" => f(x),
+            instruction => return Err(source.error(instruction, format!("Unexpected instruction: {:?}", instruction))),
+        }
+        Ok(())
+    })
+}
+
+fn get_xs(input: &str) -> Result<Vec<i64>, ParseError> {
+    get_xs_from(input.as_bytes())
+}
+
+fn get_xs_from(reader: impl BufRead) -> Result<Vec<i64>, ParseError> {
+    let mut xs = Vec::new();
+    read_xs(reader, |x| xs.push(x))?;
+    Ok(xs)
+}
+
+/// Signal strength during the cycle, counted from one, if it is measured.
+fn signal(cycle: usize, x: i64, params: &Params) -> i64 {
+    if params.cycles.contains(&cycle) { cycle as i64 * x } else { 0 }
+}
+
+fn pixel(pixel: usize, x: i64, params: &Params) -> &'static str {
+    let col = pixel % params.width;
+    let last = col + 1 == params.width;
+    if col as i64 >= x - 1 && col as i64 <= x + 1 {
+        if last { "#\n" } else { "#" }
+    } else if last { ".\n" } else { "." }
+}
+
+fn signal_strength(xs: &[i64], params: &Params) -> i64 {
+    xs.iter()
+        .enumerate()
+        .map(|(i, &x)| signal(i + 1, x, params))
+        .sum()
+}
+
+fn render(xs: &[i64], params: &Params) -> String {
+    xs.iter()
+        .enumerate()
+        .map(|(i, &x)| pixel(i, x, params))
+        .collect()
+}
+
+/// Program drawing the whole screen with the sprite staying on it, the
+/// screen size is fixed so `size` is not used.
+fn generate(rng: &mut Rng, _size: usize, params: &Params) -> String {
+    let width = params.width.max(3) as i64;
+    let mut program = String::new();
+    let (mut x, mut cycle) = (1, 0);
+    while cycle < 6 * width {
+        if rng.chance(0.3) {
+            program += "noop\n";
+            cycle += 1;
+        } else {
+            let value = loop {
+                let value = rng.range(-10..=10);
+                if value != 0 && (0..width).contains(&(x + value)) { break value; }
+            };
+            x += value;
+            program += &format!("addx {}\n", value);
+            cycle += 2;
+        }
+    }
+    program
+}
+
+pub fn solution1(input: &str) -> Result<i64, ParseError> {
+    solution1_from(input.as_bytes())
+}
+
+pub fn solution2(input: &str) -> Result<String, ParseError> {
+    solution2_from(input.as_bytes())
+}
+
+pub fn solution1_from(reader: impl BufRead) -> Result<i64, ParseError> {
+    let params = Params::default();
+    let (mut cycle, mut strength) = (0, 0);
+    read_xs(reader, |x| {
+        cycle += 1;
+        strength += signal(cycle, x, &params);
+    })?;
+    Ok(strength)
+}
+
+pub fn solution2_from(reader: impl BufRead) -> Result<String, ParseError> {
+    let params = Params::default();
+    let (mut i, mut screen) = (0, String::new());
+    read_xs(reader, |x| {
+        screen += pixel(i, x, &params);
+        i += 1;
+    })?;
+    Ok(screen)
+}
+
+pub struct Params {
+    /// Cycles (counted from one) during which signal strength is measured.
+    pub cycles: Vec<usize>,
+    /// CRT width in pixels.
+    pub width: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { cycles: vec![20, 60, 100, 140, 180, 220], width: 40 }
+    }
+}
+
+impl Config for Params {
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "cycles" => self.cycles = config::list(key, value)?,
+            "width" => match config::value(key, value)? {
+                0 => return Err("Width must be at least 1".to_string()),
+                width => self.width = width,
+            },
+            _ => return Err(config::unknown(key)),
+        }
+        Ok(())
+    }
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Input = Vec<i64>;
+    type Params = Params;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        get_xs(input)
+    }
+
+    fn parse_from(reader: impl BufRead) -> Result<Self::Input, ParseError> {
+        get_xs_from(reader)
+    }
+
+    fn part1(xs: &Self::Input, params: &Params) -> Result<Answer, String> {
+        Ok(signal_strength(xs, params).into())
+    }
+
+    fn part2(xs: &Self::Input, params: &Params) -> Result<Answer, String> {
+        Ok(Answer::glyph(render(xs, params)))
+    }
+
+    fn generate(rng: &mut Rng, size: usize, params: &Params) -> String {
+        generate(rng, size, params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::example;
+
+    #[test]
+    fn test_solution1() {
+        let input = example!("test.txt");
+        assert_eq!(solution1(input), Ok(13140));
+    }
+
+    #[test]
+    fn test_solution2() {
+        let input = example!("test.txt");
+        assert_eq!(
+            solution2(input).unwrap(), "\
+            ##..##..##..##..##..##..##..##..##..##..\n\
+            ###...###...###...###...###...###...###.\n\
+            ####....####....####....####....####....\n\
+            #####.....#####.....#####.....#####.....\n\
+            ######......######......######......####\n\
+            #######.......#######.......#######.....\n\
+            "
+        );
+    }
+
+    #[test]
+    fn test_reader() {
+        let input = example!("test.txt");
+        let reader = std::io::BufReader::with_capacity(5, input.as_bytes());
+        assert_eq!(solution2_from(reader), solution2(input));
+        assert_eq!(solution1("noop\naddx 3\n"), Ok(0));
+        let e = solution1_from("noop\naddx x\n".as_bytes()).unwrap_err();
+        assert_eq!((e.line, e.column), (2, 6));
+    }
+
+    #[test]
+    fn test_params() {
+        let xs = get_xs(example!("test.txt")).unwrap();
+        let mut params = Params::default();
+        params.set("cycles", "1,20").unwrap();
+        params.set("width", "20").unwrap();
+        assert_eq!(signal_strength(&xs, &params), 1 + 420);
+        assert!(render(&xs, &params).starts_with("##..##..##..##..##..\n"));
+        assert!(params.set("width", "0").is_err());
+        assert!(params.set("cycles", "1,x").is_err());
+
+        let input = generate(&mut Rng::new(1), 0, &params);
+        assert_eq!(render(&get_xs(&input).unwrap(), &params).lines().next().unwrap().len(), 20);
+    }
+}
@@ -0,0 +1,291 @@
+use std::mem;
+
+use aoc_common::{config, lcm, Answer, Config, ParseError, Rng, Solution, Source};
+
+#[derive(Clone, Copy)]
+enum Operation {
+    Add(u64),
+    Mul(u64),
+    Square,
+}
+
+impl Operation {
+    fn checked_apply(&self, old: u64) -> Option<u64> {
+        match self {
+            Operation::Add(operand) => old.checked_add(*operand),
+            Operation::Mul(operand) => old.checked_mul(*operand),
+            Operation::Square => old.checked_mul(old),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Monkey {
+    items: Vec<u64>,
+    operation: Operation,
+    test_div: u64,
+    throw: [usize; 2],
+    inspect_count: usize,
+}
+
+impl Monkey {
+    fn from_input(source: &Source, input: &str, monkey_count: usize) -> Result<Self, ParseError> {
+        let mut line = input.split('\n').skip(1);
+        let mut next_line = |prefix: &str| {
+            let l = source.next(&mut line, input, &format!("line {:?}", prefix))?;
+            source.strip_prefix(l, prefix)
+        };
+
+        let items = next_line("  Starting items: ")?
+            .split(", ")
+            .map(|x| source.parse::<u64>(x))
+            .collect::<Result<_, _>>()?;
+
+        let op = next_line("  Operation: new = old ")?;
+        let operation = if op == "* old" {
+            Operation::Square
+        } else if let Some(operand) = op.strip_prefix("+ ") {
+            Operation::Add(source.parse(operand)?)
+        } else if let Some(operand) = op.strip_prefix("* ") {
+            Operation::Mul(source.parse(operand)?)
+        } else {
+            return Err(source.error(op, format!("Unexpected operation: {}", op)));
+        };
+
+        let test = next_line("  Test: divisible by ")?;
+        let test_div = source.parse::<u64>(test)?;
+        if test_div == 0 {
+            return Err(source.error(test, "Cannot test divisibility by zero"));
+        }
+
+        let mut throw = [0; 2];
+        for (i, prefix) in [(1, "    If true: throw to monkey "), (0, "    If false: throw to monkey ")] {
+            let target = next_line(prefix)?;
+            throw[i] = source.parse::<usize>(target)?;
+            if throw[i] >= monkey_count {
+                return Err(source.error(target, format!("Expected monkey number less than {}", monkey_count)));
+            }
+        }
+
+        Ok(Monkey {
+            items,
+            operation,
+            test_div,
+            throw,
+            inspect_count: 0,
+        })
+    }
+}
+
+fn parse_monkeys(input: &str) -> Result<Vec<Monkey>, ParseError> {
+    let source = Source::new(Day11::DAY, input);
+    let blocks: Vec<&str> = input
+        .trim_end()
+        .split("\n\n")
+        .collect();
+    blocks
+        .iter()
+        .map(|block| Monkey::from_input(&source, block, blocks.len()))
+        .collect()
+}
+
+fn solution<Transform: Fn(u64) -> u64>(
+    monkeys: &mut [Monkey],
+    rounds: usize,
+    transform: Transform,
+) -> Result<usize, String> {
+    for round in 0..rounds {
+        for i in 0..monkeys.len() {
+            let mut items = Vec::<u64>::new();
+            mem::swap(&mut monkeys[i].items, &mut items);
+            monkeys[i].inspect_count += items.len();
+            for old in items {
+                let item = monkeys[i].operation.checked_apply(old)
+                    .ok_or_else(|| format!("Monkey {}: Worry level overflows in round {}", i, round + 1))?;
+                let item = transform(item);
+                let target_monkey = monkeys[i].throw[item.is_multiple_of(monkeys[i].test_div) as usize];
+                monkeys[target_monkey].items.push(item);
+            }
+        }
+    }
+
+    let mut counts: Vec<usize> = monkeys.iter().map(|m| m.inspect_count).collect();
+    counts.sort_unstable_by(|a, b| b.cmp(a));
+    Ok(counts.iter().take(2).product())
+}
+
+fn monkey_business(monkeys: &[Monkey], params: &Params) -> Result<usize, String> {
+    let mut monkeys = monkeys.to_vec();
+    solution(&mut monkeys, params.rounds1, |x| x / 3)
+}
+
+fn monkey_business_without_relief(monkeys: &[Monkey], params: &Params) -> Result<usize, String> {
+    let mut monkeys = monkeys.to_vec();
+    let d = monkeys.iter().map(|m| m.test_div).fold(1, lcm);
+    solution(&mut monkeys, params.rounds2, |x| x % d)
+}
+
+/// Whether any worry level overflows in the first part.
+fn worry_overflows(monkeys: &[Monkey], rounds: usize) -> bool {
+    let mut items: Vec<Vec<u64>> = monkeys.iter().map(|m| m.items.clone()).collect();
+    for _round in 0..rounds {
+        for (i, monkey) in monkeys.iter().enumerate() {
+            for old in mem::take(&mut items[i]) {
+                let Some(item) = monkey.operation.checked_apply(old) else { return true };
+                let item = item / 3;
+                items[monkey.throw[item.is_multiple_of(monkey.test_div) as usize]].push(item);
+            }
+        }
+    }
+    false
+}
+
+fn generate_monkey(rng: &mut Rng, index: usize, count: usize, test_div: u64, multiply: bool) -> Monkey {
+    let operation = match rng.below(8) {
+        0 if multiply => Operation::Square,
+        1 | 2 if multiply => Operation::Mul(rng.range(2..=19) as u64),
+        _ => Operation::Add(rng.range(1..=8) as u64),
+    };
+    let targets: Vec<usize> = (0..count).filter(|&i| i != index).collect();
+    let on_true = rng.below(targets.len());
+    let on_false = (on_true + 1 + rng.below(targets.len().max(2) - 1)) % targets.len();
+    Monkey {
+        items: (0..rng.below(8) + 1).map(|_| rng.range(50..=99) as u64).collect(),
+        operation,
+        test_div,
+        throw: [targets[on_false], targets[on_true]],
+        inspect_count: 0,
+    }
+}
+
+/// Monkeys testing divisibility by distinct primes, worry levels in the
+/// first part fit into `u64`.
+fn generate(rng: &mut Rng, size: usize, params: &Params) -> String {
+    let count = (size / 10).clamp(2, 8);
+    let mut primes = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+    let mut attempt = 0;
+    let monkeys = loop {
+        rng.shuffle(&mut primes);
+        // Give up on multiplication if worry levels keep overflowing.
+        attempt += 1;
+        let multiply = attempt < 100;
+        let monkeys: Vec<Monkey> = (0..count)
+            .map(|i| generate_monkey(rng, i, count, primes[i], multiply))
+            .collect();
+        if !worry_overflows(&monkeys, params.rounds1) { break monkeys; }
+    };
+
+    monkeys.iter()
+        .enumerate()
+        .map(|(i, monkey)| {
+            let items: Vec<String> = monkey.items.iter().map(|item| item.to_string()).collect();
+            let operation = match monkey.operation {
+                Operation::Add(operand) => format!("+ {}", operand),
+                Operation::Mul(operand) => format!("* {}", operand),
+                Operation::Square => "* old".to_string(),
+            };
+            format!(
+                "Monkey {}:\n  Starting items: {}\n  Operation: new = old {}\n  Test: divisible by {}\n    \
+                 If true: throw to monkey {}\n    If false: throw to monkey {}\n",
+                i, items.join(", "), operation, monkey.test_div, monkey.throw[1], monkey.throw[0])
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Overflowing worry levels are reported at the whole input.
+fn solve(input: &str, f: fn(&[Monkey], &Params) -> Result<usize, String>) -> Result<usize, ParseError> {
+    let source = Source::new(Day11::DAY, input);
+    f(&parse_monkeys(input)?, &Params::default()).map_err(|e| source.error(input.trim_end(), e))
+}
+
+pub fn solution1(input: &str) -> Result<usize, ParseError> {
+    solve(input, monkey_business)
+}
+
+pub fn solution2(input: &str) -> Result<usize, ParseError> {
+    solve(input, monkey_business_without_relief)
+}
+
+pub struct Params {
+    /// Rounds with relief (worry level divided by three).
+    pub rounds1: usize,
+    /// Rounds without relief.
+    pub rounds2: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { rounds1: 20, rounds2: 10000 }
+    }
+}
+
+impl Config for Params {
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "rounds1" => self.rounds1 = config::value(key, value)?,
+            "rounds2" => self.rounds2 = config::value(key, value)?,
+            _ => return Err(config::unknown(key)),
+        }
+        Ok(())
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Input = Vec<Monkey>;
+    type Params = Params;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_monkeys(input)
+    }
+
+    fn part1(monkeys: &Self::Input, params: &Params) -> Result<Answer, String> {
+        monkey_business(monkeys, params).map(Answer::from)
+    }
+
+    fn part2(monkeys: &Self::Input, params: &Params) -> Result<Answer, String> {
+        monkey_business_without_relief(monkeys, params).map(Answer::from)
+    }
+
+    fn generate(rng: &mut Rng, size: usize, params: &Params) -> String {
+        generate(rng, size, params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::example;
+
+    #[test]
+    fn test_solution1() {
+        let input = example!("test.txt");
+        assert_eq!(solution1(input), Ok(10605));
+    }
+
+    #[test]
+    fn test_solution2() {
+        let input = example!("test.txt");
+        assert_eq!(solution2(input), Ok(2713310158));
+    }
+
+    #[test]
+    fn test_params() {
+        let monkeys = parse_monkeys(example!("test.txt")).unwrap();
+        let mut params = Params::default();
+        params.set("rounds1", "1").unwrap();
+        params.set("rounds2", "20").unwrap();
+        assert_eq!(monkey_business(&monkeys, &params), Ok(5 * 4));
+        assert_eq!(monkey_business_without_relief(&monkeys, &params), Ok(103 * 99));
+        assert!(params.set("rounds", "1").is_err());
+
+        params.set("rounds1", "100").unwrap();
+        params.set("rounds2", "0").unwrap();
+        assert_eq!(monkey_business(&monkeys, &params), Err("Monkey 2: Worry level overflows in round 92".to_string()));
+        assert_eq!(monkey_business_without_relief(&monkeys, &params), Ok(0));
+    }
+}
@@ -0,0 +1,210 @@
+use aoc_common::parallel;
+use aoc_common::search::{bfs, Outcome};
+use aoc_common::{Answer, Direction, Grid, ParseError, Point, Rng, Solution, Source};
+
+type Pos = Point<usize>;
+
+pub struct Map {
+    m: Grid<char>
+}
+
+impl Map {
+    fn from(input: &str) -> Result<Self, ParseError> {
+        let source = Source::new(Day12::DAY, input);
+        let m = Grid::parse(&source, input, "elevation", |c| {
+            Some(c).filter(|c| c.is_ascii_lowercase() || *c == 'S' || *c == 'E')
+        })?;
+        if !input.contains('S') {
+            return Err(source.error(input.trim_end(), "Missing start position 'S'"));
+        }
+        Ok(Map { m })
+    }
+
+    fn at(&self, p: Pos) -> char {
+        self.m[p]
+    }
+
+    fn start(&self) -> Option<Pos> {
+        self.m.position(|&c| c == 'S')
+    }
+
+    fn neighbors(&self, p: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.m.neighbors4(p)
+    }
+
+    fn climbable(&self, p: Pos) -> impl Iterator<Item = Pos> + '_ {
+        let a = height(self.at(p));
+        self.neighbors(p).filter(move |&p2| height(self.at(p2)) <= a + 1)
+    }
+
+    fn route(&self, start: Pos) -> Outcome<Pos, usize> {
+        bfs([start], |&p| self.climbable(p), |&p| self.at(p) == 'E')
+    }
+
+    fn steps(&self, start: Pos) -> Option<usize> {
+        self.route(start).found.map(|found| found.cost)
+    }
+
+    /// Map with arrows along the path.
+    fn render(&self, path: &[Pos]) -> String {
+        let mut arrows = Grid::new(self.m.width(), self.m.height(), '.');
+        for step in path.windows(2) {
+            let direction = Direction::ALL.into_iter()
+                .find(|&d| self.m.step(step[0], d) == Some(step[1]))
+                .expect("Path consists of adjacent positions");
+            arrows[step[0]] = direction.arrow();
+        }
+        if let Some(&end) = path.last() {
+            arrows[end] = self.at(end);
+        }
+        arrows.render(|_, &c| c)
+    }
+}
+
+fn height(c: char) -> u8 {
+    match c {
+        'S' => b'a',
+        'E' => b'z',
+        _ => c as u8,
+    }
+}
+
+fn fewest_steps(map: &Map) -> Result<usize, String> {
+    let start = map.start().unwrap();
+    map.steps(start).ok_or_else(|| "No route from the start to the end".to_string())
+}
+
+fn fewest_steps_from_any(map: &Map) -> Result<usize, String> {
+    // Visit only 'a' positions which have at least one non-'a' neighbor.
+    let starts: Vec<Pos> = map.m.points()
+        .filter(|&pos| height(map.at(pos)) == b'a' && !map.neighbors(pos).all(|p| height(map.at(p)) == b'a'))
+        .collect();
+    parallel::map(&starts, |&pos| map.steps(pos)).into_iter().flatten().min()
+        .ok_or_else(|| "No route from any lowest position to the end".to_string())
+}
+
+/// Map with a route climbing from the start in the top left corner to the
+/// end in the bottom right corner.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let (width, height) = (size.max(26), (size / 4).max(5));
+    // Elevation on any route going only right and down grows at most by one.
+    let elevation = |p: Pos| ((p.x + p.y) * 25 / (width + height - 2)) as u8;
+    let mut m = Grid::from_fn(width, height, |p| elevation(p).saturating_sub(rng.below(5) as u8));
+    let mut p = Point::new(0, 0);
+    while p != Point::new(width - 1, height - 1) {
+        m[p] = elevation(p);
+        if p.y == height - 1 || (p.x < width - 1 && rng.chance(0.5)) {
+            p.x += 1;
+        } else {
+            p.y += 1;
+        }
+    }
+    m.render(|p, &h| match (p.x, p.y) {
+        (0, 0) => 'S',
+        (x, y) if x == width - 1 && y == height - 1 => 'E',
+        _ => (b'a' + h) as char,
+    })
+}
+
+/// Without a route, the error points at the start or the end, if any.
+fn solve(input: &str, f: fn(&Map) -> Result<usize, String>, at: char) -> Result<usize, ParseError> {
+    let source = Source::new(Day12::DAY, input);
+    f(&Map::from(input)?).map_err(|e| {
+        let at = input.find(at).map_or(input.trim_end(), |i| &input[i..i + 1]);
+        source.error(at, e)
+    })
+}
+
+pub fn solution1(input: &str) -> Result<usize, ParseError> {
+    solve(input, fewest_steps, 'S')
+}
+
+pub fn solution2(input: &str) -> Result<usize, ParseError> {
+    solve(input, fewest_steps_from_any, 'E')
+}
+
+/// Map with the shortest hiking route from the start, if there is any.
+pub fn hiking_route(input: &str) -> Result<Option<String>, ParseError> {
+    let map = Map::from(input)?;
+    let start = map.start().unwrap();
+    Ok(map.route(start).found.map(|found| map.render(&found.path)))
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+
+    type Input = Map;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Map::from(input)
+    }
+
+    fn part1(map: &Self::Input, _params: &()) -> Result<Answer, String> {
+        fewest_steps(map).map(Answer::from)
+    }
+
+    fn part2(map: &Self::Input, _params: &()) -> Result<Answer, String> {
+        fewest_steps_from_any(map).map(Answer::from)
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &()) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::example;
+
+    #[test]
+    fn test_map_start1() {
+        let map = Map::from("\
+            aaa\n\
+            aaa\n\
+            aSa\n\
+            aaa\n\
+        ").unwrap();
+        assert_eq!(map.start(), Some(Point::new(1, 2)));
+    }
+
+    #[test]
+    fn test_map_start2() {
+        let input = example!("test.txt");
+        let map = Map::from(input).unwrap();
+        assert_eq!(map.start(), Some(Point::new(0, 0)));
+    }
+
+    #[test]
+    fn test_solution1() {
+        let input = example!("test.txt");
+        assert_eq!(solution1(input), Ok(31));
+    }
+
+    #[test]
+    fn test_hiking_route() {
+        let input = example!("test.txt");
+        let route = hiking_route(input).unwrap().unwrap();
+        assert_eq!(route.lines().count(), 5);
+        assert_eq!(route.chars().filter(|c| "^>v<".contains(*c)).count(), 31);
+        assert_eq!(route.find('E'), input.find('E'));
+        assert!(route.starts_with('v') || route.starts_with('>'));
+
+        assert_eq!(hiking_route("Saz\nzzE\n"), Ok(None));
+        let e = solution1("Saz\nzzE\n").unwrap_err();
+        assert_eq!((e.line, e.column), (1, 1));
+        assert_eq!(e.message, "No route from the start to the end");
+        let e = solution2("Saz\nzzE\n").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 3));
+        assert_eq!(e.message, "No route from any lowest position to the end");
+    }
+
+    #[test]
+    fn test_solution2() {
+        let input = example!("test.txt");
+        assert_eq!(solution2(input), Ok(29));
+    }
+}
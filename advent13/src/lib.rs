@@ -0,0 +1,242 @@
+use std::cmp::Ordering;
+
+use aoc_common::{Answer, ParseError, Rng, Solution, Source};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Node {
+    Value(u8),
+    Nodes(Vec<Node>),
+}
+
+macro_rules! nodes {
+    ( $( $x:expr ),* ) => {
+        Node::Nodes(vec![
+            $( $x, )*
+        ])
+    };
+}
+
+macro_rules! values {
+    ( $( $x:expr ),* ) => {
+        Node::Nodes(vec![
+            $( Node::Value($x), )*
+        ])
+    };
+}
+
+impl Node {
+    pub fn from(line: &str) -> Result<Self, ParseError> {
+        Self::parse(&Source::new(Day13::DAY, line), line)
+    }
+
+    fn parse(source: &Source, line: &str) -> Result<Self, ParseError> {
+        let mut stack: Vec<Vec<Node>> = vec![];
+        let mut nodes: Vec<Node> = vec![];
+        let mut maybe_value: Option<u8> = None;
+        for (i, c) in line.char_indices() {
+            let at = &line[i..i + c.len_utf8()];
+            if let Some(d) = c.to_digit(10) {
+                maybe_value = maybe_value.unwrap_or(0)
+                    .checked_mul(10)
+                    .and_then(|v| v.checked_add(d as u8));
+                if maybe_value.is_none() {
+                    return Err(source.error(at, "Value is too large"));
+                }
+            } else {
+                if let Some(v) = maybe_value {
+                    nodes.push(Node::Value(v));
+                    maybe_value = None;
+                }
+
+                match c {
+                    '[' => {
+                        stack.push(nodes);
+                        nodes = vec![];
+                    },
+                    ']' => {
+                        let mut parent = stack.pop()
+                            .ok_or_else(|| source.error(at, "Unmatched ']'"))?;
+                        parent.push(Node::Nodes(nodes));
+                        nodes = parent;
+                    },
+                    ',' => {},
+                    _ => return Err(source.error(at, format!("Unexpected char: {}", c))),
+                }
+            }
+        }
+        if !stack.is_empty() {
+            return Err(source.missing(line, "']'"));
+        }
+        nodes.pop().ok_or_else(|| source.missing(line, "packet"))
+    }
+}
+
+impl PartialOrd for Node {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Node {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::Value(l), Self::Value(r)) => l.cmp(r),
+            (Self::Value(l), Self::Nodes(_)) =>
+                Self::Nodes(vec![Self::Value(*l)]).cmp(other),
+            (Self::Nodes(_), Self::Value(v)) =>
+                self.cmp(&Self::Nodes(vec![Self::Value(*v)])),
+            (Self::Nodes(l), Self::Nodes(r)) => {
+                let ord = l.iter().zip(r.iter()).find_map(|(l, r)| {
+                        let ord = l.cmp(r);
+                        if ord == Ordering::Equal {
+                            None
+                        } else {
+                            Some(ord)
+                        }
+                    }).unwrap_or(Ordering::Equal);
+                if ord == Ordering::Equal {
+                    l.len().cmp(&r.len())
+                } else {
+                    ord
+                }
+            },
+        }
+    }
+}
+
+fn parse(input: &str) -> Result<Vec<Node>, ParseError> {
+    let source = Source::new(Day13::DAY, input);
+    let packets = input
+        .trim_end()
+        .split('\n')
+        .filter(|line| !line.is_empty())
+        .map(|line| Node::parse(&source, line))
+        .collect::<Result<Vec<Node>, _>>()?;
+    if packets.len() % 2 != 0 {
+        return Err(source.missing(input.trim_end(), "second packet of a pair"));
+    }
+    Ok(packets)
+}
+
+fn ordered_pairs(packets: &[Node]) -> usize {
+    packets
+        .chunks(2)
+        .enumerate()
+        .filter(|(_i, pair)| pair[0] < pair[1])
+        .map(|(i, _)| i + 1)
+        .sum()
+}
+
+fn divs() -> [Node; 2] {
+    [
+        nodes![values![2]],
+        nodes![values![6]],
+    ]
+}
+
+fn decoder_key(packets: &[Node]) -> usize {
+    let mut nodes = packets.to_vec();
+
+    for div in divs() {
+        nodes.push(div);
+    }
+
+    nodes.sort();
+
+    divs().iter().map(|x| {
+        nodes.iter().position(|y| x == y).unwrap() + 1
+    }).product()
+}
+
+fn generate_packet(rng: &mut Rng, depth: usize) -> String {
+    let items: Vec<String> = (0..rng.below(6))
+        .map(|_| if depth < 4 && rng.chance(0.3) {
+            generate_packet(rng, depth + 1)
+        } else {
+            rng.range(0..=10).to_string()
+        })
+        .collect();
+    format!("[{}]", items.join(","))
+}
+
+fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| format!("{}\n{}\n", generate_packet(rng, 0), generate_packet(rng, 0)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn solution1(input: &str) -> Result<usize, ParseError> {
+    Ok(ordered_pairs(&parse(input)?))
+}
+
+pub fn solution2(input: &str) -> Result<usize, ParseError> {
+    Ok(decoder_key(&parse(input)?))
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+
+    type Input = Vec<Node>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(packets: &Self::Input, _params: &()) -> Result<Answer, String> {
+        Ok(ordered_pairs(packets).into())
+    }
+
+    fn part2(packets: &Self::Input, _params: &()) -> Result<Answer, String> {
+        Ok(decoder_key(packets).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &()) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::example;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Node::from("[1,2]"), Ok(values![1, 2]));
+        assert_eq!(Node::from("[1,10,2]"), Ok(values![1, 10, 2]));
+        assert_eq!(Node::from("[[1]]"), Ok(nodes![values![1]]));
+        assert_eq!(Node::from("[[1],[2]]"), Ok(nodes![values![1], values![2]]));
+        assert_eq!(Node::from("[[1],2]"), Ok(nodes![values![1], Node::Value(2)]));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let e = Node::from("[1,x]").unwrap_err();
+        assert_eq!((e.line, e.column), (1, 4));
+        assert_eq!(e.message, "Unexpected char: x");
+
+        let e = Node::from("[[1]").unwrap_err();
+        assert_eq!((e.line, e.column), (1, 5));
+        assert_eq!(e.message, "Missing ']'");
+
+        let e = parse("[1]\n[2]\n\n[3]\n[1]]\n").unwrap_err();
+        assert_eq!((e.line, e.column), (5, 4));
+        assert_eq!(e.message, "Unmatched ']'");
+    }
+
+    #[test]
+    fn test_solution1() {
+        let input = example!("test.txt");
+        assert_eq!(solution1(input), Ok(13));
+    }
+
+    #[test]
+    fn test_solution2() {
+        let input = example!("test.txt");
+        assert_eq!(solution2(input), Ok(140));
+    }
+}
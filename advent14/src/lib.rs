@@ -0,0 +1,168 @@
+use aoc_common::{Answer, ParseError, Point, Rng, Solution, Source, SparseGrid};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Tile {Rock, Sand}
+type Pos = Point;
+type Map = SparseGrid<Tile>;
+const SAND: Pos = Point::new(500, 0);
+
+fn parse_map(input: &str) -> Result<Map, ParseError> {
+    let source = Source::new(Day14::DAY, input);
+    let mut map = Map::new();
+    for line in input.trim_end().split('\n') {
+        let ps: Vec<(Pos, &str)> = line.split(" -> ")
+            .map(|pos| {
+                let mut it = pos.split(',');
+                let x = source.parse::<u16>(source.next(&mut it, pos, "x coordinate")?)?;
+                let y = source.parse::<u16>(source.next(&mut it, pos, "y coordinate")?)?;
+                Ok((Point::new(x.into(), y.into()), pos))
+            }).collect::<Result<_, ParseError>>()?;
+        for p2 in ps.windows(2) {
+            let (Point { x: x1, y: y1 }, _) = p2[0];
+            let (Point { x: x2, y: y2 }, at) = p2[1];
+            if x1 != x2 && y1 != y2 {
+                return Err(source.error(at, "Expected horizontal or vertical line"));
+            }
+            if x1 < x2 {
+                map.extend((x1..=x2).map(|x| (Point::new(x, y2), Tile::Rock)));
+            } else if x1 > x2 {
+                map.extend((x2..=x1).map(|x| (Point::new(x, y2), Tile::Rock)));
+            } else if y1 < y2 {
+                map.extend((y1..=y2).map(|y| (Point::new(x2, y), Tile::Rock)));
+            } else {
+                map.extend((y2..=y1).map(|y| (Point::new(x2, y), Tile::Rock)));
+            }
+        }
+    }
+    Ok(map)
+}
+
+fn solution(map: &mut Map) -> usize {
+    let block_count = map.len();
+    let max_y = map.bounding_box().unwrap().max.y;
+    let mut land_y = map.points()
+        .filter(|p| p.x == SAND.x)
+        .min_by_key(|p| {
+            p.y - SAND.y
+        }).unwrap().y;
+
+    loop {
+        let mut p = Point::new(SAND.x, land_y);
+
+        loop {
+            if !map.contains(p) {
+                p.y += 1;
+                if p.y > max_y {
+                    return map.len() - block_count;
+                }
+            } else if !map.contains(p + Point::new(-1, 0)) {
+                p.x -= 1;
+            } else if !map.contains(p + Point::new(1, 0)) {
+                p.x += 1;
+            } else {
+                break;
+            }
+        }
+
+        p.y -= 1;
+        land_y = land_y.min(p.y);
+        map.insert(p, Tile::Sand);
+
+        if p == SAND {
+            return map.len() - block_count;
+        }
+    }
+}
+
+fn sand_until_abyss(map: &Map) -> usize {
+    let mut map = map.clone();
+    solution(&mut map)
+}
+
+fn sand_until_blocked(map: &Map) -> usize {
+    let mut map = map.clone();
+
+    // Add floor
+    let max_y = map.bounding_box().unwrap().max.y + 2;
+    map.extend((0..=1000).map(|x| (Point::new(x, max_y), Tile::Rock)));
+
+    solution(&mut map)
+}
+
+/// Rock paths below the sand source, the first one right under it.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|i| {
+            let mut p = Point::new(rng.range(460..=540) as i32, rng.range(13..=170) as i32);
+            let mut horizontal = i == 0 || rng.chance(0.5);
+            if i == 0 {
+                p.x = SAND.x - rng.range(0..=8) as i32;
+            }
+            let mut path = vec![p];
+            for _ in 0..rng.below(4) + 1 {
+                let step = rng.range(1..=8) as i32;
+                if i == 0 && path.len() == 1 {
+                    p.x = SAND.x + step;
+                } else {
+                    let step = if rng.chance(0.5) { step } else { -step };
+                    if horizontal { p.x += step } else { p.y += step }
+                }
+                horizontal = !horizontal;
+                path.push(p);
+            }
+            let path: Vec<String> = path.iter().map(|p| format!("{},{}", p.x, p.y)).collect();
+            format!("{}\n", path.join(" -> "))
+        })
+        .collect()
+}
+
+pub fn solution1(input: &str) -> Result<usize, ParseError> {
+    Ok(sand_until_abyss(&parse_map(input)?))
+}
+
+pub fn solution2(input: &str) -> Result<usize, ParseError> {
+    Ok(sand_until_blocked(&parse_map(input)?))
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+
+    type Input = Map;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_map(input)
+    }
+
+    fn part1(map: &Self::Input, _params: &()) -> Result<Answer, String> {
+        Ok(sand_until_abyss(map).into())
+    }
+
+    fn part2(map: &Self::Input, _params: &()) -> Result<Answer, String> {
+        Ok(sand_until_blocked(map).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &()) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::example;
+
+    #[test]
+    fn test_solution1() {
+        let input = example!("test.txt");
+        assert_eq!(solution1(input), Ok(24));
+    }
+
+    #[test]
+    fn test_solution2() {
+        let input = example!("test.txt");
+        assert_eq!(solution2(input), Ok(93));
+    }
+}
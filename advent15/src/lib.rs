@@ -0,0 +1,291 @@
+
+use std::collections::HashSet;
+
+use aoc_common::{config, Answer, Config, ParseError, Point, Rng, Solution, Source};
+
+type Pos = Point<i32>;
+#[derive(Debug)]
+pub struct Sensor {
+    pos: Pos,
+    beacon: Pos,
+    distance: i32,
+}
+
+impl Sensor {
+    fn from(source: &Source, line: &str) -> Result<Self, ParseError> {
+        let mut it = line
+            .split(&['=', ',', ':'][..])
+            .skip(1)
+            .step_by(2);
+        let mut coordinate = |what| source.parse::<i32>(source.next(&mut it, line, what)?);
+        let pos = Point::new(coordinate("sensor x")?, coordinate("sensor y")?);
+        let beacon = Point::new(coordinate("beacon x")?, coordinate("beacon y")?);
+        let distance = pos.manhattan(beacon) as i32;
+        Ok(Sensor {pos, beacon, distance})
+    }
+
+    fn covers(&self, p: Pos) -> bool {
+        self.pos.manhattan(p) <= self.distance as u64
+    }
+}
+
+fn parse(input: &str) -> Result<Vec<Sensor>, ParseError> {
+    let source = Source::new(Day15::DAY, input);
+    input
+        .trim_end()
+        .split('\n')
+        .map(|line| Sensor::from(&source, line))
+        .collect()
+}
+
+fn covered_in_row(sensors: &[Sensor], row: i32) -> usize {
+    let mut ranges: Vec<_> = sensors.iter()
+        .filter_map(|sensor| {
+            let d = sensor.distance - sensor.pos.y.abs_diff(row) as i32;
+            (d >= 0).then_some((sensor.pos.x - d, sensor.pos.x + d))
+        })
+        .collect();
+    ranges.sort();
+
+    // Ranges may overlap or leave gaps in-between.
+    let mut covered = 0;
+    let mut next_x = i32::MIN;
+    for (x1, x2) in ranges {
+        let x1 = x1.max(next_x);
+        if x1 <= x2 {
+            covered += (x2 - x1 + 1) as usize;
+            next_x = x2 + 1;
+        }
+    }
+
+    let beacons: HashSet<_> = sensors.iter()
+        .map(|sensor| sensor.beacon)
+        .filter(|beacon| beacon.y == row)
+        .collect();
+    covered - beacons.len()
+}
+
+fn tuning_frequency(sensors: &[Sensor], max: i32) -> Result<i64, String> {
+    // An uncovered location is either right next to the right edge of a
+    // sensor's area, or just below the area in the first column, or in the
+    // top left corner.
+    let right_of_areas = sensors.iter().flat_map(|sensor| {
+        let Point { x, y } = sensor.pos;
+        let below = y.max(0)..=(y + sensor.distance).min(max);
+        let above = (y - sensor.distance).max(0)..y.min(max + 1);
+        below.chain(above).map(move |y2| {
+            Point::new(x + sensor.distance - y.abs_diff(y2) as i32 + 1, y2)
+        })
+    });
+    let below_areas = sensors.iter().map(|sensor| {
+        Point::new(0, sensor.pos.y + sensor.distance - sensor.pos.x.abs() + 1)
+    });
+    right_of_areas
+        .chain(below_areas)
+        .chain([Point::new(0, 0)])
+        .filter(|p| (0..=max).contains(&p.x) && (0..=max).contains(&p.y))
+        .find(|&p| !sensors.iter().any(|sensor| sensor.covers(p)))
+        .map(|p| p.x as i64 * 4000000 + p.y as i64)
+        .ok_or_else(|| "No uncovered position in the search area".to_string())
+}
+
+/// Sensors leaving exactly one position in the search area uncovered.
+///
+/// Four sensors reach the position from the sides and four more along the
+/// diagonals, together they cover everything else. The sensor on the left
+/// comes first so that the search finds the position right away.
+fn generate(rng: &mut Rng, size: usize, params: &Params) -> String {
+    let max = params.max.max(1);
+    let mut coordinate = |max: i32| rng.range(0..=max as i64) as i32;
+    let hidden = loop {
+        let p = Point::new(coordinate(max), coordinate(max - 1));
+        if p.y != params.row { break p; }
+    };
+    let k = max + 1 + coordinate(max / 4);
+    let a = max + 1 + coordinate(max / 4);
+    let mut sensors = vec![
+        (hidden - Point::new(k, 0), k - 1),
+        (hidden + Point::new(k, 0), k - 1),
+        (hidden - Point::new(0, k), k - 1),
+        (hidden + Point::new(0, k), k - 1),
+    ];
+    for (dx, dy) in [(a, a), (a, -a), (-a, a), (-a, -a)] {
+        sensors.push((hidden + Point::new(dx, dy), 2 * a - 2));
+    }
+    for _ in 0..size {
+        let pos = Point::new(coordinate(max), coordinate(max));
+        let distance = pos.manhattan(hidden) as i32;
+        if distance >= 2 {
+            sensors.push((pos, distance - 1 - coordinate(distance / 4)));
+        }
+    }
+    rng.shuffle(&mut sensors[1..]);
+
+    sensors.iter()
+        .map(|&(pos, distance)| {
+            let dx = rng.range(-distance as i64..=distance as i64) as i32;
+            let dy = (distance - dx.abs()) * if rng.chance(0.5) { 1 } else { -1 };
+            format!("Sensor at x={}, y={}: closest beacon is at x={}, y={}\n",
+                pos.x, pos.y, pos.x + dx, pos.y + dy)
+        })
+        .collect()
+}
+
+pub fn solution1(input: &str, row: i32) -> Result<usize, ParseError> {
+    Ok(covered_in_row(&parse(input)?, row))
+}
+
+pub fn solution2(input: &str, max: i32) -> Result<i64, ParseError> {
+    let source = Source::new(Day15::DAY, input);
+    tuning_frequency(&parse(input)?, max).map_err(|e| source.error(input.trim_end(), e))
+}
+
+pub struct Params {
+    /// Row to count covered positions in.
+    pub row: i32,
+    /// Maximum coordinate of the distress beacon.
+    pub max: i32,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { row: 2000000, max: 4000000 }
+    }
+}
+
+impl Config for Params {
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "row" => self.row = config::value(key, value)?,
+            "max" => self.max = config::value(key, value)?,
+            _ => return Err(config::unknown(key)),
+        }
+        Ok(())
+    }
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+
+    type Input = Vec<Sensor>;
+    type Params = Params;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(sensors: &Self::Input, params: &Params) -> Result<Answer, String> {
+        Ok(covered_in_row(sensors, params.row).into())
+    }
+
+    fn part2(sensors: &Self::Input, params: &Params) -> Result<Answer, String> {
+        tuning_frequency(sensors, params.max).map(Answer::from)
+    }
+
+    fn generate(rng: &mut Rng, size: usize, params: &Params) -> String {
+        generate(rng, size, params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::{example, property};
+
+    #[test]
+    fn test_solution1() {
+        let input = example!("test.txt");
+        assert_eq!(solution1(input, 10), Ok(26));
+    }
+
+    #[test]
+    fn test_solution2() {
+        let input = example!("test.txt");
+        assert_eq!(solution2(input, 20), Ok(56000011));
+
+        let e = solution2(input, 2).unwrap_err();
+        assert_eq!(e.message, "No uncovered position in the search area");
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "\
+            Sensor at x=2, y=18: closest beacon is at x=-2, y=15\n\
+            Sensor at x=9, y=16: closest beacon is at x=10, y=a\n\
+            Sensor at x=13, y=2: closest beacon is at x=15\n";
+
+        let e = parse(input).unwrap_err();
+        assert_eq!((e.line, e.column), (2, 51));
+        assert_eq!(e.snippet, "Sensor at x=9, y=16: closest beacon is at x=10, y=a");
+
+        let e = parse(&input.replace("y=a", "y=16")).unwrap_err();
+        assert_eq!((e.line, e.column), (3, 47));
+        assert_eq!(e.message, "Missing beacon y");
+    }
+
+    /// Positions in the row which cannot contain a beacon, checked one by one.
+    fn covered_positions(sensors: &[Sensor], row: i32) -> usize {
+        let x1 = sensors.iter().map(|s| s.pos.x - s.distance).min().unwrap();
+        let x2 = sensors.iter().map(|s| s.pos.x + s.distance).max().unwrap();
+        (x1..=x2)
+            .map(|x| Point::new(x, row))
+            .filter(|&p| sensors.iter().any(|s| s.covers(p)))
+            .filter(|&p| sensors.iter().all(|s| s.beacon != p))
+            .count()
+    }
+
+    /// Uncovered positions in the search area, checked one by one.
+    fn uncovered_positions(sensors: &[Sensor], max: i32) -> Vec<Pos> {
+        (0..=max)
+            .flat_map(|y| (0..=max).map(move |x| Point::new(x, y)))
+            .filter(|&p| !sensors.iter().any(|s| s.covers(p)))
+            .collect()
+    }
+
+    fn frequency(p: Pos) -> i64 {
+        p.x as i64 * 4000000 + p.y as i64
+    }
+
+    #[test]
+    fn test_generated() {
+        let params = Params { row: 10, max: 20 };
+        property::check::<Day15>(20, &params, property::cases(200), |_, sensors| {
+            assert_eq!(covered_in_row(sensors, params.row), covered_positions(sensors, params.row));
+            let uncovered = uncovered_positions(sensors, params.max);
+            assert_eq!(uncovered.len(), 1);
+            assert_eq!(tuning_frequency(sensors, params.max), Ok(frequency(uncovered[0])));
+        });
+    }
+
+    #[test]
+    fn test_random_sensors() {
+        // Sensors placed anywhere, not just around a single uncovered position.
+        for seed in 0..property::cases(500) {
+            let mut rng = Rng::new(seed);
+            let input: String = (0..rng.range(1..=6))
+                .map(|_| {
+                    let mut c = || rng.range(-5..=25);
+                    format!("Sensor at x={}, y={}: closest beacon is at x={}, y={}\n", c(), c(), c(), c())
+                })
+                .collect();
+            let sensors = parse(&input).unwrap();
+            let uncovered = uncovered_positions(&sensors, 20);
+            let found = tuning_frequency(&sensors, 20);
+            assert!(
+                found.map_or(uncovered.is_empty(), |found| uncovered.iter().any(|&p| frequency(p) == found)),
+                "seed {}, input:\n{}", seed, input);
+            assert_eq!(covered_in_row(&sensors, 10), covered_positions(&sensors, 10), "seed {}, input:\n{}", seed, input);
+        }
+    }
+
+    #[test]
+    fn test_regression() {
+        // Used to count positions up to the last covered one and subtract a
+        // single beacon, even if there were more beacons in the row.
+        let input = example!("test_regression.txt");
+        assert_eq!(solution1(input, 10), Ok(95));
+        assert_eq!(solution2(input, 20), Ok(frequency(Point::new(6, 16))));
+    }
+}
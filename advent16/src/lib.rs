@@ -0,0 +1,457 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use aoc_common::search::best_first;
+use aoc_common::{config, Answer, Config, ParseError, Rng, Solution, Source};
+
+type ValveId = u8;
+pub struct Valve {
+    rate: ValveId,
+    next: Vec<ValveId>,
+}
+type Valves = HashMap<ValveId, Valve>;
+
+pub struct Tunnels {
+    ids: HashMap<String, ValveId>,
+    valves: Valves,
+}
+
+impl Tunnels {
+    fn start(&self, params: &Params) -> Result<ValveId, String> {
+        self.ids.get(&params.start).copied()
+            .filter(|id| self.valves.contains_key(id))
+            .ok_or_else(|| format!("No start valve {}", params.start))
+    }
+}
+
+fn parse(input: &str) -> Result<Tunnels, ParseError> {
+    let source = Source::new(Day16::DAY, input);
+    let mut valve_ids = HashMap::<&str, u8>::new();
+    let mut free_valve_id = 0;
+    let mut valve_id = |id| {
+        *valve_ids.entry(id)
+            .or_insert_with(|| {free_valve_id += 1; free_valve_id})
+    };
+    let mut valves = Valves::new();
+    for line in input.trim_end().split('\n') {
+        // line: "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB"
+        let line = source.strip_prefix(line, "Valve ")?;
+        let mut it = line.split(&[' ', '=', ';', ','][..]);
+        let valve = valve_id(source.next(&mut it, line, "valve")?);
+        let rate = it.nth(3).ok_or_else(|| source.missing(line, "flow rate"))?;
+        let rate = source.parse::<u8>(rate)?;
+        let next = it.skip(5).step_by(2).map(&mut valve_id).collect();
+        valves.insert(valve, Valve {rate, next});
+    }
+    let ids = valve_ids.into_iter().map(|(name, id)| (name.to_string(), id)).collect();
+    Ok(Tunnels {ids, valves})
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+struct Flowing {
+    flowing: u64,
+}
+
+impl Flowing {
+    fn none() -> Self {
+        Flowing {flowing: 0}
+    }
+
+    fn is_open(&self, valve_id: u8) -> bool {
+        (self.flowing & (1 << valve_id)) > 0
+    }
+
+    fn with_opened(&self, valve_id: u8) -> Flowing {
+        Flowing { flowing: (self.flowing | (1 << valve_id)) }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+struct State {
+    flow: usize,
+    remaining_time: usize,
+    flowing: Flowing,
+    opened: usize,
+}
+
+impl State {
+    fn new(remaining_time: usize) -> Self {
+        State {
+            flow: 0,
+            remaining_time,
+            flowing: Flowing::none(),
+            opened: 0,
+        }
+    }
+
+    fn cost(&self) -> usize {
+        self.flow + (16 - self.opened) * 25 * self.remaining_time / 2
+    }
+}
+
+impl Ord for State {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cost().cmp(&other.cost())
+    }
+}
+
+impl PartialOrd for State {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Best flow and remaining time seen for each position and opened valves.
+struct Visited<Key> {
+    visited: HashMap<(Key, u64), (usize, usize)>,
+}
+
+impl<Key> Visited<Key> {
+    fn new() -> Self {
+        Visited {visited: HashMap::new()}
+    }
+
+    /// State to explore unless a better one was already seen.
+    fn visit(&mut self, key: Key, state: &State) -> Option<(State, Key)>
+        where Key: Copy + Eq + Hash
+    {
+        let v = (key, state.flowing.flowing);
+        if let Some((time, flow)) = self.visited.get(&v).copied() {
+            if time >= state.remaining_time && flow >= state.flow {
+                return None;
+            }
+        }
+        self.visited.insert(v, (state.remaining_time, state.flow));
+        Some((*state, key))
+    }
+}
+
+/// Best pressure released for each set of opened valves.
+fn max_pressure_by_opened(start: u8, valves: &Valves, minutes: usize) -> HashMap<u64, usize> {
+    let mut visited = Visited::<u8>::new();
+    let start_state = State::new(minutes);
+
+    let mut best = HashMap::from([(0, 0)]);
+    best_first(visited.visit(start, &start_state), |(state, id)| {
+        let mut next = Vec::new();
+        let valve = &valves[&id];
+        // Needed for the start valve, other valves are opened when moving to them.
+        if valve.rate > 0 && !state.flowing.is_open(id) && state.remaining_time > 1 {
+            let flow = state.flow + (state.remaining_time - 1) * valve.rate as usize;
+            let flowing = state.flowing.with_opened(id);
+            let best_flow = best.entry(flowing.flowing).or_default();
+            *best_flow = flow.max(*best_flow);
+            let new = State {
+                flow,
+                remaining_time: state.remaining_time - 1,
+                flowing,
+                opened: state.opened + 1,
+            };
+            next.extend(visited.visit(id, &new));
+        }
+
+        for next_id in &valve.next {
+            let next_valve = &valves[next_id];
+
+            if next_valve.rate > 0 && !state.flowing.is_open(*next_id) && state.remaining_time > 2 {
+                let flow = state.flow + (state.remaining_time - 2) * next_valve.rate as usize;
+                let flowing = state.flowing.with_opened(*next_id);
+                let best_flow = best.entry(flowing.flowing).or_default();
+                *best_flow = flow.max(*best_flow);
+
+                if state.remaining_time > 3 {
+                    let new = State {
+                        flow,
+                        remaining_time: state.remaining_time - 2,
+                        flowing,
+                        opened: state.opened + 1,
+                    };
+                    next.extend(visited.visit(*next_id, &new));
+                }
+            }
+
+            if state.remaining_time > 2 {
+                let new = State {
+                    flow: state.flow,
+                    remaining_time: state.remaining_time - 1,
+                    flowing: state.flowing,
+                    opened: state.opened,
+                };
+                next.extend(visited.visit(*next_id, &new));
+            }
+        }
+        Some(next)
+    });
+
+    best
+}
+
+fn max_pressure(start: u8, valves: &Valves, minutes: usize) -> usize {
+    max_pressure_by_opened(start, valves, minutes).into_values().max().unwrap()
+}
+
+fn max_pressure_with_elephant(start: u8, valves: &Valves, minutes: usize) -> usize {
+    // Both open different valves, each on their own.
+    let mut best: Vec<_> = max_pressure_by_opened(start, valves, minutes).into_iter().collect();
+    best.sort_by_key(|&(_, flow)| Reverse(flow));
+
+    let mut max_flow = 0;
+    for (i, &(opened1, flow1)) in best.iter().enumerate() {
+        for &(opened2, flow2) in &best[i..] {
+            if flow1 + flow2 <= max_flow {
+                break;
+            }
+            if opened1 & opened2 == 0 {
+                max_flow = flow1 + flow2;
+            }
+        }
+    }
+    max_flow
+}
+
+/// Connected valves with at most 15 positive flow rates, as the search
+/// assumes.
+fn generate(rng: &mut Rng, size: usize, params: &Params) -> String {
+    let count = (size / 2).clamp(2, 60);
+    let mut names = vec![params.start.clone()];
+    while names.len() < count {
+        let name: String = (0..2).map(|_| (b'A' + rng.below(26) as u8) as char).collect();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+
+    let mut tunnels = vec![Vec::new(); count];
+    let mut connect = |a: usize, b: usize| {
+        if a != b && !tunnels[a].contains(&b) {
+            tunnels[a].push(b);
+            tunnels[b].push(a);
+        }
+    };
+    for i in 1..count {
+        connect(i, rng.below(i));
+    }
+    for _ in 0..count / 4 {
+        connect(rng.below(count), rng.below(count));
+    }
+
+    let mut order: Vec<usize> = (0..count).collect();
+    rng.shuffle(&mut order);
+    let mut rates = vec![0; count];
+    for &i in order.iter().filter(|&&i| i != 0).take((count / 4).clamp(1, 15)) {
+        rates[i] = rng.range(1..=25);
+    }
+
+    order.iter()
+        .map(|&i| {
+            let next: Vec<&str> = tunnels[i].iter().map(|&j| names[j].as_str()).collect();
+            let tunnels = if next.len() == 1 { "tunnel leads to valve" } else { "tunnels lead to valves" };
+            format!("Valve {} has flow rate={}; {} {}\n", names[i], rates[i], tunnels, next.join(", "))
+        })
+        .collect()
+}
+
+fn part1(tunnels: &Tunnels, params: &Params) -> Result<usize, String> {
+    tunnels.start(params).map(|start| max_pressure(start, &tunnels.valves, params.minutes1))
+}
+
+fn part2(tunnels: &Tunnels, params: &Params) -> Result<usize, String> {
+    tunnels.start(params).map(|start| max_pressure_with_elephant(start, &tunnels.valves, params.minutes2))
+}
+
+/// Without the start valve, the error points at the whole input.
+fn solve(input: &str, f: fn(&Tunnels, &Params) -> Result<usize, String>) -> Result<usize, ParseError> {
+    let source = Source::new(Day16::DAY, input);
+    f(&parse(input)?, &Params::default()).map_err(|e| source.error(input.trim_end(), e))
+}
+
+pub fn solution1(input: &str) -> Result<usize, ParseError> {
+    solve(input, part1)
+}
+
+pub fn solution2(input: &str) -> Result<usize, ParseError> {
+    solve(input, part2)
+}
+
+pub struct Params {
+    /// Valve to start at.
+    pub start: String,
+    /// Minutes to release pressure alone.
+    pub minutes1: usize,
+    /// Minutes to release pressure with the elephant.
+    pub minutes2: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { start: "AA".to_string(), minutes1: 30, minutes2: 26 }
+    }
+}
+
+impl Config for Params {
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "start" => self.start = value.to_string(),
+            "minutes1" => self.minutes1 = config::value(key, value)?,
+            "minutes2" => self.minutes2 = config::value(key, value)?,
+            _ => return Err(config::unknown(key)),
+        }
+        Ok(())
+    }
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+
+    type Input = Tunnels;
+    type Params = Params;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(tunnels: &Self::Input, params: &Params) -> Result<Answer, String> {
+        part1(tunnels, params).map(Answer::from)
+    }
+
+    fn part2(tunnels: &Self::Input, params: &Params) -> Result<Answer, String> {
+        part2(tunnels, params).map(Answer::from)
+    }
+
+    fn generate(rng: &mut Rng, size: usize, params: &Params) -> String {
+        generate(rng, size, params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use aoc_common::{example, property};
+
+    #[test]
+    fn test_flowing() {
+        assert!(!Flowing::none().is_open(1));
+        assert!(!Flowing::none().is_open(2));
+        assert!(!Flowing::none().is_open(63));
+
+        assert!(Flowing::none().with_opened(1).is_open(1));
+        assert!(!Flowing::none().with_opened(1).is_open(2));
+        assert!(!Flowing::none().with_opened(1).is_open(3));
+        assert!(!Flowing::none().with_opened(1).is_open(4));
+
+        assert!(!Flowing::none().with_opened(2).is_open(1));
+        assert!(Flowing::none().with_opened(2).is_open(2));
+        assert!(!Flowing::none().with_opened(2).is_open(3));
+        assert!(!Flowing::none().with_opened(2).is_open(4));
+
+        assert!(!Flowing::none().with_opened(3).is_open(1));
+        assert!(!Flowing::none().with_opened(3).is_open(2));
+        assert!(Flowing::none().with_opened(3).is_open(3));
+        assert!(!Flowing::none().with_opened(3).is_open(4));
+
+        assert!(Flowing::none().with_opened(63).is_open(63));
+        assert!(!Flowing::none().with_opened(63).is_open(62));
+        assert!(!Flowing::none().with_opened(63).is_open(1));
+        assert_eq!((1..64).filter(
+                |v| Flowing::none().with_opened(63).is_open(*v)).collect::<Vec<u8>>(), [63]);
+    }
+
+    #[test]
+    fn test_solution1() {
+        let input = example!("test.txt");
+        assert_eq!(solution1(input), Ok(1651));
+    }
+
+    #[test]
+    fn test_solution2() {
+        let input = example!("test.txt");
+        assert_eq!(solution2(input), Ok(1707));
+    }
+
+    /// Best pressure for each set of opened valves, trying every order of
+    /// opening valves with positive flow rate.
+    fn pressure_by_opened(start: u8, valves: &Valves, minutes: usize) -> HashMap<u64, usize> {
+        let distances = |from: u8| {
+            let mut distances = HashMap::from([(from, 0)]);
+            let mut queue = VecDeque::from([from]);
+            while let Some(id) = queue.pop_front() {
+                let d = distances[&id];
+                for &next in &valves[&id].next {
+                    distances.entry(next).or_insert_with(|| {
+                        queue.push_back(next);
+                        d + 1
+                    });
+                }
+            }
+            distances
+        };
+        // Flow rate and distances to the other valves, the start is the last one.
+        let mut ids: Vec<u8> = valves.iter().filter(|(_, v)| v.rate > 0).map(|(&id, _)| id).collect();
+        ids.push(start);
+        let graph: Vec<(usize, Vec<Option<usize>>)> = ids.iter()
+            .map(|&id| {
+                let distances = distances(id);
+                (valves[&id].rate as usize, ids.iter().map(|id2| distances.get(id2).copied()).collect())
+            })
+            .collect();
+
+        type Graph = [(usize, Vec<Option<usize>>)];
+        fn visit(graph: &Graph, i: usize, time: usize, opened: u64, pressure: usize, best: &mut HashMap<u64, usize>) {
+            let entry = best.entry(opened).or_insert(0);
+            *entry = (*entry).max(pressure);
+            for (j, &(rate, _)) in graph.iter().enumerate().take(graph.len() - 1) {
+                let Some(d) = graph[i].1[j] else { continue };
+                if opened & (1 << j) == 0 && d + 1 < time {
+                    let time = time - d - 1;
+                    visit(graph, j, time, opened | (1 << j), pressure + time * rate, best);
+                }
+            }
+        }
+
+        let mut best = HashMap::new();
+        visit(&graph, graph.len() - 1, minutes, 0, 0, &mut best);
+        best
+    }
+
+    #[test]
+    fn test_generated() {
+        let params = Params::default();
+        property::check::<Day16>(40, &params, property::cases(50), |_, tunnels| {
+            let (start, valves) = (tunnels.start(&params).unwrap(), &tunnels.valves);
+            let alone = pressure_by_opened(start, valves, 30);
+            assert_eq!(max_pressure(start, valves, 30), *alone.values().max().unwrap());
+
+            let together = pressure_by_opened(start, valves, 26);
+            let best = together.iter()
+                .flat_map(|(a, pa)| together.iter().filter(|(b, _)| *a & *b == 0).map(move |(_, pb)| pa + pb))
+                .max()
+                .unwrap();
+            assert_eq!(max_pressure_with_elephant(start, valves, 26), best);
+        });
+    }
+
+    #[test]
+    fn test_params() {
+        let tunnels = parse(example!("test.txt")).unwrap();
+        let mut params = Params::default();
+        params.set("start", "JJ").unwrap();
+        params.set("minutes1", "10").unwrap();
+        let start = tunnels.start(&params).unwrap();
+        let expected = *pressure_by_opened(start, &tunnels.valves, 10).values().max().unwrap();
+        assert_eq!(part1(&tunnels, &params), Ok(expected));
+
+        params.set("start", "XX").unwrap();
+        assert_eq!(part2(&tunnels, &params), Err("No start valve XX".to_string()));
+    }
+
+    #[test]
+    fn test_regression() {
+        // States were skipped for states with less remaining time.
+        let input = example!("test_regression.txt");
+        assert_eq!(solution2(input), Ok(1867));
+    }
+}
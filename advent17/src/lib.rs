@@ -0,0 +1,351 @@
+use std::collections::HashSet;
+use std::collections::HashMap;
+
+use aoc_common::visual::{self, Cell, Color, Frame};
+use aoc_common::{config, Answer, Config, ParseError, Point, Rng, Solution, Source};
+
+const START_X: usize = 2;
+const START_Y: usize = 3;
+
+pub type Pos = (usize, usize);
+pub type Rock = Vec<Pos>;
+pub type Rocks = Vec<Rock>;
+type Chamber = HashSet<Pos>;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Move {
+    Left,
+    Right,
+}
+type Moves = Vec<Move>;
+
+pub fn parse_rocks(input: &str) -> Rocks {
+    input.trim_end()
+        .split("\n\n")
+        .map(|block| {
+            block.split('\n')
+                .enumerate()
+                .flat_map(|(y, line)| {
+                    line.chars()
+                        .enumerate()
+                        .filter(|(_x, c)| *c == '#')
+                        .map(move |(x, _c)| (x, y))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn parse_moves(input: &str) -> Result<Moves, ParseError> {
+    let source = Source::new(Day17::DAY, input);
+    let input = input.trim_end();
+    if input.is_empty() {
+        return Err(source.missing(input, "jet pattern"));
+    }
+    input.char_indices()
+        .map(|(i, c)| match c {
+            '<' => Ok(Move::Left),
+            '>' => Ok(Move::Right),
+            _ => Err(source.error(&input[i..i + c.len_utf8()], format!("Unexpected jet: {:?}", c))),
+        })
+        .collect()
+}
+
+fn is_valid_position(pos: Pos, rock: &Rock, chamber: &Chamber) -> bool {
+    !rock.iter().any(|p| {
+        chamber.contains(&(p.0 + pos.0, pos.1 - p.1))
+    })
+}
+
+/// Chamber with the falling rock, the top row is at the bottom of the frame.
+struct View<'a> {
+    width: usize,
+    rock_pos: Pos,
+    rock: &'a Rock,
+    chamber: &'a Chamber,
+    top: usize,
+}
+
+impl View<'_> {
+    fn height(&self) -> usize {
+        (self.rock_pos.1 + 1).max(self.top + START_Y)
+    }
+}
+
+impl Frame for View<'_> {
+    fn size(&self) -> Point<usize> {
+        Point::new(self.width, self.height())
+    }
+
+    fn cell(&self, p: Point<usize>) -> Cell {
+        let pos = (p.x, self.height() - 1 - p.y);
+        if self.rock.iter().any(|p| (p.0 + self.rock_pos.0, self.rock_pos.1 - p.1) == pos) {
+            Cell::new('@', Color::Red)
+        } else if self.chamber.contains(&pos) {
+            '#'.into()
+        } else if pos.1 == self.top {
+            Cell::new('.', Color::Yellow)
+        } else {
+            '.'.into()
+        }
+    }
+
+    fn focus(&self) -> Option<Point<usize>> {
+        Some(Point::new(self.rock_pos.0, self.height() - 1 - self.rock_pos.1))
+    }
+
+    fn status(&self) -> String {
+        format!("height {}", self.top)
+    }
+}
+
+fn render(width: usize, rock_pos: Pos, rock: &Rock, chamber: &Chamber, top: usize) {
+    if visual::enabled() {
+        visual::show(&View { width, rock_pos, rock, chamber, top });
+    }
+}
+
+fn solution(moves: &Moves, rocks: &Rocks, chamber_width: usize, rock_count: usize) -> usize {
+    let mut top = 0;
+    let mut top_add = 0;
+    let mut cache: HashMap<(Vec<u16>, usize, usize), (usize, usize)> = HashMap::new();
+    // Rows below the top compared when looking for cycles, this is the
+    // deepest any rock got so far.
+    let mut window = 0;
+    let mut chamber = HashSet::<Pos>::new();
+    let mut move_index = 0;
+    let mut r = 0;
+    while r < rock_count {
+        let rock = &rocks[r % rocks.len()];
+        let width = rock.iter().map(|p| p.0).max().unwrap() + 1;
+        let height = rock[rock.len() - 1].1 + 1;
+        let dropped_at = top;
+        let mut pos = (START_X, top + height + START_Y - 1);
+        loop {
+            render(chamber_width, pos, rock, &chamber, top);
+            let m = moves[move_index];
+            move_index = (move_index + 1) % moves.len();
+
+            if m == Move::Left && pos.0 > 0 {
+                let p2 = (pos.0 - 1, pos.1);
+                if is_valid_position(p2, rock, &chamber) { pos = p2; }
+            } else if m == Move::Right && pos.0 + width < chamber_width {
+                let p2 = (pos.0 + 1, pos.1);
+                if is_valid_position(p2, rock, &chamber) { pos = p2; }
+            }
+            render(chamber_width, pos, rock, &chamber, top);
+
+            if pos.1 >= height {
+                let p2 = (pos.0, pos.1 - 1);
+                if is_valid_position(p2, rock, &chamber) {
+                    pos = p2;
+                    continue;
+                }
+            }
+
+            top = top.max(pos.1 + 1);
+            for p in rock {
+                chamber.insert((pos.0 + p.0, pos.1 - p.1));
+            }
+
+            // Collision checks went down to the row below the rock or to the floor.
+            let depth = dropped_at + height - pos.1;
+            if depth > window {
+                // Cached surfaces are too shallow to prove a cycle.
+                window = depth;
+                cache.clear();
+            }
+
+            let surface: Vec<u16> = (1..=window).map(|y| {
+                if y > top {
+                    (1 << chamber_width) - 1
+                } else {
+                    (0..chamber_width)
+                        .filter(|&x| chamber.contains(&(x, top - y)))
+                        .map(|x| 1 << x)
+                        .sum()
+                }
+            }).collect();
+            // Falling rocks never got below the surface, so the same surface,
+            // rock and jet means that the tower repeats.
+            let cache_key = (surface, r % rocks.len(), move_index);
+            match cache.get(&cache_key) {
+                Some(&(r2, top2)) => {
+                    let d = r - r2;
+                    // Rocks r2 + 1..=r repeat, the last rock has index rock_count - 1.
+                    let times = (rock_count - 1 - r2) / d;
+                    top_add += (top - top2) * (times - 1);
+                    r = r2 + d * times;
+                    // Fewer than d rocks remain, so there is no other cycle.
+                    cache.clear();
+                },
+                None => {
+                    cache.insert(cache_key, (r, top));
+                },
+            }
+            break;
+        }
+
+        r += 1;
+    }
+    top + top_add
+}
+
+fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1)).map(|_| *rng.choose(&['<', '>'])).chain(['\n']).collect()
+}
+
+pub fn solution1(input: &str, rocks: &Rocks) -> Result<usize, ParseError> {
+    let params = Params::default();
+    Ok(solution(&parse_moves(input)?, rocks, params.width, params.rock_count1))
+}
+
+pub fn solution2(input: &str, rocks: &Rocks) -> Result<usize, ParseError> {
+    let params = Params::default();
+    Ok(solution(&parse_moves(input)?, rocks, params.width, params.rock_count2))
+}
+
+pub struct Params {
+    pub rocks: Rocks,
+    /// Chamber width.
+    pub width: usize,
+    /// Rocks dropped in part 1.
+    pub rock_count1: usize,
+    /// Rocks dropped in part 2.
+    pub rock_count2: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            rocks: parse_rocks(include_str!("../rocks.txt")),
+            width: 7,
+            rock_count1: 2022,
+            rock_count2: 1_000_000_000_000,
+        }
+    }
+}
+
+impl Config for Params {
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "width" => {
+                let width = config::value(key, value)?;
+                // Rows of the compared surface are 16-bit masks and every
+                // rock must fit next to the left gap when it appears.
+                let widest = self.rocks.iter().flatten().map(|p| p.0 + 1).max().unwrap_or(0);
+                if !(START_X + widest..=16).contains(&width) {
+                    return Err(format!("Width must be between {} and 16", START_X + widest));
+                }
+                self.width = width;
+            },
+            "rock_count1" => self.rock_count1 = config::value(key, value)?,
+            "rock_count2" => self.rock_count2 = config::value(key, value)?,
+            _ => return Err(config::unknown(key)),
+        }
+        Ok(())
+    }
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+
+    type Input = Moves;
+    type Params = Params;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_moves(input)
+    }
+
+    fn part1(moves: &Self::Input, params: &Params) -> Result<Answer, String> {
+        Ok(solution(moves, &params.rocks, params.width, params.rock_count1).into())
+    }
+
+    fn part2(moves: &Self::Input, params: &Params) -> Result<Answer, String> {
+        Ok(solution(moves, &params.rocks, params.width, params.rock_count2).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::{example, property};
+
+    #[test]
+    fn test_solution1() {
+        let rocks = Params::default().rocks;
+        let input = example!("test.txt");
+        assert_eq!(solution1(input, &rocks), Ok(3068));
+    }
+
+    #[test]
+    fn test_solution2() {
+        let rocks = Params::default().rocks;
+        let input = example!("test.txt");
+        assert_eq!(solution2(input, &rocks), Ok(1514285714288));
+    }
+
+    /// Tower height after dropping rocks one by one, without skipping cycles.
+    fn tower_height(moves: &Moves, rocks: &Rocks, chamber_width: usize, rock_count: usize) -> usize {
+        let mut chamber = Chamber::new();
+        let mut top = 0;
+        let mut jets = moves.iter().cycle();
+        for rock in rocks.iter().cycle().take(rock_count) {
+            let width = rock.iter().map(|p| p.0).max().unwrap() + 1;
+            let height = rock.iter().map(|p| p.1).max().unwrap() + 1;
+            let mut pos = (START_X, top + height + START_Y - 1);
+            loop {
+                let x = match jets.next().unwrap() {
+                    Move::Left => pos.0.checked_sub(1),
+                    Move::Right => Some(pos.0 + 1).filter(|x| x + width <= chamber_width),
+                };
+                if let Some(x) = x.filter(|&x| is_valid_position((x, pos.1), rock, &chamber)) {
+                    pos.0 = x;
+                }
+                if pos.1 < height || !is_valid_position((pos.0, pos.1 - 1), rock, &chamber) {
+                    break;
+                }
+                pos.1 -= 1;
+            }
+            chamber.extend(rock.iter().map(|p| (pos.0 + p.0, pos.1 - p.1)));
+            top = top.max(pos.1 + 1);
+        }
+        top
+    }
+
+    #[test]
+    fn test_cycle_detection() {
+        let rocks = Params::default().rocks;
+        let cases = property::cases(50);
+        property::check::<Day17>(40, &Params::default(), cases, |_, moves| {
+            for rock_count in [100, 2022, 5000] {
+                assert_eq!(solution(moves, &rocks, 7, rock_count), tower_height(moves, &rocks, 7, rock_count));
+            }
+        });
+    }
+
+    #[test]
+    fn test_regression() {
+        // A cycle used to be skipped while rocks in it fell below the compared surface.
+        let rocks = Params::default().rocks;
+        let moves = parse_moves(example!("test_regression.txt")).unwrap();
+        assert_eq!(solution(&moves, &rocks, 7, 5000), tower_height(&moves, &rocks, 7, 5000));
+    }
+
+    #[test]
+    fn test_params() {
+        let mut params = Params::default();
+        let moves = parse_moves(example!("test.txt")).unwrap();
+        params.set("width", "10").unwrap();
+        params.set("rock_count2", "3000").unwrap();
+        assert_eq!(Day17::part2(&moves, &params), Ok(tower_height(&moves, &params.rocks, 10, 3000).into()));
+        assert!(params.set("width", "5").is_err());
+        assert!(params.set("width", "17").is_err());
+    }
+}
@@ -0,0 +1,142 @@
+use std::collections::HashSet;
+
+use aoc_common::{Answer, ParseError, Point3, Rng, Solution, Source};
+
+type Pos = Point3<i8>;
+
+fn parse(input: &str) -> Result<HashSet<Pos>, ParseError> {
+    let source = Source::new(Day18::DAY, input);
+    input
+        .trim_end()
+        .split('\n')
+        .map(|line| {
+            // line: "2,2,2"
+            let mut it = line.split(',');
+            let mut coordinate = |what| source.parse::<i8>(source.next(&mut it, line, what)?);
+            Ok(Point3::new(coordinate("x")?, coordinate("y")?, coordinate("z")?))
+        })
+        .collect()
+}
+
+fn surface_area(ps: &HashSet<Pos>) -> usize {
+    ps.iter()
+        .flat_map(|&p| Pos::sides().map(|s| p + s))
+        .filter(|p| !ps.contains(p))
+        .count()
+}
+
+fn exterior_surface_area(ps: &HashSet<Pos>) -> usize {
+
+    let x_min = ps.iter().map(|p| p.x).min().unwrap() - 1;
+    let x_max = ps.iter().map(|p| p.x).max().unwrap() + 1;
+    let y_min = ps.iter().map(|p| p.y).min().unwrap() - 1;
+    let y_max = ps.iter().map(|p| p.y).max().unwrap() + 1;
+    let z_min = ps.iter().map(|p| p.z).min().unwrap() - 1;
+    let z_max = ps.iter().map(|p| p.z).max().unwrap() + 1;
+
+    let faces: HashSet<(Pos, Pos)> = ps.iter()
+        .flat_map(|&p| Pos::sides().map(|s| (p, s)))
+        .filter(|&(p, s)| !ps.contains(&(p + s)))
+        .collect();
+    let mut external_faces = HashSet::<(Pos, Pos)>::new();
+
+    let mut to_visit = Vec::<Pos>::new();
+    let mut visited = HashSet::<Pos>::new();
+    to_visit.push(Pos::default());
+    while let Some(f) = to_visit.pop() {
+        visited.insert(f);
+        for s in Pos::sides() {
+            let p = f - s;
+
+            if p.x < x_min || p.x > x_max
+            || p.y < y_min || p.y > y_max
+            || p.z < z_min || p.z > z_max
+            {
+                continue;
+            }
+
+            if !ps.contains(&p) {
+                if !visited.contains(&p) {
+                    to_visit.push(p);
+                }
+            } else if faces.contains(&(p, s)) {
+                external_faces.insert((p, s));
+            }
+        }
+    }
+
+    external_faces.len()
+}
+
+/// Distinct cubes in a ball, with air pockets between them.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut cubes = Vec::new();
+    let mut seen = HashSet::new();
+    while cubes.len() < size.clamp(1, 2000) {
+        let mut coordinate = || rng.range(0..=20) as i8;
+        let p = Point3::new(coordinate(), coordinate(), coordinate());
+        let d = |c: i8| (c as i32 - 10).pow(2);
+        if d(p.x) + d(p.y) + d(p.z) <= 100 && seen.insert(p) {
+            cubes.push(p);
+        }
+    }
+    cubes.iter().map(|p| format!("{},{},{}\n", p.x, p.y, p.z)).collect()
+}
+
+pub fn solution1(input: &str) -> Result<usize, ParseError> {
+    Ok(surface_area(&parse(input)?))
+}
+
+pub fn solution2(input: &str) -> Result<usize, ParseError> {
+    Ok(exterior_surface_area(&parse(input)?))
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+
+    type Input = HashSet<Pos>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(ps: &Self::Input, _params: &()) -> Result<Answer, String> {
+        Ok(surface_area(ps).into())
+    }
+
+    fn part2(ps: &Self::Input, _params: &()) -> Result<Answer, String> {
+        Ok(exterior_surface_area(ps).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &()) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::{example, Inputs};
+
+    #[test]
+    fn test_solution1() {
+        let input = example!("test.txt");
+        assert_eq!(solution1(input), Ok(64));
+    }
+
+    #[test]
+    fn test_solution2() {
+        let input = example!("test.txt");
+        assert_eq!(solution2(input), Ok(58));
+    }
+
+    #[test]
+    fn test_solution2_input() {
+        let Ok(input) = Inputs::from_env().load(Day18::DAY) else { return };
+        let x = solution2(&input).unwrap();
+        assert!(x > 2536, "Value too low: {}", x);
+    }
+}
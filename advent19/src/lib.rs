@@ -0,0 +1,336 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use aoc_common::parallel;
+use aoc_common::search::best_first;
+use aoc_common::{config, Answer, Config, ParseError, Rng, Solution, Source};
+
+type Cost = [usize; 3];
+
+type BluePrint = [Cost; 4];
+
+type BluePrints = Vec<BluePrint>;
+
+fn parse_blueprints(input: &str) -> Result<BluePrints, ParseError> {
+    let source = Source::new(Day19::DAY, input);
+    input.trim_end()
+        .split('\n')
+        .map(|line| {
+            let mut it = line.split("costs ").skip(1);
+            let mut robot = |what| -> Result<Vec<&str>, ParseError> {
+                let costs = source.next(&mut it, line, what)?;
+                Ok(costs.split(' ').collect())
+            };
+            // amount of resource at given index, e.g. "3 ore and 14 clay"
+            let cost = |costs: &[&str], index: usize| -> Result<usize, ParseError> {
+                let amount = costs.get(index)
+                    .ok_or_else(|| source.missing(costs[costs.len() - 1], "robot cost"))?;
+                source.parse::<usize>(amount)
+            };
+            let ore_robot = robot("ore robot cost")?;
+            let clay_robot = robot("clay robot cost")?;
+            let obsidian_robot = robot("obsidian robot cost")?;
+            let geode_robot = robot("geode robot cost")?;
+            Ok([
+                [cost(&ore_robot, 0)?, 0, 0],
+                [cost(&clay_robot, 0)?, 0, 0],
+                [cost(&obsidian_robot, 0)?, cost(&obsidian_robot, 3)?, 0],
+                [cost(&geode_robot, 0)?, 0, cost(&geode_robot, 3)?],
+            ])
+        })
+        .collect()
+}
+
+fn can_build(c: &Cost, other: &Cost) -> bool {
+    c[0] >= other[0] && c[1] >= other[1] && c[2] >= other[2]
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+struct State {
+    robots: [usize; 4],
+    c: Cost,
+    /// Robots which could have been built instead of waiting.
+    skipped: [bool; 4],
+}
+
+impl Ord for State {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.robots[3].cmp(&other.robots[3])
+            .then_with(|| self.robots[2].cmp(&other.robots[2]))
+            .then_with(|| self.robots[1].cmp(&other.robots[1]))
+            .then_with(|| self.robots[0].cmp(&other.robots[0]))
+    }
+}
+
+impl PartialOrd for State {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Most geodes seen for each state and remaining time.
+struct States {
+    visited: HashMap<(State, usize), usize>,
+}
+
+impl States {
+    fn new() -> Self
+    {
+        States {visited: HashMap::new()}
+    }
+
+    /// State to explore unless a better one was already seen.
+    fn visit(&mut self, state: &State, minutes: usize, geodes: usize) -> Option<(usize, usize, State)>
+    {
+        if let Some(geodes2) = self.visited.get(&(*state, minutes)).copied() {
+            if geodes2 >= geodes { return None; }
+        }
+
+        self.visited.insert((*state, minutes), geodes);
+        Some((geodes, minutes, *state))
+    }
+}
+
+fn maximize_geodes(blueprint: &BluePrint, minutes: usize) -> usize {
+    let mut visited = States::new();
+    let start_state = State {robots: [1, 0, 0, 0], c: [0; 3], skipped: [false; 4]};
+    let mut max_geodes = 0;
+    // Only one robot can be built each minute, so more robots than the
+    // highest cost of the resource are useless.
+    let max_robots: [usize; 3] = core::array::from_fn(|i| {
+        blueprint.iter().map(|cost| cost[i]).max().unwrap()
+    });
+
+    best_first(visited.visit(&start_state, minutes, 0), |(geodes, minutes, s)| {
+        // Geodes collected without building any other robot.
+        max_geodes = max_geodes.max(geodes + s.robots[3] * minutes);
+
+        let mut next = Vec::new();
+        // Not even a new geode robot every minute would be better.
+        if minutes <= 1 || geodes + s.robots[3] * minutes + minutes * (minutes - 1) / 2 <= max_geodes {
+            return Some(next);
+        }
+
+        let c = [
+            s.c[0] + s.robots[0],
+            s.c[1] + s.robots[1],
+            s.c[2] + s.robots[2],
+        ];
+        let geodes = geodes + s.robots[3];
+        // Resources which cannot be spent in the remaining time make no difference.
+        let mut visit = |robots: [usize; 4], c: Cost, skipped| {
+            let c = core::array::from_fn(|i| c[i].min(max_robots[i] * (minutes - 1)));
+            next.extend(visited.visit(&State {robots, c, skipped}, minutes - 1, geodes));
+        };
+
+        // Building a robot later than possible is never better.
+        let mut skipped = s.skipped;
+        for i in (0..4).rev() {
+            if i < 3 && s.robots[i] >= max_robots[i] { continue; }
+            if !s.skipped[i] && can_build(&s.c, &blueprint[i]) {
+                let mut robots = s.robots;
+                robots[i] += 1;
+                visit(robots, [c[0] - blueprint[i][0], c[1] - blueprint[i][1], c[2] - blueprint[i][2]], [false; 4]);
+                skipped[i] = true;
+            }
+        }
+
+        visit(s.robots, c, skipped);
+        Some(next)
+    });
+    max_geodes
+}
+
+fn quality_levels(bs: &BluePrints, params: &Params) -> usize {
+    let geodes = parallel::map(bs, |b| maximize_geodes(b, params.minutes1));
+    geodes.iter().enumerate().map(|(i, g)| (i+1) * g ).sum()
+}
+
+fn max_geodes_product(bs: &BluePrints, params: &Params) -> usize {
+    let bs = &bs[0..params.blueprints2.min(bs.len())];
+    parallel::map(bs, |b| maximize_geodes(b, params.minutes2) ).iter().product()
+}
+
+fn generate(rng: &mut Rng, size: usize) -> String {
+    (1..=(size / 10).max(3))
+        .map(|i| format!(
+            "Blueprint {}: Each ore robot costs {} ore. Each clay robot costs {} ore. \
+             Each obsidian robot costs {} ore and {} clay. Each geode robot costs {} ore and {} obsidian.\n",
+            i, rng.range(2..=4), rng.range(2..=4), rng.range(2..=4), rng.range(5..=20), rng.range(2..=4), rng.range(5..=20)))
+        .collect()
+}
+
+pub fn solution1(input: &str) -> Result<usize, ParseError> {
+    Ok(quality_levels(&parse_blueprints(input)?, &Params::default()))
+}
+
+pub fn solution2(input: &str) -> Result<usize, ParseError> {
+    Ok(max_geodes_product(&parse_blueprints(input)?, &Params::default()))
+}
+
+pub struct Params {
+    /// Minutes for each blueprint in part 1.
+    pub minutes1: usize,
+    /// Minutes for each blueprint in part 2.
+    pub minutes2: usize,
+    /// Number of blueprints from the start used in part 2.
+    pub blueprints2: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { minutes1: 24, minutes2: 32, blueprints2: 3 }
+    }
+}
+
+impl Config for Params {
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "minutes1" => self.minutes1 = config::value(key, value)?,
+            "minutes2" => self.minutes2 = config::value(key, value)?,
+            "blueprints2" => self.blueprints2 = config::value(key, value)?,
+            _ => return Err(config::unknown(key)),
+        }
+        Ok(())
+    }
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+
+    type Input = BluePrints;
+    type Params = Params;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_blueprints(input)
+    }
+
+    fn part1(bs: &Self::Input, params: &Params) -> Result<Answer, String> {
+        Ok(quality_levels(bs, params).into())
+    }
+
+    fn part2(bs: &Self::Input, params: &Params) -> Result<Answer, String> {
+        Ok(max_geodes_product(bs, params).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::{example, property, Inputs};
+
+    #[test]
+    fn test_parse_error() {
+        let input = example!("test.txt");
+        assert_eq!(parse_blueprints(input).map(|bs| bs[1][3]), Ok([3, 0, 12]));
+
+        let e = parse_blueprints(&input.replace("and 8 clay", "and x clay")).unwrap_err();
+        assert_eq!((e.line, e.column), (2, 107));
+        assert_eq!(e.message, "Unexpected value: \"x\"");
+    }
+
+    #[test]
+    fn test_solution1() {
+        let input = example!("test.txt");
+        assert_eq!(solution1(input), Ok(33));
+    }
+
+    #[test]
+    fn test_solution2() {
+        let input = example!("test.txt");
+        assert_eq!(solution2(input), Ok(56 * 62));
+    }
+
+    #[test]
+    fn test_solution1_full() {
+        let Ok(input) = Inputs::from_env().load(Day19::DAY) else { return };
+        assert_eq!(solution1(&input), Ok(1675));
+    }
+
+    #[test]
+    fn test_solution2_full() {
+        let Ok(input) = Inputs::from_env().load(Day19::DAY) else { return };
+        assert_eq!(solution2(&input), Ok(6840));
+    }
+
+    /// Most geodes, trying every robot to build next and waiting for
+    /// resources in-between.
+    fn geodes_by_building(blueprint: &BluePrint, minutes: usize) -> usize {
+        fn visit(blueprint: &BluePrint, robots: [usize; 4], c: [usize; 4], minutes: usize, best: &mut usize) {
+            let geodes = c[3] + robots[3] * minutes;
+            *best = geodes.max(*best);
+            // Even a new geode robot every minute would not be better.
+            if geodes + minutes * minutes.saturating_sub(1) / 2 <= *best {
+                return;
+            }
+            for i in 0..4 {
+                // Resources are never spent faster than this.
+                if i < 3 && robots[i] >= blueprint.iter().map(|cost| cost[i]).max().unwrap() {
+                    continue;
+                }
+                let wait = (0..3)
+                    .map(|r| match blueprint[i][r].saturating_sub(c[r]) {
+                        0 => Some(0),
+                        _ if robots[r] == 0 => None,
+                        missing => Some(missing.div_ceil(robots[r])),
+                    })
+                    .try_fold(0, |wait, w| w.map(|w| w.max(wait)));
+                let Some(wait) = wait.filter(|&wait| wait + 1 < minutes) else { continue };
+                let mut c2 = c;
+                for (r, amount) in c2.iter_mut().enumerate() {
+                    *amount += robots[r] * (wait + 1);
+                    if r < 3 {
+                        *amount -= blueprint[i][r];
+                    }
+                }
+                let mut robots2 = robots;
+                robots2[i] += 1;
+                visit(blueprint, robots2, c2, minutes - wait - 1, best);
+            }
+        }
+
+        let mut best = 0;
+        visit(blueprint, [1, 0, 0, 0], [0; 4], minutes, &mut best);
+        best
+    }
+
+    #[test]
+    fn test_generated() {
+        property::check::<Day19>(50, &Params::default(), property::cases(10), |_, blueprints| {
+            for blueprint in blueprints {
+                assert_eq!(maximize_geodes(blueprint, 24), geodes_by_building(blueprint, 24), "{:?}", blueprint);
+            }
+            for blueprint in &blueprints[..1] {
+                assert_eq!(maximize_geodes(blueprint, 32), geodes_by_building(blueprint, 32), "{:?}", blueprint);
+            }
+        });
+    }
+
+    #[test]
+    fn test_regression() {
+        // Used to stop building ore robots once there was enough ore for clay
+        // robots, and to give up the search too early.
+        let input = example!("test_regression.txt");
+        assert_eq!(solution1(input), Ok(6 + 2 * 2));
+        assert_eq!(solution2(input), Ok(41 * 23));
+    }
+
+    #[test]
+    fn test_params() {
+        let blueprints = parse_blueprints(example!("test.txt")).unwrap();
+        let mut params = Params::default();
+        params.set("minutes1", "18").unwrap();
+        params.set("minutes2", "20").unwrap();
+        params.set("blueprints2", "1").unwrap();
+        let geodes: Vec<_> = blueprints.iter().map(|b| geodes_by_building(b, 18)).collect();
+        assert_eq!(quality_levels(&blueprints, &params), geodes[0] + 2 * geodes[1]);
+        assert_eq!(max_geodes_product(&blueprints, &params), geodes_by_building(&blueprints[0], 20));
+    }
+}
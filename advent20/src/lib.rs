@@ -0,0 +1,174 @@
+
+use aoc_common::{config, Answer, Config, ParseError, Rng, Solution, Source};
+
+type Num = i64;
+
+fn parse(input: &str) -> Result<Vec<Num>, ParseError> {
+    let source = Source::new(Day20::DAY, input);
+    input.trim_end()
+        .split('\n')
+        .map(|x| source.parse(x))
+        .collect()
+}
+
+fn mix(i0: usize, ns: &[Num], is: &mut [usize]) {
+    let mut n = ns[i0];
+    let i = is.iter().copied().position(|j| j == i0).unwrap();
+    let l = ns.len() as Num;
+
+    // Skip the current n in repeated loops.
+    let d = n / (l - 1);
+
+    if n < 0 {
+        n = l * (d.abs() + 1) + n - 1;
+    }
+
+    let j = ((n + d + i as Num) % l) as usize;
+    if i < j {
+        for k in i..j {
+            is.swap(k, k + 1);
+        }
+    } else {
+        for k in (j+2..i+1).rev() {
+            is.swap(k, k - 1);
+        }
+    }
+}
+
+fn groove_coords(ns: &[Num], is: &[usize]) -> Num {
+    let z0 = ns.iter().copied().position(|n| n == 0).unwrap();
+    let z = is.iter().copied().position(|j| j == z0).unwrap();
+    let l = ns.len();
+    [1000, 2000, 3000].into_iter()
+        .map(|i| ns[is[(z + i) % l]]).sum()
+}
+
+fn decrypt(ns: &[Num]) -> Num {
+    let l = ns.len();
+    let mut is: Vec<usize> = (0..l).collect();
+    for i0 in 0..l {
+        mix(i0, ns, &mut is);
+    }
+    groove_coords(ns, &is)
+}
+
+fn decrypt_with_key(ns: &[Num], params: &Params) -> Num {
+    let ns: Vec<Num> = ns.iter().map(|n| n * params.key).collect();
+    let l = ns.len();
+    let mut is: Vec<usize> = (0..l).collect();
+    for _ in 0..params.rounds {
+        for i0 in 0..l {
+            mix(i0, &ns, &mut is);
+        }
+    }
+    groove_coords(&ns, &is)
+}
+
+/// Non-zero numbers with a single zero at a random position.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut numbers: Vec<Num> = (1..size.max(2))
+        .map(|_| loop {
+            let n = rng.range(-10000..=10000);
+            if n != 0 { break n; }
+        })
+        .collect();
+    numbers.insert(rng.below(numbers.len() + 1), 0);
+    numbers.iter().map(|n| format!("{}\n", n)).collect()
+}
+
+pub fn solution1(input: &str) -> Result<Num, ParseError> {
+    Ok(decrypt(&parse(input)?))
+}
+
+pub fn solution2(input: &str) -> Result<Num, ParseError> {
+    Ok(decrypt_with_key(&parse(input)?, &Params::default()))
+}
+
+pub struct Params {
+    /// Decryption key the numbers are multiplied with in part 2.
+    pub key: Num,
+    /// Mixing rounds in part 2.
+    pub rounds: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { key: 811589153, rounds: 10 }
+    }
+}
+
+impl Config for Params {
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "key" => self.key = config::value(key, value)?,
+            "rounds" => self.rounds = config::value(key, value)?,
+            _ => return Err(config::unknown(key)),
+        }
+        Ok(())
+    }
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+
+    type Input = Vec<Num>;
+    type Params = Params;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(ns: &Self::Input, _params: &Params) -> Result<Answer, String> {
+        Ok(decrypt(ns).into())
+    }
+
+    fn part2(ns: &Self::Input, params: &Params) -> Result<Answer, String> {
+        Ok(decrypt_with_key(ns, params).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::{example, Inputs};
+
+    #[test]
+    fn test_solution1() {
+        let input = example!("test.txt");
+        assert_eq!(solution1(input), Ok(3));
+    }
+
+    #[test]
+    fn test_solution2() {
+        let input = example!("test.txt");
+        assert_eq!(solution2(input), Ok(1623178306));
+    }
+
+    #[test]
+    fn test_params() {
+        let ns = parse(example!("test.txt")).unwrap();
+        let mut params = Params::default();
+        params.set("key", "1").unwrap();
+        params.set("rounds", "1").unwrap();
+        assert_eq!(decrypt_with_key(&ns, &params), decrypt(&ns));
+        assert!(params.set("key", "x").is_err());
+    }
+
+    #[test]
+    fn test_full_solution1() {
+        let Ok(input) = Inputs::from_env().load(Day20::DAY) else { return };
+        assert_eq!(solution1(&input), Ok(7153));
+    }
+
+    #[test]
+    fn test_full_solution2() {
+        let Ok(input) = Inputs::from_env().load(Day20::DAY) else { return };
+        assert_eq!(solution2(&input), Ok(6146976244822));
+    }
+}
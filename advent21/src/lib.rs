@@ -0,0 +1,374 @@
+use std::collections::{HashMap, HashSet};
+
+use aoc_common::{config, Answer, Config, ParseError, Rng, Solution, Source};
+
+type Monkey = String;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Operation { Add, Sub, Mul, Div }
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Job {
+    Number(i64),
+    Eval((Operation, Monkey, Monkey)),
+}
+
+type Monkeys = HashMap<Monkey, Job>;
+
+fn parse(input: &str) -> Result<Monkeys, ParseError> {
+    let source = Source::new(Day21::DAY, input);
+    let mut monkeys = Monkeys::new();
+    let mut jobs = Vec::<(&str, Vec<&str>)>::new();
+    for line in input.trim_end().split('\n') {
+        let mut it = line.split(": ");
+        let monkey = source.next(&mut it, line, "monkey")?;
+        let rhs = source.next(&mut it, line, "job")?;
+        let mut it2 = rhs.split(' ');
+        let a = source.next(&mut it2, rhs, "number or monkey")?;
+        let (job, operands) = if let Ok(number) = a.parse::<i64>() {
+            (Job::Number(number), vec![])
+        } else {
+            let op = source.next(&mut it2, rhs, "operation")?;
+            let b = source.next(&mut it2, rhs, "monkey")?;
+            let operation = match op {
+                "+" => Operation::Add,
+                "-" => Operation::Sub,
+                "*" => Operation::Mul,
+                "/" => Operation::Div,
+                _ => return Err(source.error(op, format!("Unexpected operation: {:?}", op))),
+            };
+            (Job::Eval((operation, a.to_string(), b.to_string())), vec![a, b])
+        };
+        if monkeys.insert(monkey.to_string(), job).is_some() {
+            return Err(source.error(monkey, format!("Duplicate monkey: {:?}", monkey)));
+        }
+        jobs.push((monkey, operands));
+    }
+
+    if let Some(operand) = jobs.iter().flat_map(|(_, operands)| operands).find(|m| !monkeys.contains_key(**m)) {
+        return Err(source.error(operand, format!("Unknown monkey: {:?}", operand)));
+    }
+    if let Some(monkey) = cyclic_monkey(&jobs) {
+        return Err(source.error(monkey, format!("Monkey {:?} depends on itself", monkey)));
+    }
+    Ok(monkeys)
+}
+
+/// Monkey waiting for its own number, if any.
+///
+/// Monkeys are removed in topological order, once the numbers of their
+/// operands are known. The remaining ones are in a cycle or wait for one.
+fn cyclic_monkey<'a>(jobs: &[(&'a str, Vec<&'a str>)]) -> Option<&'a str> {
+    let by_name: HashMap<&str, &(&str, Vec<&str>)> = jobs.iter().map(|job| (job.0, job)).collect();
+    let mut waiting: HashMap<&str, usize> = jobs.iter().map(|(monkey, operands)| (*monkey, operands.len())).collect();
+    let mut dependents = HashMap::<&str, Vec<&str>>::new();
+    for (monkey, operands) in jobs {
+        for operand in operands {
+            dependents.entry(operand).or_default().push(monkey);
+        }
+    }
+    let mut known: Vec<&str> = jobs.iter().filter(|(_, operands)| operands.is_empty()).map(|job| job.0).collect();
+    while let Some(monkey) = known.pop() {
+        waiting.remove(monkey);
+        for dependent in dependents.get(monkey).into_iter().flatten() {
+            let count = waiting.get_mut(dependent).unwrap();
+            *count -= 1;
+            if *count == 0 {
+                known.push(dependent);
+            }
+        }
+    }
+
+    // Following waiting operands from any waiting monkey ends in a cycle.
+    let mut monkey = jobs.iter().find(|(monkey, _)| waiting.contains_key(monkey))?.0;
+    let mut visited = HashSet::new();
+    while visited.insert(monkey) {
+        let operand = by_name[monkey].1.iter().find(|m| waiting.contains_key(*m)).unwrap();
+        monkey = by_name[operand].0;
+    }
+    Some(monkey)
+}
+
+fn apply(monkey: &Monkey, operation: Operation, a: i64, b: i64) -> Result<i64, String> {
+    let result = match operation {
+        Operation::Add => a.checked_add(b),
+        Operation::Sub => a.checked_sub(b),
+        Operation::Mul => a.checked_mul(b),
+        Operation::Div if b == 0 => return Err(format!("Monkey {:?}: Division by zero", monkey)),
+        Operation::Div => a.checked_div(b),
+    };
+    result.ok_or_else(|| format!("Monkey {:?}: Number overflow", monkey))
+}
+
+fn eval(monkey: &Monkey, ms: &Monkeys) -> Result<i64, String> {
+    match &ms[monkey] {
+        Job::Number(number) => Ok(*number),
+        Job::Eval((operation, a, b)) => apply(monkey, *operation, eval(a, ms)?, eval(b, ms)?),
+    }
+}
+
+/// Number for `monkey` so that both operands of `root` are equal.
+fn eval2(monkey: &Monkey, root: &Monkey, ms: &Monkeys) -> Result<i64, String> {
+    for (m, rhs) in ms {
+        let Job::Eval((operation, a, b)) = rhs else { continue };
+        if m == root || (a != monkey && b != monkey) {
+            continue;
+        }
+        let value = eval2(m, root, ms)?;
+        return if a == monkey {
+            let b = eval(b, ms)?;
+            match operation {
+                Operation::Add => apply(m, Operation::Sub, value, b),
+                Operation::Sub => apply(m, Operation::Add, value, b),
+                Operation::Mul => apply(m, Operation::Div, value, b),
+                Operation::Div => apply(m, Operation::Mul, value, b),
+            }
+        } else {
+            let a = eval(a, ms)?;
+            match operation {
+                Operation::Add => apply(m, Operation::Sub, value, a),
+                Operation::Sub => apply(m, Operation::Sub, a, value),
+                Operation::Mul => apply(m, Operation::Div, value, a),
+                Operation::Div => apply(m, Operation::Div, a, value),
+            }
+        };
+    }
+    match &ms[root] {
+        Job::Eval((_, a, b)) if a == monkey => eval(b, ms),
+        Job::Eval((_, a, b)) if b == monkey => eval(a, ms),
+        Job::Eval(_) => Err(format!("Monkey {:?} does not wait for monkey {:?}", root, monkey)),
+        Job::Number(_) => Err(format!("Monkey {:?} yells a number instead of comparing two", root)),
+    }
+}
+
+fn generate_name(rng: &mut Rng, names: &mut HashSet<String>) -> String {
+    loop {
+        let name: String = (0..4).map(|_| (b'a' + rng.below(26) as u8) as char).collect();
+        if names.insert(name.clone()) {
+            return name;
+        }
+    }
+}
+
+/// Monkey yelling `value`, numbers are positive and divisions exact.
+fn generate_job(rng: &mut Rng, names: &mut HashSet<String>, jobs: &mut Vec<String>, value: i64, depth: usize) -> Monkey {
+    let monkey = generate_name(rng, names);
+    let divisors: Vec<i64> = (2..=10).filter(|d| value % d == 0).collect();
+    let (a, op, b) = match rng.below(4) {
+        _ if depth == 0 && value > 0 => {
+            jobs.push(format!("{}: {}", monkey, value));
+            return monkey;
+        },
+        0 if value > 1 => {
+            let a = rng.range(1..=value - 1);
+            (a, '+', value - a)
+        },
+        1 if value > 0 && !divisors.is_empty() => {
+            let d = *rng.choose(&divisors);
+            (value / d, '*', d)
+        },
+        2 if value > 0 && value < 1000000 => {
+            let d = rng.range(2..=10);
+            (value * d, '/', d)
+        },
+        _ => {
+            let b = rng.range(1..=20) + (-value).max(0);
+            (value + b, '-', b)
+        },
+    };
+    let a = generate_job(rng, names, jobs, a, depth.saturating_sub(1));
+    let b = generate_job(rng, names, jobs, b, depth.saturating_sub(1));
+    jobs.push(format!("{}: {} {} {}", monkey, a, op, b));
+    monkey
+}
+
+/// Expression tree with "humn" appearing once, never as a divisor, so
+/// that the equality has a single integer solution.
+fn generate(rng: &mut Rng, size: usize, params: &Params) -> String {
+    let mut names = HashSet::from([params.root.clone(), params.human.clone()]);
+    let mut jobs = vec![format!("{}: {}", params.human, rng.range(1..=1000))];
+    // Value of the monkeys on the path from "humn" to "root" when the
+    // equality holds.
+    let mut value = rng.range(1..=1000);
+    let mut monkey = params.human.clone();
+    for _ in 0..(size / 8).clamp(1, 12) {
+        let operand = rng.range(1..=10);
+        let (job, next) = match rng.below(6) {
+            0 => (format!("{} + {{}}", monkey), value + operand),
+            1 => (format!("{{}} + {}", monkey), value + operand),
+            2 => (format!("{} - {{}}", monkey), value - operand),
+            3 => (format!("{{}} - {}", monkey), operand - value),
+            4 if value % operand == 0 => (format!("{} / {{}}", monkey), value / operand),
+            _ if value.abs() < 1000000000 => (format!("{} * {{}}", monkey), value * operand),
+            _ => (format!("{} - {{}}", monkey), value - operand),
+        };
+        let depth = rng.below(4);
+        let operand = generate_job(rng, &mut names, &mut jobs, operand, depth);
+        let name = generate_name(rng, &mut names);
+        jobs.push(format!("{}: {}", name, job.replace("{}", &operand)));
+        (monkey, value) = (name, next);
+    }
+    let depth = rng.below(6);
+    let other = generate_job(rng, &mut names, &mut jobs, value, depth);
+    jobs.push(format!("{}: {} + {}", params.root, monkey, other));
+    rng.shuffle(&mut jobs);
+    jobs.iter().map(|job| format!("{}\n", job)).collect()
+}
+
+/// Fails with the first monkey missing in the input.
+fn has_monkeys(ms: &Monkeys, monkeys: &[&Monkey]) -> Result<(), String> {
+    match monkeys.iter().find(|m| !ms.contains_key(**m)) {
+        Some(monkey) => Err(format!("No monkey {:?}", monkey)),
+        None => Ok(()),
+    }
+}
+
+fn root_number(ms: &Monkeys, params: &Params) -> Result<i64, String> {
+    has_monkeys(ms, &[&params.root])?;
+    eval(&params.root, ms)
+}
+
+fn human_number(ms: &Monkeys, params: &Params) -> Result<i64, String> {
+    has_monkeys(ms, &[&params.root, &params.human])?;
+    if params.human == params.root {
+        return Err(format!("Monkey {:?} cannot compare its own number", params.root));
+    }
+    eval2(&params.human, &params.root, ms)
+}
+
+/// Unsolvable input is reported at the whole input.
+fn solve(input: &str, f: fn(&Monkeys, &Params) -> Result<i64, String>) -> Result<i64, ParseError> {
+    let source = Source::new(Day21::DAY, input);
+    f(&parse(input)?, &Params::default()).map_err(|e| source.error(input.trim_end(), e))
+}
+
+pub fn solution1(input: &str) -> Result<i64, ParseError> {
+    solve(input, root_number)
+}
+
+pub fn solution2(input: &str) -> Result<i64, ParseError> {
+    solve(input, human_number)
+}
+
+pub struct Params {
+    /// Monkey yelling the answer in part 1, comparing two numbers in part 2.
+    pub root: Monkey,
+    /// Monkey whose number is to be found in part 2.
+    pub human: Monkey,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { root: "root".to_string(), human: "humn".to_string() }
+    }
+}
+
+impl Config for Params {
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "root" => self.root = value.to_string(),
+            "human" => self.human = value.to_string(),
+            _ => return Err(config::unknown(key)),
+        }
+        Ok(())
+    }
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+
+    type Input = Monkeys;
+    type Params = Params;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(ms: &Self::Input, params: &Params) -> Result<Answer, String> {
+        root_number(ms, params).map(Answer::from)
+    }
+
+    fn part2(ms: &Self::Input, params: &Params) -> Result<Answer, String> {
+        human_number(ms, params).map(Answer::from)
+    }
+
+    fn generate(rng: &mut Rng, size: usize, params: &Params) -> String {
+        generate(rng, size, params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::example;
+
+    #[test]
+    fn test_solution1() {
+        let input = example!("test.txt");
+        assert_eq!(solution1(input), Ok(152));
+    }
+
+    #[test]
+    fn test_solution2() {
+        let input = example!("test.txt");
+        assert_eq!(solution2(input), Ok(301));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = example!("test.txt");
+        let e = parse(&input.replace("root: pppw + sjmn", "root: pppw % sjmn")).unwrap_err();
+        assert_eq!((e.line, e.column), (1, 12));
+        assert_eq!(e.message, "Unexpected operation: \"%\"");
+
+        let e = parse(&input.replace("root: pppw + sjmn", "root: pppw + sjmm")).unwrap_err();
+        assert_eq!((e.line, e.column, e.width), (1, 14, 4));
+        assert_eq!(e.message, "Unknown monkey: \"sjmm\"");
+
+        let e = parse(&input.replace("root: pppw + sjmn", "root: pppw + root")).unwrap_err();
+        assert_eq!((e.line, e.column, e.width), (1, 1, 4));
+        assert_eq!(e.message, "Monkey \"root\" depends on itself");
+
+        let e = parse("root: a + b\na: b * c\nb: a - c\nc: 1\n").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 1));
+        assert_eq!(e.message, "Monkey \"a\" depends on itself");
+
+        let e = parse("root: 1\nroot: 2\n").unwrap_err();
+        assert_eq!(e.message, "Duplicate monkey: \"root\"");
+    }
+
+    #[test]
+    fn test_eval_error() {
+        let ms = parse("root: a / b\na: 1\nb: 0\n").unwrap();
+        assert_eq!(root_number(&ms, &Params::default()), Err("Monkey \"root\": Division by zero".to_string()));
+        let ms = parse("root: a * a\na: 9000000000000000000\nhumn: 1\n").unwrap();
+        assert_eq!(root_number(&ms, &Params::default()), Err("Monkey \"root\": Number overflow".to_string()));
+        assert_eq!(human_number(&ms, &Params::default()), Err("Monkey \"root\" does not wait for monkey \"humn\"".to_string()));
+
+        let ms = parse(example!("test.txt")).unwrap();
+        let params = Params { root: "sllz".to_string(), human: "humn".to_string() };
+        assert_eq!(human_number(&ms, &params), Err("Monkey \"sllz\" yells a number instead of comparing two".to_string()));
+    }
+
+    #[test]
+    fn test_params() {
+        let ms = parse(example!("test.txt")).unwrap();
+        let mut params = Params::default();
+        params.set("root", "pppw").unwrap();
+        // (sllz + ljgn * (humn - dvpt)) / lfqf
+        assert_eq!(root_number(&ms, &params), Ok((4 + 2 * (5 - 3)) / 4));
+        params.set("root", "root").unwrap();
+        params.set("human", "sllz").unwrap();
+        // (sllz + lgvd) / lfqf = (hmdt - zczc) * dbpl
+        assert_eq!(human_number(&ms, &params), Ok((32 - 2) * 5 * 4 - 4));
+
+        params.set("human", "none").unwrap();
+        assert_eq!(human_number(&ms, &params), Err("No monkey \"none\"".to_string()));
+
+        let params = Params { root: "main".to_string(), human: "me".to_string() };
+        let input = generate(&mut Rng::new(2), 20, &params);
+        assert!(human_number(&parse(&input).unwrap(), &params).is_ok_and(|n| n != 0));
+    }
+}
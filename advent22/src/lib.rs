@@ -0,0 +1,431 @@
+use std::iter;
+
+use aoc_common::visual::{self, Cell, Color, Frame};
+use aoc_common::{Answer, ParseError, Point, Rng, Solution, Source, SparseGrid};
+
+type Pos = Point;
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Tile {Open, Wall}
+type Map = SparseGrid<Tile>;
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Turn {L = -1, R = 1}
+type Move = (u8, Turn);
+type Path = Vec<Move>;
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Facing {
+    Right = 0,
+    Down = 1,
+    Left = 2,
+    Up = 3,
+}
+const FACINGS: [Facing; 4] = [Facing::Right, Facing::Down, Facing::Left, Facing::Up];
+
+fn parse(input: &str) -> Result<(Map, Path), ParseError> {
+    let source = Source::new(Day22::DAY, input);
+    let mut it = input.trim_end().split("\n\n");
+    let map_block = source.next(&mut it, input, "map")?;
+    let map = Map::parse(&source, map_block, "map tile", &[' '], |c| match c {
+        '.' => Some(Tile::Open),
+        '#' => Some(Tile::Wall),
+        _ => None,
+    })?;
+    // Positions are numbered from one.
+    let map: Map = map.iter().map(|(p, &tile)| (p + Point::new(1, 1), tile)).collect();
+    if map.is_empty() {
+        return Err(source.missing(map_block, "map"));
+    }
+
+    let path_block = source.next(&mut it, map_block, "path")?;
+    let path = path_block
+        .split_inclusive(&['R', 'L'][..])
+        .map(|m| {
+            let turn = match &m[m.len() - 1..m.len()] {
+                "L" => Turn::L,
+                "R" => Turn::R,
+                _ => return Ok((source.parse::<u8>(m)?, Turn::R)),
+            };
+            let forward = source.parse::<u8>(&m[0..m.len() - 1])?;
+            Ok((forward, turn))
+        })
+        .chain(iter::once(Ok((0, Turn::L))))
+        .collect::<Result<_, _>>()?;
+    Ok((map, path))
+}
+
+fn facing_char(f: Facing) -> char {
+    match f {
+        Facing::Right => '>',
+        Facing::Down => 'v',
+        Facing::Left => '<',
+        Facing::Up => '^',
+    }
+}
+
+struct View<'a> {
+    map: &'a Map,
+    size: Point<usize>,
+    pos: Pos,
+    f: Facing,
+}
+
+impl Frame for View<'_> {
+    fn size(&self) -> Point<usize> {
+        self.size
+    }
+
+    fn cell(&self, p: Point<usize>) -> Cell {
+        let p = Point::new(p.x as i32, p.y as i32);
+        if p == self.pos {
+            return Cell::new(facing_char(self.f), Color::Red);
+        }
+        match self.map.get(p) {
+            Some(Tile::Open) => '.'.into(),
+            Some(Tile::Wall) => Cell::new('#', Color::Yellow),
+            None => ' '.into(),
+        }
+    }
+
+    fn focus(&self) -> Option<Point<usize>> {
+        Some(Point::new(self.pos.x as usize, self.pos.y as usize))
+    }
+
+    fn status(&self) -> String {
+        format!("{},{} {}", self.pos.x, self.pos.y, facing_char(self.f))
+    }
+}
+
+fn render(map: &Map, size: Point<usize>, pos: Pos, f: Facing) {
+    if visual::enabled() {
+        visual::show(&View { map, size, pos, f });
+    }
+}
+
+type WrapFn<'a> = dyn FnMut(Pos, Facing) -> Result<(Pos, Facing), String> + 'a;
+
+fn solution(map: &Map, path: &Path, wrap_fn: &mut WrapFn) -> Result<usize, String> {
+    let mut pos = map.points().min_by_key(|p| (p.y, p.x)).unwrap();
+    let mut f = Facing::Right;
+    let max = map.bounding_box().unwrap().max;
+    let size = Point::new(max.x as usize + 1, max.y as usize + 1);
+    for (fw, turn) in path.iter().copied() {
+        for _i in 0..fw {
+            let p2 = match f {
+                Facing::Right => Point::new(pos.x + 1, pos.y),
+                Facing::Down => Point::new(pos.x, pos.y + 1),
+                Facing::Left => Point::new(pos.x - 1, pos.y),
+                Facing::Up => Point::new(pos.x, pos.y - 1),
+            };
+            match map.get(p2) {
+                Some(Tile::Open) => pos = p2,
+                Some(Tile::Wall) => break,
+                None => {
+                    let (p3, f2) = wrap_fn(pos, f)?;
+                    if map.get(p3) == Some(&Tile::Wall) { break; }
+                    pos = p3;
+                    f = f2;
+                }
+            }
+            render(map, size, pos, f);
+        }
+        f = FACINGS[(4 + f as i8 + turn as i8) as usize % 4];
+    }
+    Ok(1000 * pos.y as usize + 4 * pos.x as usize + f as usize)
+}
+
+fn password(map: &Map, path: &Path) -> usize {
+    let wrapped = solution(map, path, &mut |p2: Pos, f: Facing| {
+        let it = map.points();
+        let p3 = match f {
+            Facing::Right =>
+                it.filter(|p| p.y == p2.y).min_by_key(|p| p.x).unwrap(),
+            Facing::Down =>
+                it.filter(|p| p.x == p2.x).min_by_key(|p| p.y).unwrap(),
+            Facing::Left =>
+                it.filter(|p| p.y == p2.y).max_by_key(|p| p.x).unwrap(),
+            Facing::Up =>
+                it.filter(|p| p.x == p2.x).max_by_key(|p| p.y).unwrap(),
+        };
+        Ok((p3, f))
+    });
+    wrapped.expect("Wrapping around a row or column always succeeds")
+}
+
+/// Side of the faces of the cube net, which must be one of the supported
+/// layouts.
+fn cube_side(map: &Map) -> Result<i32, String> {
+    let bounds = map.bounding_box().unwrap();
+    let side = (bounds.max.x.max(bounds.max.y) / 4).max(1);
+    let mut faces: Vec<(usize, usize)> = map.points()
+        .map(|p| (((p.x - 1) / side) as usize, ((p.y - 1) / side) as usize))
+        .collect();
+    faces.sort_by_key(|&(x, y)| (y, x));
+    faces.dedup();
+    if map.len() != 6 * (side * side) as usize || ![EXAMPLE_FACES, FACES].iter().any(|net| net[..] == faces[..]) {
+        let faces: Vec<String> = faces.iter().map(|(x, y)| format!("{},{}", x, y)).collect();
+        return Err(format!("Unsupported cube net with side {} and faces at {}", side, faces.join(" ")));
+    }
+    Ok(side)
+}
+
+fn cube_password(map: &Map, path: &Path) -> Result<usize, String> {
+    let side = cube_side(map)?;
+    let unsupported = |p: Pos, f: Facing| {
+        Err(format!("Cannot wrap around the cube net at {},{} {}", p.x, p.y, facing_char(f)))
+    };
+    solution(map, path, &mut |p: Pos, f: Facing| {
+        let (x, y) = (p.x - 1, p.y - 1);
+        let next_side = |xy: i32| (xy / side + 1) * side;
+        let prev_side = |xy: i32| (xy / side - 1) * side;
+        let wrap = |xy: i32| side - (xy % side) - 1;
+        match f {
+            Facing::Right => {
+                let p2 = Point::new(
+                    next_side(x) + wrap(y) + 1,
+                    next_side(y) + wrap(x) + 1,
+                );
+                if map.contains(p2) { return Ok((p2, Facing::Down)); }
+
+                if y >= side {
+                    let p2 = Point::new(
+                        next_side(x) + y % side + 1,
+                        prev_side(y) + side - 1 + 1,
+                    );
+                    if map.contains(p2) { return Ok((p2, Facing::Up)); }
+                }
+
+                if y >= 2 * side {
+                    let p2 = Point::new(
+                        next_side(x) + side - 1 + 1,
+                        prev_side(prev_side(y)) + wrap(y) + 1,
+                    );
+                    if map.contains(p2) { return Ok((p2, Facing::Left)); }
+                }
+
+                if x >= side {
+                    let p2 = Point::new(
+                        prev_side(x) + side - 1 + 1,
+                        next_side(next_side(y)) + wrap(y) + 1,
+                    );
+                    if map.contains(p2) { return Ok((p2, Facing::Left)); }
+                }
+
+                unsupported(p, f)
+            },
+            Facing::Down => {
+                if x >= 2 * side && y >= side {
+                    let p2 = Point::new(
+                        prev_side(x - side) + wrap(x) + 1,
+                        prev_side(y) + side - 1 + 1,
+                    );
+                    if map.contains(p2) { return Ok((p2, Facing::Up)); }
+                }
+
+                if x >= side {
+                    let p2 = Point::new(
+                        prev_side(x) + side - 1 + 1,
+                        next_side(y) + x % side + 1,
+                    );
+                    if map.contains(p2) { return Ok((p2, Facing::Left)); }
+                }
+
+                if y >= 3 * side {
+                    let p2 = Point::new(
+                        next_side(next_side(x)) + x % side + 1,
+                        prev_side(prev_side(prev_side(y))) + 1,
+                    );
+                    if map.contains(p2) { return Ok((p2, Facing::Down)); }
+                }
+
+                unsupported(p, f)
+            },
+            Facing::Left => {
+                if x >= side {
+                    let p2 = Point::new(
+                        prev_side(x) + y % side + 1,
+                        next_side(y) + 1,
+                    );
+                    if map.contains(p2) { return Ok((p2, Facing::Down)); }
+                }
+
+                if x == 0 && y >= 3 * side {
+                    let p2 = Point::new(
+                        next_side(x) + y % side + 1,
+                        prev_side(prev_side(prev_side(y))) + 1,
+                    );
+                    if map.contains(p2) { return Ok((p2, Facing::Down)); }
+                }
+
+                if x == 0 && y >= 2 * side {
+                    let p2 = Point::new(
+                        next_side(x) + 1,
+                        prev_side(prev_side(y)) + wrap(y) + 1,
+                    );
+                    if map.contains(p2) { return Ok((p2, Facing::Right)); }
+                }
+
+                if x >= side {
+                    let p2 = Point::new(
+                        prev_side(x) + 1,
+                        next_side(next_side(y)) + wrap(y) + 1,
+                    );
+                    if map.contains(p2) { return Ok((p2, Facing::Right)); }
+                }
+
+                unsupported(p, f)
+            },
+            Facing::Up => {
+                if y >= side {
+                    let p2 = Point::new(
+                        next_side(x) + y % side + 1,
+                        prev_side(y) + x % side + 1,
+                    );
+                    if map.contains(p2) { return Ok((p2, Facing::Right)); }
+                }
+
+                if x >= side && y == 0 {
+                    let p2 = Point::new(
+                        prev_side(x) + 1,
+                        next_side(next_side(next_side(y))) + x % side + 1,
+                    );
+                    if map.contains(p2) { return Ok((p2, Facing::Right)); }
+                }
+
+                if y == 0 {
+                    let p2 = Point::new(
+                        prev_side(prev_side(x)) + x % side + 1,
+                        next_side(next_side(next_side(y))) + side - 1 + 1,
+                    );
+                    if map.contains(p2) { return Ok((p2, Facing::Up)); }
+                }
+
+                unsupported(p, f)
+            },
+        }
+    })
+}
+
+/// Faces of the cube net in the same layout as in the real input, which is
+/// the only one folding supports besides the example.
+const FACES: [(usize, usize); 6] = [(1, 0), (2, 0), (1, 1), (0, 2), (1, 2), (0, 3)];
+const EXAMPLE_FACES: [(usize, usize); 6] = [(2, 0), (0, 1), (1, 1), (2, 1), (2, 2), (3, 2)];
+
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let side = (size / 2).clamp(2, 50);
+    let rows: Vec<String> = (0..4 * side)
+        .map(|y| {
+            let row: String = (0..3 * side)
+                .map(|x| if !FACES.contains(&(x / side, y / side)) {
+                    ' '
+                } else if (x, y) != (side, 0) && rng.chance(0.1) {
+                    '#'
+                } else {
+                    '.'
+                })
+                .collect();
+            row.trim_end().to_string()
+        })
+        .collect();
+    let mut path = rng.range(1..=2 * side as i64).to_string();
+    for _ in 1..size.max(1) {
+        path += *rng.choose(&["L", "R"]);
+        path += &rng.range(1..=2 * side as i64).to_string();
+    }
+    format!("{}\n\n{}\n", rows.join("\n"), path)
+}
+
+pub fn solution1(input: &str) -> Result<usize, ParseError> {
+    let (map, path) = parse(input)?;
+    Ok(password(&map, &path))
+}
+
+pub fn solution2(input: &str) -> Result<usize, ParseError> {
+    let source = Source::new(Day22::DAY, input);
+    let (map, path) = parse(input)?;
+    cube_password(&map, &path).map_err(|e| source.error(input.trim_end(), e))
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: u8 = 22;
+
+    type Input = (Map, Path);
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1((map, path): &Self::Input, _params: &()) -> Result<Answer, String> {
+        Ok(password(map, path).into())
+    }
+
+    fn part2((map, path): &Self::Input, _params: &()) -> Result<Answer, String> {
+        cube_password(map, path).map(Answer::from)
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &()) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::{example, Inputs};
+
+    #[test]
+    fn test_solution1() {
+        let input = example!("test.txt");
+        assert_eq!(solution1(input), Ok(6032));
+    }
+
+    #[test]
+    fn test_solution2() {
+        let input = example!("test.txt");
+        assert_eq!(solution2(input), Ok(5031));
+    }
+
+    #[test]
+    fn test_cube_ring() {
+        // Going straight around the cube comes back after four sides, here
+        // from the bottom of the net over the top right side.
+        let map = "  ....\n  ....\n  ..\n  ..\n....\n....\n..\n..\n";
+        for path in ["0L0L1R", "0L0L2R"] {
+            let password = |steps: usize| solution2(&format!("{}\n{}{}\n", map, path, steps)).unwrap();
+            assert_eq!(password(2), password(2 + 4 * 2));
+        }
+    }
+
+    #[test]
+    fn test_unsupported_net() {
+        // Cross, faces in a row and an incomplete face.
+        let cross = ".\n...\n.\n.\n\n5\n";
+        assert_eq!(solution1(cross), Ok(1004));
+        let e = solution2(cross).unwrap_err();
+        assert_eq!(e.message, "Unsupported cube net with side 1 and faces at 0,0 0,1 1,1 2,1 0,2 0,3");
+        let e = solution2("......\n\n5\n").unwrap_err();
+        assert_eq!(e.message, "Unsupported cube net with side 1 and faces at 0,0 1,0 2,0 3,0 4,0 5,0");
+        let input = example!("test.txt").replacen("...#", "...", 1);
+        assert!(solution2(&input).unwrap_err().message.starts_with("Unsupported cube net with side 4"));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let e = parse("..x\n\n1\n").unwrap_err();
+        assert_eq!((e.line, e.column), (1, 3));
+        assert_eq!(e.message, "Unexpected map tile: 'x'");
+    }
+
+    #[test]
+    fn test_full_solution1() {
+        let Ok(input) = Inputs::from_env().load(Day22::DAY) else { return };
+        assert_eq!(solution1(&input), Ok(57350));
+    }
+
+    #[test]
+    fn test_full_solution2() {
+        let Ok(input) = Inputs::from_env().load(Day22::DAY) else { return };
+        assert_eq!(solution2(&input), Ok(104385));
+    }
+}
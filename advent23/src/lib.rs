@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+
+use aoc_common::visual::{self, Cell, Color, Frame};
+use aoc_common::{Answer, BoundingBox, Grid, ParseError, Point, Rng, Solution, Source, SparseGrid};
+
+type Pos = Point;
+type Map = SparseGrid;
+
+fn parse(input: &str) -> Result<Map, ParseError> {
+    let source = Source::new(Day23::DAY, input);
+    let map = Map::parse(&source, input, "tile", &['.'], |c| (c == '#').then_some(()))?;
+    if map.is_empty() {
+        return Err(source.missing(input.trim_end(), "elves"));
+    }
+    Ok(map)
+}
+
+struct View<'a> {
+    map: &'a Map,
+    /// Area taken by elves in any round so far, so that frames do not shrink.
+    bounds: BoundingBox,
+    round: usize,
+}
+
+impl Frame for View<'_> {
+    fn size(&self) -> Point<usize> {
+        Point::new(self.bounds.width(), self.bounds.height())
+    }
+
+    fn cell(&self, p: Point<usize>) -> Cell {
+        let p = self.bounds.min + Point::new(p.x as i32, p.y as i32);
+        if self.map.contains(p) { Cell::new('#', Color::Green) } else { '.'.into() }
+    }
+
+    fn status(&self) -> String {
+        format!("round {}", self.round)
+    }
+}
+
+fn render(map: &Map, bounds: &mut BoundingBox, round: usize) {
+    if visual::enabled() {
+        let current = map.bounding_box().unwrap();
+        bounds.extend(current.min);
+        bounds.extend(current.max);
+        visual::show(&View { map, bounds: *bounds, round });
+    }
+}
+
+fn solution(m: &Map, rounds: usize) -> (usize, usize) {
+    let mut m = m.clone();
+    let mut dir = 0;
+    let mut round = 0;
+    let mut bounds = m.bounding_box().unwrap();
+    render(&m, &mut bounds, round);
+    loop {
+        if round > 0 && round == rounds { break; }
+        round += 1;
+
+        let mut collide = HashMap::<Pos, Option<Pos>>::new();
+        for p in m.points() {
+            // Clockwise from north: n, ne, e, se, s, sw, w, nw
+            let free = p.neighbors8().map(|p| !m.contains(p));
+            if free.iter().all(|&f| f) { continue; }
+            let [n, ne, e, se, s, sw, w, nw] = free;
+
+            for i in 0..4 {
+                let d = (dir + i) % 4;
+                let to = match d {
+                    0 if n && ne && nw => Point::new(p.x, p.y - 1),
+                    1 if s && se && sw => Point::new(p.x, p.y + 1),
+                    2 if w && nw && sw => Point::new(p.x - 1, p.y),
+                    3 if e && ne && se => Point::new(p.x + 1, p.y),
+                    0..=3 => continue,
+                    _ => unreachable!(),
+                };
+                collide.entry(to).and_modify(|e| *e = None).or_insert_with(|| Some(p));
+                break;
+            }
+        }
+
+        dir = (dir + 1) % 4;
+
+        let mut moved = false;
+        for (to, from) in collide {
+            if let Some(from) = from {
+                m.remove(from);
+                m.insert(to, ());
+                moved = true;
+            }
+        }
+        render(&m, &mut bounds, round);
+        if !moved { break; }
+    }
+    (m.bounding_box().unwrap().area() - m.len(), round)
+}
+
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let side = (size / 2).max(1);
+    Grid::from_fn(side, side, |p| p == Point::new(side / 2, side / 2) || rng.chance(0.5))
+        .render(|_, &elf| if elf { '#' } else { '.' })
+}
+
+pub fn solution1(input: &str) -> Result<usize, ParseError> {
+    Ok(solution(&parse(input)?, 10).0)
+}
+
+pub fn solution2(input: &str) -> Result<usize, ParseError> {
+    Ok(solution(&parse(input)?, 0).1)
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u8 = 23;
+
+    type Input = Map;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(m: &Self::Input, _params: &()) -> Result<Answer, String> {
+        Ok(solution(m, 10).0.into())
+    }
+
+    fn part2(m: &Self::Input, _params: &()) -> Result<Answer, String> {
+        Ok(solution(m, 0).1.into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &()) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::example;
+
+    #[test]
+    fn test_solution1() {
+        let input = example!("test.txt");
+        assert_eq!(solution1(input), Ok(110));
+    }
+
+    #[test]
+    fn test_simple_solution1() {
+        let input = example!("test_simple.txt");
+        assert_eq!(solution1(input), Ok(25));
+    }
+
+    #[test]
+    fn test_solution2() {
+        let input = example!("test.txt");
+        assert_eq!(solution2(input), Ok(20));
+    }
+}
@@ -0,0 +1,265 @@
+use std::iter;
+
+use aoc_common::search::dijkstra;
+use aoc_common::visual::{self, Cell, Color, Frame};
+use aoc_common::{lcm, Answer, Direction, Grid, ParseError, Point, Rng, Solution, Source};
+
+const UP: u8 = 0b1;
+const DOWN: u8 = 0b10;
+const LEFT: u8 = 0b100;
+const RIGHT: u8 = 0b1000;
+
+const BLIZZARDS: [(u8, Direction); 4] = [
+    (UP, Direction::Up),
+    (DOWN, Direction::Down),
+    (LEFT, Direction::Left),
+    (RIGHT, Direction::Right),
+];
+
+type Pos = Point<usize>;
+type Entities = u8;
+type Map = Grid<Entities>;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+struct State {
+    pos: Pos,
+    /// Minutes modulo the period of blizzards.
+    minute: usize,
+    return_times: u8,
+}
+
+fn parse(input: &str) -> Result<Map, ParseError> {
+    let source = Source::new(Day24::DAY, input);
+    let tiles = Grid::parse(&source, input, "tile", |c| match c {
+        '^' => Some(UP),
+        'v' => Some(DOWN),
+        '<' => Some(LEFT),
+        '>' => Some(RIGHT),
+        '.' | '#' => Some(0),
+        _ => None,
+    })?;
+
+    if tiles.width() < 3 || tiles.height() < 3 || tiles.iter().all(|(_, &v)| v == 0) {
+        return Err(source.missing(input.trim_end(), "blizzards"));
+    }
+    // Blizzards wrap around inside the walls.
+    Ok(Grid::from_fn(tiles.width() - 2, tiles.height() - 2, |p| tiles[p + Point::new(1, 1)]))
+}
+
+struct View<'a> {
+    map: &'a Map,
+    /// Expedition, if it is already in the valley.
+    pos: Option<Pos>,
+    minutes: usize,
+}
+
+impl Frame for View<'_> {
+    fn size(&self) -> Point<usize> {
+        Point::new(self.map.width(), self.map.height())
+    }
+
+    fn cell(&self, p: Point<usize>) -> Cell {
+        if self.pos == Some(p) {
+            return Cell::new('X', Color::Red);
+        }
+        match self.map[p] {
+            0 => '.'.into(),
+            UP => '^'.into(),
+            DOWN => 'v'.into(),
+            LEFT => '<'.into(),
+            RIGHT => '>'.into(),
+            _ => Cell::new('o', Color::Cyan),
+        }
+    }
+
+    fn focus(&self) -> Option<Point<usize>> {
+        self.pos
+    }
+
+    fn status(&self) -> String {
+        format!("minute {}", self.minutes)
+    }
+}
+
+fn render(map: &Map, pos: Option<Pos>, minutes: usize) {
+    if visual::enabled() {
+        visual::show(&View { map, pos, minutes });
+    }
+}
+
+fn simulate(m: &mut Map) {
+    let mut m2 = Grid::new(m.width(), m.height(), 0);
+    for (p, &v) in m.iter() {
+        for (blizzard, direction) in BLIZZARDS {
+            if v & blizzard != 0 {
+                m2[m.wrapping_step(p, direction)] |= blizzard;
+            }
+        }
+    }
+    *m = m2;
+}
+
+fn solution(m: &Map, start: Pos, exit: Pos, return_times: u8) -> Result<usize, String> {
+    // Blizzards are back at their initial positions after this many minutes.
+    let repeat = lcm(m.width(), m.height());
+    let maps: Vec<Map> = iter::successors(Some(m.clone()), |m| {
+        let mut m = m.clone();
+        simulate(&mut m);
+        Some(m)
+    }).take(repeat).collect();
+    let free = |pos: Pos, minutes: usize| maps[minutes % repeat][pos] == 0;
+
+    // wait until we can move
+    let mut first = 1;
+    while !free(start, first) {
+        render(&maps[first % repeat], None, first);
+        first += 1;
+        if first > repeat {
+            return Err("Entrance is always blocked".to_string());
+        }
+    }
+
+    let finish = |s: &State| if s.return_times & 1 == 0 { exit } else { start };
+    let outcome = dijkstra(
+        [State{pos: start, minute: first % repeat, return_times}],
+        |s, cost| {
+            let minutes = first + cost;
+            let mut next = Vec::new();
+            if !free(s.pos, minutes) { return next; }
+
+            render(&maps[s.minute], Some(s.pos), minutes);
+
+            if s.pos == finish(s) {
+                // Wait outside the valley for any time before going back.
+                let return_times = s.return_times - 1;
+                for wait in 1..repeat {
+                    next.push((State{minute: (minutes + wait) % repeat, return_times, ..*s}, wait));
+                }
+                return next;
+            }
+
+            let minute = (minutes + 1) % repeat;
+            next.push((State{minute, ..*s}, 1));
+            for pos in m.neighbors4(s.pos) {
+                next.push((State{pos, minute, ..*s}, 1));
+            }
+            next
+        },
+        |s| s.return_times == 0 && s.pos == finish(s) && free(s.pos, s.minute),
+    );
+    outcome.found
+        .map(|found| first + found.cost + 1)
+        .ok_or_else(|| "No way through the valley".to_string())
+}
+
+fn fastest_crossing(m: &Map, return_times: u8) -> Result<usize, String> {
+    let start = Point::new(0, 0);
+    let exit = Point::new(m.width() - 1, m.height() - 1);
+    solution(m, start, exit, return_times)
+}
+
+/// Valley with blizzards which never cross the entrance or the exit, and
+/// with a way there and back again.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let (width, height) = (size / 4 + 4, size / 20 + 3);
+    loop {
+        let valley = Grid::from_fn(width, height, |p| {
+            let vertical = p.x != 0 && p.x != width - 1;
+            match rng.below(8) {
+                0 => '>',
+                1 => '<',
+                2 if vertical => '^',
+                3 if vertical => 'v',
+                _ => '.',
+            }
+        });
+        if valley.iter().all(|(_, &c)| c == '.') { continue; }
+        let rows: String = valley.render(|_, &c| c).lines().map(|row| format!("#{}#\n", row)).collect();
+        let input = format!("#.{}\n{}{}.#\n", "#".repeat(width), rows, "#".repeat(width));
+        if parse(&input).is_ok_and(|m| fastest_crossing(&m, 2).is_ok()) {
+            return input;
+        }
+    }
+}
+
+/// Impassable valley is reported at the whole input.
+fn solve(input: &str, return_times: u8) -> Result<usize, ParseError> {
+    let source = Source::new(Day24::DAY, input);
+    fastest_crossing(&parse(input)?, return_times).map_err(|e| source.error(input.trim_end(), e))
+}
+
+pub fn solution1(input: &str) -> Result<usize, ParseError> {
+    solve(input, 0)
+}
+
+pub fn solution2(input: &str) -> Result<usize, ParseError> {
+    solve(input, 2)
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u8 = 24;
+
+    type Input = Map;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(m: &Self::Input, _params: &()) -> Result<Answer, String> {
+        fastest_crossing(m, 0).map(Answer::from)
+    }
+
+    fn part2(m: &Self::Input, _params: &()) -> Result<Answer, String> {
+        fastest_crossing(m, 2).map(Answer::from)
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &()) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::{example, Inputs};
+
+    #[test]
+    fn test_solution1() {
+        let input = example!("test.txt");
+        assert_eq!(solution1(input), Ok(18));
+    }
+
+    #[test]
+    fn test_solution2() {
+        let input = example!("test.txt");
+        assert_eq!(solution2(input), Ok(54));
+    }
+
+    #[test]
+    fn test_no_way() {
+        let e = solution1("#.###\n#>>>#\n###.#\n").unwrap_err();
+        assert_eq!((e.line, e.column), (1, 1));
+        assert_eq!(e.message, "Entrance is always blocked");
+        let valley = parse("#.###\n#...#\n#>>>#\n###.#\n").unwrap();
+        assert_eq!(fastest_crossing(&valley, 0), Err("No way through the valley".to_string()));
+        for seed in 0..10 {
+            let input = generate(&mut Rng::new(seed), 30);
+            assert!(solution2(&input).is_ok(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_full_solution1() {
+        let Ok(input) = Inputs::from_env().load(Day24::DAY) else { return };
+        assert_eq!(solution1(&input), Ok(271));
+    }
+
+    #[test]
+    fn test_full_solution2() {
+        let Ok(input) = Inputs::from_env().load(Day24::DAY) else { return };
+        assert_eq!(solution2(&input), Ok(813));
+    }
+}
@@ -0,0 +1,156 @@
+use std::io::BufRead;
+
+use aoc_common::{read_lines, Answer, ParseError, Rng, Solution};
+
+type Num = i64;
+type Nums = Vec<Num>;
+
+/// Calls `f` with each SNAFU number.
+fn read_nums(reader: impl BufRead, mut f: impl FnMut(Num)) -> Result<(), ParseError> {
+    read_lines(Day25::DAY, reader, |source, line| {
+        let n = line.char_indices()
+            .try_fold(0 as Num, |n, (i, c)| {
+                let digit = match c {
+                    '0' => 0,
+                    '1' => 1,
+                    '2' => 2,
+                    '-' => -1,
+                    '=' => -2,
+                    _ => return Err(source.error(&line[i..i + c.len_utf8()], format!("Unexpected digit: {:?}", c))),
+                };
+                n.checked_mul(5)
+                    .and_then(|n| n.checked_add(digit))
+                    .ok_or_else(|| source.error(line, "Number is too large"))
+            })?;
+        f(n);
+        Ok(())
+    })
+}
+
+fn parse(input: &str) -> Result<Nums, ParseError> {
+    parse_from(input.as_bytes())
+}
+
+fn parse_from(reader: impl BufRead) -> Result<Nums, ParseError> {
+    let mut ns = Vec::new();
+    read_nums(reader, |n| ns.push(n))?;
+    Ok(ns)
+}
+
+fn to_snafu(i: Num) -> String
+{
+    let mut i = i;
+    let l = (i.abs() as f64).log(5.0).floor() as usize + 1;
+    let mut ns: Vec<i8> = (1..=l).rev().map(|l| {
+        let m = (5 as Num).pow(l as u32 - 1);
+        let n = i / m;
+        i -= m * n;
+        n as i8
+    }).collect();
+
+    let mut carry = 0;
+    for n in ns.iter_mut().rev() {
+        *n += carry;
+        if *n > 2 {
+            *n -= 5;
+            carry = 1;
+        } else {
+            carry = 0;
+        }
+    }
+
+    if carry != 0 || ns.is_empty() {
+        ns.insert(0, carry);
+    }
+
+    let ds = b"=-012";
+    ns.iter().map(|n| { ds[(n+2) as usize] as char }).collect()
+}
+
+fn fuel_sum(ns: &[Num]) -> String {
+    to_snafu(ns.iter().sum())
+}
+
+fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            let mut n = rng.choose(&["1", "2"]).to_string();
+            n.extend((0..rng.below(20)).map(|_| *rng.choose(&['=', '-', '0', '1', '2'])));
+            n.push('\n');
+            n
+        })
+        .collect()
+}
+
+pub fn solution1(input: &str) -> Result<String, ParseError> {
+    solution1_from(input.as_bytes())
+}
+
+pub fn solution1_from(reader: impl BufRead) -> Result<String, ParseError> {
+    let mut sum = 0;
+    read_nums(reader, |n| sum += n)?;
+    Ok(to_snafu(sum))
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u8 = 25;
+
+    type Input = Nums;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn parse_from(reader: impl BufRead) -> Result<Self::Input, ParseError> {
+        parse_from(reader)
+    }
+
+    fn part1(ns: &Self::Input, _params: &()) -> Result<Answer, String> {
+        Ok(fuel_sum(ns).into())
+    }
+
+    fn part2(_ns: &Self::Input, _params: &()) -> Result<Answer, String> {
+        Ok(Answer::None)
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &()) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::example;
+
+    #[test]
+    fn test_to_snafu() {
+        assert_eq!(to_snafu(0), "0");
+        assert_eq!(to_snafu(1), "1");
+        assert_eq!(to_snafu(2), "2");
+        assert_eq!(to_snafu(3), "1=");
+        assert_eq!(to_snafu(4), "1-");
+        assert_eq!(to_snafu(2022), "1=11-2");
+        assert_eq!(to_snafu(12345), "1-0---0");
+        assert_eq!(to_snafu(314159265), "1121-1110-1=0");
+        assert_eq!(to_snafu(4890), "2=-1=0");
+    }
+
+    #[test]
+    fn test_solution1() {
+        let input = example!("test.txt");
+        assert_eq!(solution1(input), Ok("2=-1=0".to_string()));
+    }
+
+    #[test]
+    fn test_reader() {
+        let input = example!("test.txt");
+        let reader = std::io::BufReader::with_capacity(2, input.as_bytes());
+        assert_eq!(solution1_from(reader), Ok("2=-1=0".to_string()));
+        let e = solution1_from("1=\n12\n2=3\n".as_bytes()).unwrap_err();
+        assert_eq!((e.line, e.column), (3, 3));
+    }
+}
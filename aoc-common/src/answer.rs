@@ -0,0 +1,75 @@
+use std::fmt;
+
+/// Answer for a puzzle part.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Answer {
+    Int(i64),
+    Str(String),
+    /// Multi-line picture, like letters rendered on a CRT screen.
+    Glyph(String),
+    /// The part has no puzzle to solve.
+    None,
+}
+
+impl Answer {
+    pub fn glyph(picture: impl Into<String>) -> Self {
+        Answer::Glyph(picture.into())
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Int(x) => write!(f, "{}", x),
+            Answer::Str(x) => write!(f, "{}", x),
+            Answer::Glyph(x) => write!(f, "{}", x),
+            Answer::None => write!(f, "-"),
+        }
+    }
+}
+
+macro_rules! from_int {
+    ( $( $t:ty ),* ) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(x: $t) -> Self {
+                    Answer::Int(x as i64)
+                }
+            }
+        )*
+    };
+}
+
+from_int!(i32, i64, u8, u16, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(x: String) -> Self {
+        Answer::Str(x)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(x: &str) -> Self {
+        Answer::Str(x.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from() {
+        assert_eq!(Answer::from(24000u32), Answer::Int(24000));
+        assert_eq!(Answer::from(1514285714288usize), Answer::Int(1514285714288));
+        assert_eq!(Answer::from("CMZ"), Answer::Str("CMZ".to_string()));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::Int(-3).to_string(), "-3");
+        assert_eq!(Answer::from("2=-1=0").to_string(), "2=-1=0");
+        assert_eq!(Answer::glyph("#.\n.#\n").to_string(), "#.\n.#\n");
+        assert_eq!(Answer::None.to_string(), "-");
+    }
+}
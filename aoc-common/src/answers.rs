@@ -0,0 +1,100 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::input::repository_dir;
+use crate::toml::{self, Value};
+use crate::Answer;
+
+/// Recorded answers for the real puzzle inputs.
+///
+/// Stored in TOML file with a table for each day:
+///
+/// ```toml
+/// [day10]
+/// part1 = 13140
+/// part2 = """
+/// ##..##..
+/// """
+/// ```
+///
+/// Multi-line strings are pictures (`Answer::Glyph`).
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Answers {
+    answers: BTreeMap<(u8, u8), Answer>,
+}
+
+impl Answers {
+    /// `answers.toml` in the repository.
+    pub fn default_path() -> PathBuf {
+        repository_dir().join("answers.toml")
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Self::parse(&text).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut answers = BTreeMap::new();
+        for table in toml::parse(text).map_err(|e| e.to_string())? {
+            if table.name.is_empty() && table.entries.is_empty() {
+                continue;
+            }
+            let day = table.name.strip_prefix("day")
+                .and_then(|day| day.parse::<u8>().ok())
+                .filter(|day| (1..=25).contains(day))
+                .ok_or_else(|| format!("Expected table [day1] to [day25], got [{}]", table.name))?;
+            for (key, value) in table.entries {
+                let part = match key.as_str() {
+                    "part1" => 1,
+                    "part2" => 2,
+                    _ => return Err(format!("Expected part1 or part2 in [{}], got {}", table.name, key)),
+                };
+                let answer = match value {
+                    Value::Int(x) => Answer::Int(x),
+                    Value::Str(x) if x.contains('\n') => Answer::Glyph(x),
+                    Value::Str(x) => Answer::Str(x),
+                    Value::Bool(_) => return Err(format!("Unexpected boolean for {} in [{}]", key, table.name)),
+                };
+                answers.insert((day, part), answer);
+            }
+        }
+        Ok(Answers { answers })
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&Answer> {
+        self.answers.get(&(day, part))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse("\
+[day5]
+part1 = \"CMZ\"
+
+[day10]
+part1 = 13140
+part2 = \"\"\"
+#.
+.#
+\"\"\"
+").unwrap();
+        assert_eq!(answers.get(5, 1), Some(&Answer::from("CMZ")));
+        assert_eq!(answers.get(5, 2), None);
+        assert_eq!(answers.get(10, 1), Some(&Answer::Int(13140)));
+        assert_eq!(answers.get(10, 2), Some(&Answer::glyph("#.\n.#\n")));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert!(Answers::parse("[day26]\npart1 = 1").is_err());
+        assert!(Answers::parse("[day1]\npart3 = 1").is_err());
+        assert_eq!(Answers::parse("[day1]\npart1 = x").unwrap_err(), "line 2: Expected integer, string or boolean");
+    }
+}
@@ -0,0 +1,166 @@
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::toml::{self, Value};
+
+/// Puzzle parameters which can be changed by name, see `Settings`.
+pub trait Config: Default {
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String>;
+
+    /// Checks parameters which depend on each other, called once all of them
+    /// are set.
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+impl Config for () {
+    fn set(&mut self, key: &str, _value: &str) -> Result<(), String> {
+        Err(unknown(key))
+    }
+}
+
+/// Parses value of a parameter.
+pub fn value<T: FromStr>(key: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("Invalid value for {}: {:?}", key, value))
+}
+
+/// Parses non-empty comma-separated list, e.g. "20,60,100".
+pub fn list<T: FromStr>(key: &str, values: &str) -> Result<Vec<T>, String> {
+    values.split(',').map(|x| value(key, x.trim())).collect()
+}
+
+pub fn unknown(key: &str) -> String {
+    format!("Unknown parameter: {}", key)
+}
+
+/// Parameter values for each day.
+///
+/// Loaded from a TOML file with a table for each day:
+///
+/// ```toml
+/// [day11]
+/// rounds1 = 20
+/// rounds2 = 10000
+/// ```
+///
+/// or given on the command line as "day11.rounds1=20", or "rounds1=20" for a
+/// single day binary.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Settings {
+    params: Vec<(u8, String, String)>,
+}
+
+impl Settings {
+    pub fn load(&mut self, path: &Path) -> Result<(), String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        self.parse(&text).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn parse(&mut self, text: &str) -> Result<(), String> {
+        for table in toml::parse(text).map_err(|e| e.to_string())? {
+            if table.name.is_empty() && table.entries.is_empty() {
+                continue;
+            }
+            let day = parse_day(&table.name)
+                .ok_or_else(|| format!("Expected table [day1] to [day25], got [{}]", table.name))?;
+            for (key, value) in table.entries {
+                let value = match value {
+                    Value::Int(x) => x.to_string(),
+                    Value::Str(x) => x,
+                    Value::Bool(x) => x.to_string(),
+                };
+                self.params.push((day, key, value));
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds "KEY=VALUE" for the given day, or "dayN.KEY=VALUE" if the day is
+    /// not given.
+    pub fn add_arg(&mut self, arg: &str, day: Option<u8>) -> Result<(), String> {
+        let (key, value) = arg.split_once('=')
+            .ok_or_else(|| format!("Expected KEY=VALUE, got {:?}", arg))?;
+        let (day, key) = match day {
+            Some(day) => (day, key),
+            None => key.split_once('.')
+                .and_then(|(day, key)| Some((parse_day(day)?, key)))
+                .ok_or_else(|| format!("Expected dayN.KEY=VALUE, got {:?}", arg))?,
+        };
+        self.params.push((day, key.to_string(), value.to_string()));
+        Ok(())
+    }
+
+    /// Calls `set` with the parameters of the day in the order they were
+    /// added, so later ones override earlier ones.
+    pub fn apply(&self, day: u8, mut set: impl FnMut(&str, &str) -> Result<(), String>) -> Result<(), String> {
+        for (_, key, value) in self.params.iter().filter(|(d, _, _)| *d == day) {
+            set(key, value).map_err(|e| format!("day {}: {}", day, e))?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_day(name: &str) -> Option<u8> {
+    name.strip_prefix("day")
+        .and_then(|day| day.parse::<u8>().ok())
+        .filter(|day| (1..=25).contains(day))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Default, PartialEq)]
+    struct Params {
+        rounds: usize,
+        name: String,
+    }
+
+    impl Config for Params {
+        fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+            match key {
+                "rounds" => self.rounds = super::value(key, value)?,
+                "name" => self.name = value.to_string(),
+                _ => return Err(unknown(key)),
+            }
+            Ok(())
+        }
+    }
+
+    fn configure(settings: &Settings, day: u8) -> Result<Params, String> {
+        let mut params = Params::default();
+        settings.apply(day, |key, value| params.set(key, value))?;
+        Ok(params)
+    }
+
+    #[test]
+    fn test_settings() {
+        let mut settings = Settings::default();
+        settings.parse("[day3]\nrounds = 5\nname = \"x\"\n\n[day04]\nrounds = 1\n").unwrap();
+        settings.add_arg("day3.rounds=7", None).unwrap();
+        settings.add_arg("name=y", Some(4)).unwrap();
+        assert_eq!(configure(&settings, 3), Ok(Params { rounds: 7, name: "x".to_string() }));
+        assert_eq!(configure(&settings, 4), Ok(Params { rounds: 1, name: "y".to_string() }));
+        assert_eq!(configure(&settings, 5), Ok(Params::default()));
+
+        settings.add_arg("day5.size=1", None).unwrap();
+        assert_eq!(configure(&settings, 5), Err("day 5: Unknown parameter: size".to_string()));
+        settings.add_arg("day6.rounds=-1", None).unwrap();
+        assert_eq!(configure(&settings, 6), Err("day 6: Invalid value for rounds: \"-1\"".to_string()));
+    }
+
+    #[test]
+    fn test_settings_errors() {
+        let mut settings = Settings::default();
+        assert!(settings.parse("[day26]\nrounds = 1\n").is_err());
+        assert!(settings.parse("rounds = 1\n").is_err());
+        assert!(settings.add_arg("rounds=1", None).is_err());
+        assert!(settings.add_arg("day1.rounds", None).is_err());
+        assert!(settings.add_arg("day0.rounds=1", None).is_err());
+        assert_eq!(settings, Settings::default());
+        assert_eq!(list::<usize>("cycles", "20, 60,100"), Ok(vec![20, 60, 100]));
+        assert!(list::<usize>("cycles", "").is_err());
+    }
+}
@@ -0,0 +1,171 @@
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Error in puzzle input with location of the offending text.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    pub day: u8,
+    /// Line number, starting from 1.
+    pub line: usize,
+    /// Column number in characters, starting from 1.
+    pub column: usize,
+    /// Length of the offending text in characters.
+    pub width: usize,
+    /// Whole line containing the offending text.
+    pub snippet: String,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Returns multi-line error description pointing at the offending text.
+    pub fn diagnostic(&self) -> String {
+        let number = self.line.to_string();
+        let pad = " ".repeat(number.len());
+        format!(
+            "error: {}\n{} --> day {}, line {}, column {}\n{} |\n{} | {}\n{} | {}{}\n",
+            self.message,
+            pad, self.day, self.line, self.column,
+            pad,
+            number, self.snippet,
+            pad, " ".repeat(self.column - 1), "^".repeat(self.width.max(1)),
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "day {}, line {}, column {}: {}", self.day, self.line, self.column, self.message)
+    }
+}
+
+impl error::Error for ParseError {}
+
+/// Puzzle input being parsed.
+///
+/// All the string slices passed to the methods must be sub-slices of the
+/// input, so their location can be reported in errors.
+#[derive(Clone, Copy)]
+pub struct Source<'a> {
+    pub day: u8,
+    pub text: &'a str,
+    /// Number of the first line of `text` in the whole input.
+    pub line: usize,
+}
+
+impl<'a> Source<'a> {
+    pub fn new(day: u8, text: &'a str) -> Self {
+        Source { day, text, line: 1 }
+    }
+
+    /// Part of the input starting at the given line.
+    pub fn at_line(day: u8, text: &'a str, line: usize) -> Self {
+        Source { day, text, line }
+    }
+
+    fn offset(&self, at: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let pos = at.as_ptr() as usize;
+        if pos >= start && pos <= start + self.text.len() {
+            pos - start
+        } else {
+            0
+        }
+    }
+
+    /// Error pointing at given part of the input.
+    pub fn error(&self, at: &str, message: impl Into<String>) -> ParseError {
+        let offset = self.offset(at);
+        let line_start = self.text[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = self.text[offset..].find('\n').map_or(self.text.len(), |i| offset + i);
+        let snippet = self.text[line_start..line_end].trim_end_matches('\r');
+        ParseError {
+            day: self.day,
+            line: self.text[..line_start].matches('\n').count() + self.line,
+            column: self.text[line_start..offset].chars().count() + 1,
+            width: at.chars().count().min(self.text[offset..line_end].chars().count()).max(1),
+            snippet: snippet.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// Error pointing right after given part of the input.
+    pub fn missing(&self, after: &str, what: &str) -> ParseError {
+        self.error(&after[after.len()..], format!("Missing {}", what))
+    }
+
+    /// Parses a value, like a number.
+    pub fn parse<T: FromStr>(&self, at: &str) -> Result<T, ParseError> {
+        at.parse::<T>().map_err(|_| self.error(at, format!("Unexpected value: {:?}", at)))
+    }
+
+    /// Takes next item from iterator of sub-slices.
+    pub fn next<'b>(&self, it: &mut impl Iterator<Item = &'b str>, after: &str, what: &str)
+        -> Result<&'b str, ParseError>
+    {
+        it.next().ok_or_else(|| self.missing(after, what))
+    }
+
+    /// Strips prefix from a line or reports what was expected.
+    pub fn strip_prefix<'b>(&self, line: &'b str, prefix: &str) -> Result<&'b str, ParseError> {
+        line.strip_prefix(prefix)
+            .ok_or_else(|| self.error(line, format!("Expected {:?}", prefix)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_location() {
+        let text = "1\n22\n333 x4\n";
+        let source = Source::new(7, text);
+        let e = source.error(&text[9..11], "Bad");
+        assert_eq!(e.day, 7);
+        assert_eq!(e.line, 3);
+        assert_eq!(e.column, 5);
+        assert_eq!(e.width, 2);
+        assert_eq!(e.snippet, "333 x4");
+        assert_eq!(e.to_string(), "day 7, line 3, column 5: Bad");
+        let e = Source::at_line(7, &text[5..], 10).error(&text[9..11], "Bad");
+        assert_eq!((e.line, e.column), (10, 5));
+    }
+
+    #[test]
+    fn test_parse() {
+        let text = "12,ab";
+        let source = Source::new(1, text);
+        assert_eq!(source.parse::<u8>(&text[0..2]), Ok(12));
+        let e = source.parse::<u8>(&text[3..5]).unwrap_err();
+        assert_eq!((e.line, e.column), (1, 4));
+        assert_eq!(e.message, "Unexpected value: \"ab\"");
+    }
+
+    #[test]
+    fn test_missing() {
+        let text = "a b\r\nc";
+        let source = Source::new(1, text);
+        let mut it = text[0..3].split(' ');
+        assert_eq!(source.next(&mut it, &text[0..3], "x"), Ok("a"));
+        assert_eq!(source.next(&mut it, &text[0..3], "x"), Ok("b"));
+        let e = source.next(&mut it, &text[0..3], "number").unwrap_err();
+        assert_eq!((e.line, e.column, e.width), (1, 4, 1));
+        assert_eq!(e.snippet, "a b");
+        assert_eq!(e.message, "Missing number");
+    }
+
+    #[test]
+    fn test_diagnostic() {
+        let text = "move 1 from 2 to 1\nmove x from 1 to 3\n";
+        let source = Source::new(5, text);
+        let e = source.error(&text[24..25], "Unexpected value: \"x\"");
+        assert_eq!(e.diagnostic(), "\
+error: Unexpected value: \"x\"
+  --> day 5, line 2, column 6
+  |
+2 | move x from 1 to 3
+  |      ^
+");
+    }
+}
@@ -0,0 +1,480 @@
+use std::collections::hash_map::{self, HashMap};
+use std::ops::{Index, IndexMut};
+
+use crate::{Direction, ParseError, Point, Point3, Source};
+
+/// Rectangular 2D map stored row by row.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self
+        where T: Clone
+    {
+        Grid { width, height, cells: vec![value; width * height] }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Point<usize>) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Point::new(x, y)))
+            .map(&mut f)
+            .collect();
+        Grid { width, height, cells }
+    }
+
+    /// Parses map with a row on each line; `tile` converts characters to
+    /// cells and `what` names them in errors.
+    pub fn parse(
+        source: &Source, text: &str, what: &str, mut tile: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let text = text.trim_end();
+        if text.is_empty() {
+            return Err(source.missing(text, "map"));
+        }
+        let mut cells = Vec::with_capacity(text.len());
+        let mut width = None;
+        let mut height = 0;
+        for line in text.split('\n') {
+            let line = line.trim_end_matches('\r');
+            let mut count = 0;
+            for (i, c) in line.char_indices() {
+                let cell = tile(c).ok_or_else(|| {
+                    source.error(&line[i..i + c.len_utf8()], format!("Unexpected {}: {:?}", what, c))
+                })?;
+                cells.push(cell);
+                count += 1;
+            }
+            match width {
+                None => width = Some(count),
+                Some(width) if width != count => {
+                    return Err(source.error(line, format!("Expected {} columns", width)));
+                },
+                _ => {},
+            }
+            height += 1;
+        }
+        Ok(Grid { width: width.unwrap_or(0), height, cells })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, p: Point<usize>) -> bool {
+        p.x < self.width && p.y < self.height
+    }
+
+    pub fn get(&self, p: Point<usize>) -> Option<&T> {
+        if self.contains(p) { Some(&self.cells[p.y * self.width + p.x]) } else { None }
+    }
+
+    pub fn get_mut(&mut self, p: Point<usize>) -> Option<&mut T> {
+        if self.contains(p) { Some(&mut self.cells[p.y * self.width + p.x]) } else { None }
+    }
+
+    /// Neighbouring point in given direction unless it is outside the map.
+    pub fn step(&self, p: Point<usize>, direction: Direction) -> Option<Point<usize>> {
+        let p = match direction {
+            Direction::Up => Point::new(p.x, p.y.checked_sub(1)?),
+            Direction::Right => Point::new(p.x + 1, p.y),
+            Direction::Down => Point::new(p.x, p.y + 1),
+            Direction::Left => Point::new(p.x.checked_sub(1)?, p.y),
+        };
+        if self.contains(p) { Some(p) } else { None }
+    }
+
+    /// Orthogonally adjacent points inside the map.
+    pub fn neighbors4(&self, p: Point<usize>) -> impl Iterator<Item = Point<usize>> + '_ {
+        Direction::ALL.into_iter().filter_map(move |d| self.step(p, d))
+    }
+
+    /// Orthogonally and diagonally adjacent points inside the map.
+    pub fn neighbors8(&self, p: Point<usize>) -> impl Iterator<Item = Point<usize>> + '_ {
+        let p = Point::new(p.x as isize, p.y as isize);
+        p.neighbors8().into_iter().filter_map(|q| {
+            let q = Point::new(usize::try_from(q.x).ok()?, usize::try_from(q.y).ok()?);
+            if self.contains(q) { Some(q) } else { None }
+        })
+    }
+
+    /// Point on the map with coordinates wrapped around the edges.
+    pub fn wrap(&self, p: Point<isize>) -> Point<usize> {
+        Point::new(
+            p.x.rem_euclid(self.width as isize) as usize,
+            p.y.rem_euclid(self.height as isize) as usize,
+        )
+    }
+
+    /// Neighbouring point in given direction, wrapping around the edges.
+    pub fn wrapping_step(&self, p: Point<usize>, direction: Direction) -> Point<usize> {
+        self.wrap(Point::new(p.x as isize, p.y as isize) + direction.delta())
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "Column {} is outside of the grid", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Text with a line for each row.
+    pub fn render(&self, mut f: impl FnMut(Point<usize>, &T) -> char) -> String {
+        let mut text = String::with_capacity((self.width + 1) * self.height);
+        for (p, cell) in self.iter() {
+            text.push(f(p, cell));
+            if p.x + 1 == self.width {
+                text.push('\n');
+            }
+        }
+        text
+    }
+
+    /// All points, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point<usize>> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point<usize>, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    pub fn position(&self, mut f: impl FnMut(&T) -> bool) -> Option<Point<usize>> {
+        self.iter().find(|(_, cell)| f(cell)).map(|(p, _)| p)
+    }
+}
+
+impl<T> Index<Point<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point<usize>) -> &T {
+        assert!(self.contains(p), "Point {:?} is outside of the grid", p);
+        &self.cells[p.y * self.width + p.x]
+    }
+}
+
+impl<T> IndexMut<Point<usize>> for Grid<T> {
+    fn index_mut(&mut self, p: Point<usize>) -> &mut T {
+        assert!(self.contains(p), "Point {:?} is outside of the grid", p);
+        &mut self.cells[p.y * self.width + p.x]
+    }
+}
+
+/// Smallest rectangle containing given points, including the edges.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct BoundingBox {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl BoundingBox {
+    pub fn new(p: Point) -> Self {
+        BoundingBox { min: p, max: p }
+    }
+
+    pub fn extend(&mut self, p: Point) {
+        self.min = Point::new(self.min.x.min(p.x), self.min.y.min(p.y));
+        self.max = Point::new(self.max.x.max(p.x), self.max.y.max(p.y));
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        self.min.x <= p.x && p.x <= self.max.x && self.min.y <= p.y && p.y <= self.max.y
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.x - self.min.x) as usize + 1
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.y - self.min.y) as usize + 1
+    }
+
+    pub fn area(&self) -> usize {
+        self.width() * self.height()
+    }
+
+    /// All points, row by row.
+    pub fn points(self) -> impl Iterator<Item = Point> {
+        (self.min.y..=self.max.y).flat_map(move |y| (self.min.x..=self.max.x).map(move |x| Point::new(x, y)))
+    }
+}
+
+/// Unbounded 2D map storing only occupied cells.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SparseGrid<T = ()> {
+    cells: HashMap<Point, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid { cells: HashMap::new() }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses map with a row on each line, skipping `blank` characters;
+    /// `tile` converts other characters to cells and `what` names them in
+    /// errors.
+    pub fn parse(
+        source: &Source, text: &str, what: &str, blank: &[char], mut tile: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut grid = SparseGrid::new();
+        for (y, line) in text.trim_end().split('\n').enumerate() {
+            for (x, (i, c)) in line.trim_end_matches('\r').char_indices().enumerate() {
+                if blank.contains(&c) {
+                    continue;
+                }
+                let at = &line[i..i + c.len_utf8()];
+                let cell = tile(c).ok_or_else(|| source.error(at, format!("Unexpected {}: {:?}", what, c)))?;
+                let (Ok(x), Ok(y)) = (i32::try_from(x), i32::try_from(y)) else {
+                    return Err(source.error(at, "Map is too large"));
+                };
+                grid.insert(Point::new(x, y), cell);
+            }
+        }
+        Ok(grid)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.cells.get(&p)
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.cells.get_mut(&p)
+    }
+
+    pub fn insert(&mut self, p: Point, cell: T) -> Option<T> {
+        self.cells.insert(p, cell)
+    }
+
+    pub fn remove(&mut self, p: Point) -> Option<T> {
+        self.cells.remove(&p)
+    }
+
+    pub fn entry(&mut self, p: Point) -> hash_map::Entry<'_, Point, T> {
+        self.cells.entry(p)
+    }
+
+    /// Occupied points in arbitrary order.
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        self.cells.keys().copied()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(&p, cell)| (p, cell))
+    }
+
+    /// Box around occupied points, `None` if the map is empty.
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        let mut points = self.points();
+        let mut bounds = BoundingBox::new(points.next()?);
+        points.for_each(|p| bounds.extend(p));
+        Some(bounds)
+    }
+
+    /// Text with a line for each row of the bounding box; `f` gets `None`
+    /// for empty cells.
+    pub fn render(&self, mut f: impl FnMut(Point, Option<&T>) -> char) -> String {
+        let Some(bounds) = self.bounding_box() else { return String::new() };
+        let mut text = String::with_capacity((bounds.width() + 1) * bounds.height());
+        for p in bounds.points() {
+            text.push(f(p, self.get(p)));
+            if p.x == bounds.max.x {
+                text.push('\n');
+            }
+        }
+        text
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+        SparseGrid { cells: iter.into_iter().collect() }
+    }
+}
+
+impl<T> Extend<(Point, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Point, T)>>(&mut self, iter: I) {
+        self.cells.extend(iter)
+    }
+}
+
+/// Box-shaped 3D map.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Grid3<T> {
+    size: Point3<usize>,
+    cells: Vec<T>,
+}
+
+impl<T> Grid3<T> {
+    pub fn new(size: Point3<usize>, value: T) -> Self
+        where T: Clone
+    {
+        Grid3 { size, cells: vec![value; size.x * size.y * size.z] }
+    }
+
+    pub fn size(&self) -> Point3<usize> {
+        self.size
+    }
+
+    pub fn contains(&self, p: Point3<usize>) -> bool {
+        p.x < self.size.x && p.y < self.size.y && p.z < self.size.z
+    }
+
+    fn offset(&self, p: Point3<usize>) -> usize {
+        (p.z * self.size.y + p.y) * self.size.x + p.x
+    }
+
+    pub fn get(&self, p: Point3<usize>) -> Option<&T> {
+        if self.contains(p) { Some(&self.cells[self.offset(p)]) } else { None }
+    }
+
+    pub fn get_mut(&mut self, p: Point3<usize>) -> Option<&mut T> {
+        if self.contains(p) {
+            let offset = self.offset(p);
+            Some(&mut self.cells[offset])
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> Index<Point3<usize>> for Grid3<T> {
+    type Output = T;
+
+    fn index(&self, p: Point3<usize>) -> &T {
+        self.get(p).unwrap_or_else(|| panic!("Point {:?} is outside of the grid", p))
+    }
+}
+
+impl<T> IndexMut<Point3<usize>> for Grid3<T> {
+    fn index_mut(&mut self, p: Point3<usize>) -> &mut T {
+        assert!(self.contains(p), "Point {:?} is outside of the grid", p);
+        let offset = self.offset(p);
+        &mut self.cells[offset]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let text = "#.#\n..#\n";
+        let grid = Grid::parse(&Source::new(1, text), text, "tile", |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        }).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert!(grid[Point::new(2, 1)]);
+        assert!(!grid[Point::new(1, 0)]);
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.iter().filter(|(_, &x)| x).count(), 3);
+        assert_eq!(grid.position(|&x| !x), Some(Point::new(1, 0)));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let text = "123\n1x3\n";
+        let e = Grid::parse(&Source::new(8, text), text, "tree height", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!((e.line, e.column), (2, 2));
+        assert_eq!(e.message, "Unexpected tree height: 'x'");
+
+        let text = "123\n12\n";
+        let e = Grid::parse(&Source::new(8, text), text, "tree height", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!(e.message, "Expected 3 columns");
+    }
+
+    #[test]
+    fn test_step() {
+        let grid = Grid::from_fn(2, 2, |p| p.x + 2 * p.y);
+        assert_eq!(grid[Point::new(1, 1)], 3);
+        assert_eq!(grid.step(Point::new(0, 0), Direction::Up), None);
+        assert_eq!(grid.step(Point::new(0, 0), Direction::Right), Some(Point::new(1, 0)));
+        assert_eq!(grid.step(Point::new(1, 0), Direction::Right), None);
+        assert_eq!(grid.step(Point::new(1, 0), Direction::Down), Some(Point::new(1, 1)));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::new(3, 2, 0);
+        let n4: Vec<_> = grid.neighbors4(Point::new(0, 0)).collect();
+        assert_eq!(n4, [Point::new(1, 0), Point::new(0, 1)]);
+        assert_eq!(grid.neighbors8(Point::new(0, 0)).count(), 3);
+        assert_eq!(grid.neighbors8(Point::new(1, 1)).count(), 5);
+        assert_eq!(grid.wrapping_step(Point::new(0, 0), Direction::Up), Point::new(0, 1));
+        assert_eq!(grid.wrapping_step(Point::new(2, 1), Direction::Right), Point::new(0, 1));
+        assert_eq!(grid.wrap(Point::new(-4, 5)), Point::new(2, 1));
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = Grid::from_fn(3, 2, |p| p.x + 3 * p.y);
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [[0, 1, 2], [3, 4, 5]]);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), [1, 4]);
+        assert_eq!(grid.columns().map(|c| c.sum()).collect::<Vec<usize>>(), [3, 5, 7]);
+        assert_eq!(grid.render(|_, &x| char::from_digit(x as u32, 10).unwrap()), "012\n345\n");
+    }
+
+    #[test]
+    fn test_sparse_grid() {
+        let text = "..#\n#..\n...#\n";
+        let grid = SparseGrid::parse(&Source::new(1, text), text, "tile", &['.'], |c| (c == '#').then_some(())).unwrap();
+        assert_eq!(grid.len(), 3);
+        assert!(grid.contains(Point::new(3, 2)));
+        let bounds = grid.bounding_box().unwrap();
+        assert_eq!((bounds.min, bounds.max), (Point::new(0, 0), Point::new(3, 2)));
+        assert_eq!(bounds.area(), 12);
+        assert_eq!(grid.render(|_, cell| if cell.is_some() { '#' } else { '.' }), "..#.\n#...\n...#\n");
+
+        let e = SparseGrid::parse(&Source::new(1, text), text, "tile", &[], |c| (c == '#').then_some(())).unwrap_err();
+        assert_eq!((e.line, e.column, e.message.as_str()), (1, 1, "Unexpected tile: '.'"));
+
+        assert_eq!(SparseGrid::<()>::new().bounding_box(), None);
+    }
+
+    #[test]
+    fn test_grid3() {
+        let mut grid = Grid3::new(Point3::new(2, 3, 4), 0);
+        grid[Point3::new(1, 2, 3)] = 7;
+        assert_eq!(grid.get(Point3::new(1, 2, 3)), Some(&7));
+        assert_eq!(grid.get(Point3::new(1, 3, 3)), None);
+        assert_eq!(grid.cells.iter().sum::<i32>(), 7);
+    }
+}
@@ -0,0 +1,364 @@
+//! Minimal encoders for animated GIF and APNG with indexed colours.
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::time::Duration;
+
+/// Frames of the same size, each pixel is an index to `palette`.
+pub struct Animation {
+    pub width: usize,
+    pub height: usize,
+    pub palette: Vec<[u8; 3]>,
+    pub frames: Vec<Vec<u8>>,
+    pub delay: Duration,
+}
+
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    acc: u32,
+    len: u32,
+}
+
+impl BitWriter {
+    /// Writes the lowest `n` bits, least significant first.
+    fn write(&mut self, bits: u32, n: u32) {
+        self.acc |= bits << self.len;
+        self.len += n;
+        while self.len >= 8 {
+            self.bytes.push(self.acc as u8);
+            self.acc >>= 8;
+            self.len -= 8;
+        }
+    }
+
+    /// Writes Huffman code, most significant bit first.
+    fn write_code(&mut self, code: u32, n: u32) {
+        self.write(code.reverse_bits() >> (32 - n), n);
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.len > 0 {
+            self.bytes.push(self.acc as u8);
+        }
+        self.bytes
+    }
+}
+
+fn lzw(min_size: u32, pixels: &[u8]) -> Vec<u8> {
+    let clear = 1 << min_size;
+    let end = clear + 1;
+    let mut codes = HashMap::<(u32, u8), u32>::new();
+    let mut next = end + 1;
+    let mut size = min_size + 1;
+    let mut bits = BitWriter::default();
+    bits.write(clear, size);
+
+    let mut prefix = None;
+    for &pixel in pixels {
+        let Some(code) = prefix else {
+            prefix = Some(pixel as u32);
+            continue;
+        };
+        if let Some(&code) = codes.get(&(code, pixel)) {
+            prefix = Some(code);
+            continue;
+        }
+        bits.write(code, size);
+        if next < 4096 {
+            codes.insert((code, pixel), next);
+            // Decoder widens codes once it assigns this one.
+            if next == 1 << size && size < 12 {
+                size += 1;
+            }
+            next += 1;
+        } else {
+            bits.write(clear, size);
+            codes.clear();
+            next = end + 1;
+            size = min_size + 1;
+        }
+        prefix = Some(pixel as u32);
+    }
+    if let Some(code) = prefix {
+        bits.write(code, size);
+    }
+    bits.write(end, size);
+    bits.finish()
+}
+
+pub fn write_gif(animation: &Animation, out: &mut impl Write) -> io::Result<()> {
+    let colors = animation.palette.len();
+    if colors > 256 {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "GIF supports at most 256 colours"));
+    }
+    // Colour table has 2^(bits + 1) entries.
+    let bits = (colors.max(2).next_power_of_two().trailing_zeros() - 1).max(1);
+    let delay = (animation.delay.as_millis() / 10).max(2) as u16;
+
+    out.write_all(b"GIF89a")?;
+    out.write_all(&(animation.width as u16).to_le_bytes())?;
+    out.write_all(&(animation.height as u16).to_le_bytes())?;
+    out.write_all(&[0xf0 | bits as u8, 0, 0])?;
+    for i in 0..2 << bits {
+        out.write_all(animation.palette.get(i).unwrap_or(&[0; 3]))?;
+    }
+    // Loop forever.
+    out.write_all(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00")?;
+
+    for frame in &animation.frames {
+        out.write_all(&[0x21, 0xf9, 4, 0])?;
+        out.write_all(&delay.to_le_bytes())?;
+        out.write_all(&[0, 0])?;
+
+        out.write_all(&[0x2c, 0, 0, 0, 0])?;
+        out.write_all(&(animation.width as u16).to_le_bytes())?;
+        out.write_all(&(animation.height as u16).to_le_bytes())?;
+        out.write_all(&[0])?;
+
+        let min_size = bits + 1;
+        out.write_all(&[min_size as u8])?;
+        for block in lzw(min_size, frame).chunks(255) {
+            out.write_all(&[block.len() as u8])?;
+            out.write_all(block)?;
+        }
+        out.write_all(&[0])?;
+    }
+    out.write_all(&[0x3b])
+}
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u32; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DISTANCE_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA: [u32; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13,
+];
+const WINDOW: usize = 32768;
+const MAX_MATCH: usize = 258;
+
+/// Literal or length symbol with the fixed Huffman codes.
+fn write_symbol(bits: &mut BitWriter, symbol: u32) {
+    match symbol {
+        0..=143 => bits.write_code(0x30 + symbol, 8),
+        144..=255 => bits.write_code(0x190 + symbol - 144, 9),
+        256..=279 => bits.write_code(symbol - 256, 7),
+        _ => bits.write_code(0xc0 + symbol - 280, 8),
+    }
+}
+
+fn write_match(bits: &mut BitWriter, length: usize, distance: usize) {
+    let i = LENGTH_BASE.iter().rposition(|&base| base as usize <= length).unwrap();
+    write_symbol(bits, 257 + i as u32);
+    bits.write((length - LENGTH_BASE[i] as usize) as u32, LENGTH_EXTRA[i]);
+    let i = DISTANCE_BASE.iter().rposition(|&base| base as usize <= distance).unwrap();
+    bits.write_code(i as u32, 5);
+    bits.write((distance - DISTANCE_BASE[i] as usize) as u32, DISTANCE_EXTRA[i]);
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for chunk in data.chunks(5552) {
+        for &x in chunk {
+            a += x as u32;
+            b += a;
+        }
+        a %= 65521;
+        b %= 65521;
+    }
+    b << 16 | a
+}
+
+/// Zlib stream in a single block with fixed Huffman codes and greedy matching.
+fn zlib(data: &[u8]) -> Vec<u8> {
+    let mut bits = BitWriter::default();
+    bits.write(0x78, 8);
+    bits.write(0x01, 8);
+    bits.write(1, 1);
+    bits.write(1, 2);
+
+    // Last position of each three-byte sequence.
+    let mut last = HashMap::<[u8; 3], usize>::new();
+    let mut i = 0;
+    while i < data.len() {
+        let mut length = 0;
+        let mut distance = 0;
+        if i + 3 <= data.len() {
+            let key = [data[i], data[i + 1], data[i + 2]];
+            if let Some(&j) = last.get(&key).filter(|&&j| i - j <= WINDOW) {
+                let max = MAX_MATCH.min(data.len() - i);
+                length = (0..max).take_while(|&k| data[j + k] == data[i + k]).count();
+                distance = i - j;
+            }
+        }
+        let step = if length >= 3 {
+            write_match(&mut bits, length, distance);
+            length
+        } else {
+            write_symbol(&mut bits, data[i] as u32);
+            1
+        };
+        for k in i..(i + step).min(data.len().saturating_sub(2)) {
+            last.insert([data[k], data[k + 1], data[k + 2]], k);
+        }
+        i += step;
+    }
+    write_symbol(&mut bits, 256);
+
+    let mut bytes = bits.finish();
+    bytes.extend_from_slice(&adler32(data).to_be_bytes());
+    bytes
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &x in data {
+        crc ^= x as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { 0xedb88320 ^ (crc >> 1) } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn write_chunk(out: &mut impl Write, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    out.write_all(&(data.len() as u32).to_be_bytes())?;
+    let mut chunk = kind.to_vec();
+    chunk.extend_from_slice(data);
+    out.write_all(&chunk)?;
+    out.write_all(&crc32(&chunk).to_be_bytes())
+}
+
+pub fn write_apng(animation: &Animation, out: &mut impl Write) -> io::Result<()> {
+    let colors = animation.palette.len();
+    if colors > 256 {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "PNG supports at most 256 colours"));
+    }
+    let width = (animation.width as u32).to_be_bytes();
+    let height = (animation.height as u32).to_be_bytes();
+    let delay = (animation.delay.as_millis().min(u16::MAX as u128) as u16).to_be_bytes();
+
+    out.write_all(b"\x89PNG\r\n\x1a\n")?;
+    // Eight bits per pixel, indexed colours.
+    write_chunk(out, b"IHDR", &[&width[..], &height, &[8, 3, 0, 0, 0]].concat())?;
+    write_chunk(out, b"PLTE", &animation.palette.concat())?;
+    let frames = animation.frames.len() as u32;
+    write_chunk(out, b"acTL", &[frames.to_be_bytes(), 0u32.to_be_bytes()].concat())?;
+
+    let mut sequence = 0u32;
+    for (i, frame) in animation.frames.iter().enumerate() {
+        let control = [
+            &sequence.to_be_bytes()[..], &width, &height, &[0; 8], &delay, &1000u16.to_be_bytes(), &[0, 0],
+        ].concat();
+        write_chunk(out, b"fcTL", &control)?;
+        sequence += 1;
+
+        // Each row starts with filter type.
+        let rows: Vec<u8> = frame.chunks(animation.width)
+            .flat_map(|row| [&[0][..], row].concat())
+            .collect();
+        let data = zlib(&rows);
+        if i == 0 {
+            write_chunk(out, b"IDAT", &data)?;
+        } else {
+            write_chunk(out, b"fdAT", &[&sequence.to_be_bytes()[..], &data].concat())?;
+            sequence += 1;
+        }
+    }
+    write_chunk(out, b"IEND", &[])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reference decoder for `lzw`.
+    fn unlzw(min_size: u32, data: &[u8]) -> Vec<u8> {
+        let clear = 1 << min_size;
+        let mut table: Vec<Vec<u8>> = Vec::new();
+        let mut size = min_size + 1;
+        let mut pos = 0;
+        let mut read = |size: u32| {
+            let code = (0..size).map(|i| {
+                let bit = pos + i as usize;
+                ((data[bit / 8] >> (bit % 8)) & 1) as u32
+            }).enumerate().map(|(i, b)| b << i).sum::<u32>();
+            pos += size as usize;
+            code
+        };
+        let mut out = Vec::new();
+        let mut prev: Option<Vec<u8>> = None;
+        loop {
+            let code = read(size);
+            if code == clear {
+                table = (0..clear).map(|i| vec![i as u8]).chain([vec![], vec![]]).collect();
+                size = min_size + 1;
+                prev = None;
+                continue;
+            }
+            if code == clear + 1 {
+                return out;
+            }
+            let entry = match (table.get(code as usize), &prev) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(prev)) => [&prev[..], &prev[..1]].concat(),
+                (None, None) => panic!("Bad code"),
+            };
+            if let Some(prev) = prev {
+                if table.len() < 4096 {
+                    table.push([&prev[..], &entry[..1]].concat());
+                }
+            }
+            if table.len() == 1 << size && size < 12 {
+                size += 1;
+            }
+            out.extend_from_slice(&entry);
+            prev = Some(entry);
+        }
+    }
+
+    #[test]
+    fn test_lzw() {
+        // Long enough to fill the code table and start over.
+        let pixels: Vec<u8> = (0..40000u32).map(|i| ((i * i) / 7 % 5) as u8).collect();
+        assert_eq!(unlzw(3, &lzw(3, &pixels)), pixels);
+        assert_eq!(unlzw(2, &lzw(2, &[0, 1, 0, 1, 0, 1, 1])), [0, 1, 0, 1, 0, 1, 1]);
+    }
+
+    #[test]
+    fn test_zlib() {
+        assert_eq!(adler32(b"Wikipedia"), 0x11e60398);
+        assert_eq!(crc32(b"IEND"), 0xae426082);
+        // Checked with Python's zlib.decompress.
+        assert_eq!(zlib(b"aaaaaaaaaab"), [0x78, 0x01, 0x4b, 0x84, 0x83, 0x24, 0x00, 0x19, 0x0e, 0x04, 0x2d]);
+    }
+
+    #[test]
+    fn test_gif() {
+        let animation = Animation {
+            width: 2,
+            height: 1,
+            palette: vec![[0, 0, 0], [255, 0, 0], [0, 255, 0]],
+            frames: vec![vec![0, 1], vec![2, 0]],
+            delay: Duration::from_millis(50),
+        };
+        let mut gif = Vec::new();
+        write_gif(&animation, &mut gif).unwrap();
+        assert!(gif.starts_with(b"GIF89a\x02\x00\x01\x00\xf1"));
+        assert_eq!(gif.iter().filter(|&&b| b == 0x2c).count(), 2);
+        assert_eq!(gif.last(), Some(&0x3b));
+
+        let mut png = Vec::new();
+        write_apng(&animation, &mut png).unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR"));
+        assert!(png.windows(4).any(|w| w == b"fdAT"));
+        assert!(png.ends_with(b"IEND\xae\x42\x60\x82"));
+    }
+}
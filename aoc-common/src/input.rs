@@ -0,0 +1,277 @@
+use std::env;
+use std::error;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Cursor, Read, Write};
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+
+pub const YEAR: u16 = 2022;
+
+/// Where to look for puzzle inputs.
+///
+/// Inputs are looked up in this order:
+/// 1. path given explicitly (command line argument or `AOC_INPUT`), `-`
+///    reads stdin and `{day}` in the path is replaced with two-digit day,
+/// 2. cache directory: `<cache_dir>/<year>/day<NN>.txt`,
+/// 3. `input` file in the day's crate directory,
+/// 4. download from `url` with `session` token, stored in the cache.
+#[derive(Clone, Debug, Default)]
+pub struct Inputs {
+    pub path: Option<String>,
+    pub cache_dir: Option<PathBuf>,
+    pub crates_dir: Option<PathBuf>,
+    pub download: Option<Download>,
+}
+
+/// Server providing inputs at `<url>/<year>/day/<day>/input`.
+#[derive(Clone, Debug)]
+pub struct Download {
+    pub url: String,
+    pub session: String,
+}
+
+/// Input could not be found or read.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InputError {
+    pub day: u8,
+    /// Paths and why each of them failed.
+    pub tried: Vec<(String, String)>,
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "No input for day {}", self.day)?;
+        for (i, (path, reason)) in self.tried.iter().enumerate() {
+            let sep = if i == 0 { "; tried" } else { "," };
+            write!(f, "{} {} ({})", sep, path, reason)?;
+        }
+        Ok(())
+    }
+}
+
+impl error::Error for InputError {}
+
+/// Root directory of the repository, used for default paths.
+pub fn repository_dir() -> PathBuf {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    manifest_dir.parent().unwrap_or(manifest_dir).to_path_buf()
+}
+
+fn io_reason(e: &io::Error) -> String {
+    match e.kind() {
+        io::ErrorKind::NotFound => "not found".to_string(),
+        _ => e.to_string(),
+    }
+}
+
+impl Inputs {
+    /// Configuration from `AOC_INPUT`, `AOC_CACHE_DIR`, `AOC_URL` and
+    /// `AOC_SESSION` environment variables.
+    ///
+    /// The cache defaults to `inputs` directory in the repository.
+    pub fn from_env() -> Self {
+        let var = |name| env::var(name).ok().filter(|x: &String| !x.is_empty());
+        let download = var("AOC_SESSION").map(|session| Download {
+            url: var("AOC_URL").unwrap_or_default(),
+            session,
+        });
+        Inputs {
+            path: var("AOC_INPUT"),
+            cache_dir: Some(var("AOC_CACHE_DIR").map_or_else(|| repository_dir().join("inputs"), PathBuf::from)),
+            crates_dir: Some(repository_dir()),
+            download,
+        }
+    }
+
+    /// Overrides input path, e.g. with one given on command line.
+    pub fn with_path(mut self, path: Option<String>) -> Self {
+        if path.is_some() {
+            self.path = path;
+        }
+        self
+    }
+
+    pub fn cache_path(&self, day: u8) -> Option<PathBuf> {
+        self.cache_dir.as_ref()
+            .map(|dir| dir.join(YEAR.to_string()).join(format!("day{:02}.txt", day)))
+    }
+
+    /// Whole input, see `open` for reading it gradually.
+    pub fn load(&self, day: u8) -> Result<String, InputError> {
+        let (path, mut reader) = self.find(day)?;
+        let mut input = String::new();
+        reader.read_to_string(&mut input)
+            .map_err(|e| InputError { day, tried: vec![(path, io_reason(&e))] })?;
+        Ok(input)
+    }
+
+    /// Reader of the input so that it can be parsed without keeping it all
+    /// in memory. Downloaded input is cached first.
+    pub fn open(&self, day: u8) -> Result<Box<dyn BufRead>, InputError> {
+        self.find(day).map(|(_, reader)| reader)
+    }
+
+    /// First input found with its path or URL.
+    fn find(&self, day: u8) -> Result<(String, Box<dyn BufRead>), InputError> {
+        let mut tried = Vec::new();
+
+        if let Some(path) = &self.path {
+            let path = path.replace("{day}", &format!("{:02}", day));
+            let result = if path == "-" {
+                Ok(Box::new(io::stdin().lock()) as Box<dyn BufRead>)
+            } else {
+                File::open(&path).map(|file| Box::new(BufReader::new(file)) as Box<dyn BufRead>)
+            };
+            return match result {
+                Ok(reader) => Ok((path, reader)),
+                Err(e) => Err(InputError { day, tried: vec![(path, io_reason(&e))] }),
+            };
+        }
+
+        let cache_path = self.cache_path(day);
+        let crate_path = self.crates_dir.as_ref()
+            .map(|dir| dir.join(format!("advent{:02}", day)).join("input"));
+        for path in cache_path.iter().chain(&crate_path) {
+            match File::open(path) {
+                Ok(file) => {
+                    return Ok((path.display().to_string(), Box::new(BufReader::new(file))))
+                }
+                Err(e) => tried.push((path.display().to_string(), io_reason(&e))),
+            }
+        }
+
+        if let Some(download) = &self.download {
+            let url = format!("{}/{}/day/{}/input", download.url.trim_end_matches('/'), YEAR, day);
+            match download.fetch(&url) {
+                Ok(input) => {
+                    if let Some(path) = &cache_path {
+                        if let Err(e) = store(path, &input) {
+                            tried.push((path.display().to_string(), format!("cannot cache: {}", e)));
+                            return Err(InputError { day, tried });
+                        }
+                    }
+                    return Ok((url, Box::new(Cursor::new(input.into_bytes()))));
+                },
+                Err(e) => tried.push((url, e)),
+            }
+        }
+
+        Err(InputError { day, tried })
+    }
+}
+
+fn store(path: &Path, input: &str) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, input)
+}
+
+impl Download {
+    /// Fetches the URL with plain HTTP/1.0 request.
+    fn fetch(&self, url: &str) -> Result<String, String> {
+        let rest = url.strip_prefix("http://")
+            .ok_or("only http:// URLs are supported, set AOC_URL")?;
+        let (host, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+        let address = if host.contains(':') { host.to_string() } else { format!("{}:80", host) };
+
+        let mut stream = TcpStream::connect(&address).map_err(|e| e.to_string())?;
+        write!(
+            stream,
+            "GET {} HTTP/1.0\r\nHost: {}\r\nCookie: session={}\r\nUser-Agent: aoc-common\r\n\r\n",
+            path, host, self.session,
+        ).map_err(|e| e.to_string())?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response).map_err(|e| e.to_string())?;
+
+        let (head, body) = response.split_once("\r\n\r\n").ok_or("malformed response")?;
+        let status = head.lines().next().unwrap_or_default();
+        match status.split(' ').nth(1) {
+            Some("200") => Ok(body.to_string()),
+            _ => Err(format!("server responded: {}", status)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use std::thread;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-common-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_load_path() {
+        let dir = temp_dir("path");
+        fs::write(dir.join("day07.txt"), "$ ls\n").unwrap();
+        let inputs = Inputs {
+            path: Some(dir.join("day{day}.txt").display().to_string()),
+            ..Default::default()
+        };
+        assert_eq!(inputs.load(7), Ok("$ ls\n".to_string()));
+        let lines: Vec<String> = inputs.open(7).unwrap().lines().map(Result::unwrap).collect();
+        assert_eq!(lines, ["$ ls"]);
+
+        let e = inputs.load(8).unwrap_err();
+        assert_eq!(e.tried, vec![(dir.join("day08.txt").display().to_string(), "not found".to_string())]);
+    }
+
+    #[test]
+    fn test_load_missing() {
+        let dir = temp_dir("missing");
+        let inputs = Inputs {
+            cache_dir: Some(dir.join("cache")),
+            crates_dir: Some(dir.clone()),
+            ..Default::default()
+        };
+        let e = inputs.load(3).unwrap_err();
+        let cache = dir.join("cache").join("2022").join("day03.txt");
+        let local = dir.join("advent03").join("input");
+        assert_eq!(e.to_string(), format!(
+                "No input for day 3; tried {} (not found), {} (not found)",
+                cache.display(), local.display()));
+    }
+
+    #[test]
+    fn test_download() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/aoc", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            // Read the whole request so the client is not cut off while writing.
+            let mut request = Vec::new();
+            let mut buf = [0; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                let n = stream.read(&mut buf).unwrap();
+                if n == 0 { break; }
+                request.extend_from_slice(&buf[..n]);
+            }
+            let request = String::from_utf8_lossy(&request).to_string();
+            stream.write_all(b"HTTP/1.0 200 OK\r\nContent-Length: 4\r\n\r\n1,2\n").unwrap();
+            request
+        });
+
+        let dir = temp_dir("download");
+        let inputs = Inputs {
+            cache_dir: Some(dir.clone()),
+            download: Some(Download { url, session: "secret".to_string() }),
+            ..Default::default()
+        };
+        assert_eq!(inputs.load(18), Ok("1,2\n".to_string()));
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /aoc/2022/day/18/input HTTP/1.0\r\n"), "{}", request);
+        assert!(request.contains("\r\nCookie: session=secret\r\n"), "{}", request);
+
+        // Next time the input is read from the cache.
+        assert_eq!(fs::read_to_string(dir.join("2022").join("day18.txt")).unwrap(), "1,2\n");
+        assert_eq!(inputs.load(18), Ok("1,2\n".to_string()));
+    }
+}
@@ -0,0 +1,39 @@
+mod answer;
+mod answers;
+mod error;
+mod grid;
+mod image;
+mod input;
+mod math;
+mod point;
+mod rng;
+mod solution;
+mod stream;
+pub mod config;
+pub mod json;
+pub mod parallel;
+pub mod property;
+pub mod record;
+pub mod report;
+pub mod search;
+pub mod toml;
+pub mod visual;
+pub use answer::Answer;
+pub use answers::Answers;
+pub use config::{Config, Settings};
+pub use error::{ParseError, Source};
+pub use grid::{BoundingBox, Grid, Grid3, SparseGrid};
+pub use input::{repository_dir, Download, InputError, Inputs, YEAR};
+pub use math::{gcd, lcm};
+pub use point::{Direction, Point, Point3};
+pub use rng::Rng;
+pub use solution::{main, main_with_mode, run, run_json, solve, ModeError, Puzzle, Solution, Solver};
+pub use stream::{read_input, read_lines};
+
+/// Example input file from the calling crate's directory.
+#[macro_export]
+macro_rules! example {
+    ( $file:literal ) => {
+        include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/", $file))
+    };
+}
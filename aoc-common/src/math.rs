@@ -0,0 +1,43 @@
+use std::ops::{Div, Mul, Rem};
+
+/// Greatest common divisor.
+pub fn gcd<T>(a: T, b: T) -> T
+    where T: Copy + PartialEq + Rem<Output = T> + From<u8>
+{
+    if b != T::from(0) {
+        gcd(b, a % b)
+    } else {
+        a
+    }
+}
+
+/// Least common multiple, zero if any argument is zero.
+pub fn lcm<T>(a: T, b: T) -> T
+    where T: Copy + PartialEq + Rem<Output = T> + Div<Output = T> + Mul<Output = T> + From<u8>
+{
+    if a == T::from(0) || b == T::from(0) {
+        T::from(0)
+    } else {
+        a / gcd(a, b) * b
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(gcd(12usize, 18), 6);
+        assert_eq!(gcd(7u64, 0), 7);
+        assert_eq!(gcd(0u32, 5), 5);
+    }
+
+    #[test]
+    fn test_lcm() {
+        assert_eq!(lcm(4usize, 6), 12);
+        assert_eq!(lcm(120usize, 25), 600);
+        assert_eq!(lcm(0u64, 3), 0);
+        assert_eq!([23u64, 19, 13, 17].into_iter().fold(1, lcm), 96577);
+    }
+}
@@ -0,0 +1,121 @@
+use std::cell::Cell;
+use std::num::NonZeroUsize;
+use std::panic;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use crate::report;
+
+/// Number of worker threads, zero means one for each CPU.
+static THREADS: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    static IN_WORKER: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Sets number of worker threads, one forces single-threaded execution and
+/// zero picks one worker for each CPU.
+pub fn set_threads(threads: usize) {
+    THREADS.store(threads, Ordering::Relaxed);
+}
+
+pub fn threads() -> usize {
+    match THREADS.load(Ordering::Relaxed) {
+        0 => thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        threads => threads,
+    }
+}
+
+/// Applies `f` to the items on worker threads, results are in the order of
+/// the items.
+///
+/// Calls from a worker thread run on that thread, so nested work (e.g.
+/// blueprints of a day solved in parallel with other days) does not multiply
+/// the number of threads. A panic in `f` is propagated to the caller, and so
+/// are warnings, see `report::warn`.
+pub fn map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    map_with(threads(), items, f)
+}
+
+fn map_with<T: Sync, R: Send>(workers: usize, items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let workers = workers.min(items.len());
+    if workers <= 1 || IN_WORKER.get() {
+        return items.iter().map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let worker = || {
+        IN_WORKER.set(true);
+        let mut results = Vec::new();
+        loop {
+            let i = next.fetch_add(1, Ordering::Relaxed);
+            let Some(item) = items.get(i) else { break };
+            let result = f(item);
+            results.push((i, result, report::take_warnings()));
+        }
+        results
+    };
+    let mut results: Vec<_> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers).map(|_| scope.spawn(worker)).collect();
+        handles.into_iter()
+            .flat_map(|handle| handle.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+            .collect()
+    });
+    results.sort_by_key(|&(i, _, _)| i);
+    results.into_iter()
+        .map(|(_, result, warnings)| {
+            warnings.into_iter().for_each(report::warn);
+            result
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_map() {
+        let items: Vec<u64> = (0..100).collect();
+        let squares: Vec<u64> = items.iter().map(|x| x * x).collect();
+        for workers in [0, 1, 3, 200] {
+            let results = map_with(workers, &items, |&x| {
+                // Later items finish first.
+                thread::sleep(Duration::from_micros(100 - x));
+                x * x
+            });
+            assert_eq!(results, squares);
+        }
+        assert_eq!(map_with(4, &[] as &[u64], |&x| x), []);
+    }
+
+    #[test]
+    fn test_map_nested() {
+        let outer = thread::current().id();
+        let ids = map_with(2, &[0, 1], |_| {
+            let worker = thread::current().id();
+            assert_ne!(worker, outer);
+            map_with(2, &[0, 1], |_| thread::current().id() == worker)
+        });
+        assert_eq!(ids, [[true, true], [true, true]]);
+    }
+
+    #[test]
+    fn test_map_warnings() {
+        report::take_warnings();
+        let items: Vec<_> = (0..10).collect();
+        map_with(3, &items, |&x| report::warn(format!("item {}", x)));
+        let expected: Vec<_> = items.iter().map(|x| format!("item {}", x)).collect();
+        assert_eq!(report::take_warnings(), expected);
+    }
+
+    #[test]
+    fn test_map_panic() {
+        let result = panic::catch_unwind(|| {
+            map_with(3, &[1, 2, 3, 4], |&x| if x == 3 { panic!("item {}", x) } else { x })
+        });
+        let message = result.unwrap_err().downcast::<String>().unwrap();
+        assert_eq!(*message, "item 3");
+    }
+}
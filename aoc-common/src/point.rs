@@ -0,0 +1,237 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// Point in 2D, `y` grows downwards like rows of a map.
+#[derive(Clone, Copy, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Point<T = i32> {
+    pub x: T,
+    pub y: T,
+}
+
+/// Point in 3D.
+#[derive(Clone, Copy, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Point3<T = i32> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Point { x, y }
+    }
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Point<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({:?},{:?})", self.x, self.y)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Point3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({:?},{:?},{:?})", self.x, self.y, self.z)
+    }
+}
+
+macro_rules! impl_ops {
+    ( $point:ident { $( $c:ident ),* } ) => {
+        impl<T: Add<Output = T>> Add for $point<T> {
+            type Output = Self;
+            fn add(self, other: Self) -> Self {
+                $point { $( $c: self.$c + other.$c ),* }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $point<T> {
+            type Output = Self;
+            fn sub(self, other: Self) -> Self {
+                $point { $( $c: self.$c - other.$c ),* }
+            }
+        }
+
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $point<T> {
+            type Output = Self;
+            fn mul(self, k: T) -> Self {
+                $point { $( $c: self.$c * k ),* }
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $point<T> {
+            type Output = Self;
+            fn neg(self) -> Self {
+                $point { $( $c: -self.$c ),* }
+            }
+        }
+
+        impl<T: AddAssign> AddAssign for $point<T> {
+            fn add_assign(&mut self, other: Self) {
+                $( self.$c += other.$c; )*
+            }
+        }
+
+        impl<T: SubAssign> SubAssign for $point<T> {
+            fn sub_assign(&mut self, other: Self) {
+                $( self.$c -= other.$c; )*
+            }
+        }
+    };
+}
+
+impl_ops!(Point { x, y });
+impl_ops!(Point3 { x, y, z });
+
+macro_rules! impl_manhattan {
+    ( $( $t:ty ),* ) => {
+        $(
+            impl Point<$t> {
+                pub fn manhattan(self, other: Self) -> u64 {
+                    self.x.abs_diff(other.x) as u64 + self.y.abs_diff(other.y) as u64
+                }
+            }
+
+            impl Point3<$t> {
+                pub fn manhattan(self, other: Self) -> u64 {
+                    self.x.abs_diff(other.x) as u64
+                        + self.y.abs_diff(other.y) as u64
+                        + self.z.abs_diff(other.z) as u64
+                }
+            }
+        )*
+    };
+}
+
+impl_manhattan!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl<T: Copy + Add<Output = T> + From<i8>> Point<T> {
+    /// Orthogonally adjacent points clockwise from up.
+    pub fn neighbors4(self) -> [Self; 4] {
+        Direction::ALL.map(|d| self + d.delta())
+    }
+
+    /// Orthogonally and diagonally adjacent points clockwise from up.
+    pub fn neighbors8(self) -> [Self; 8] {
+        [(0, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1)]
+            .map(|(x, y)| self + Point::new(T::from(x), T::from(y)))
+    }
+}
+
+impl<T: From<i8>> Point3<T> {
+    /// Offsets to the six face-adjacent cubes.
+    pub fn sides() -> [Self; 6] {
+        [(1, 0, 0), (-1, 0, 0), (0, 1, 0), (0, -1, 0), (0, 0, 1), (0, 0, -1)]
+            .map(|(x, y, z)| Point3::new(T::from(x), T::from(y), T::from(z)))
+    }
+}
+
+/// Direction on a map; `Up` decreases `y`.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All directions clockwise.
+    pub const ALL: [Direction; 4] = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    /// Unit step in the direction.
+    pub fn delta<T: From<i8>>(self) -> Point<T> {
+        let (x, y) = match self {
+            Direction::Up => (0, -1),
+            Direction::Right => (1, 0),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+        };
+        Point::new(T::from(x), T::from(y))
+    }
+
+    /// One of "^", ">", "v" or "<".
+    pub fn arrow(self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+        }
+    }
+
+    /// Parses "U"/"R"/"D"/"L" or "^"/">"/"v"/"<".
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'U' | '^' => Some(Direction::Up),
+            'R' | '>' => Some(Direction::Right),
+            'D' | 'v' => Some(Direction::Down),
+            'L' | '<' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point() {
+        let a = Point::<i32>::new(1, -2);
+        let mut b = a + Point::new(3, 4) * 2;
+        assert_eq!(b, Point::new(7, 6));
+        b -= a;
+        assert_eq!(b, Point::new(6, 8));
+        assert_eq!(-b, Point::new(-6, -8));
+        assert_eq!(a.manhattan(b), 15);
+        assert_eq!(format!("{:?}", a), "(1,-2)");
+        assert!(Point::new(0, 5) < Point::new(1, 0));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let p = Point::<i32>::new(3, 3);
+        assert_eq!(p.neighbors4(), [(3, 2), (4, 3), (3, 4), (2, 3)].map(|(x, y)| Point::new(x, y)));
+        let n8 = p.neighbors8();
+        assert!(n8.iter().all(|&q| q != p && (q.x - p.x).abs() <= 1 && (q.y - p.y).abs() <= 1));
+        assert!(p.neighbors4().iter().all(|q| n8.contains(q)));
+    }
+
+    #[test]
+    fn test_point3() {
+        let p = Point3::<i8>::new(1, 2, 3);
+        let sides = Point3::<i8>::sides().map(|s| p + s);
+        assert!(sides.iter().all(|&s| s.manhattan(p) == 1));
+        assert_eq!(sides[5], Point3::new(1, 2, 2));
+    }
+
+    #[test]
+    fn test_direction() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.opposite(), Direction::Right);
+        assert_eq!(Direction::Down.delta::<i64>(), Point::new(0, 1));
+        assert_eq!(Direction::from_char('<'), Some(Direction::Left));
+        assert_eq!(Direction::from_char('x'), None);
+        assert!(Direction::ALL.iter().all(|&d| Direction::from_char(d.arrow()) == Some(d)));
+        let around = Direction::ALL.iter().fold(Point::new(0, 0), |p, d| p + d.delta());
+        assert_eq!(around, Point::new(0, 0));
+    }
+}
@@ -0,0 +1,88 @@
+use std::env;
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::{Rng, Solution};
+
+/// Number of generated inputs for each property, `AOC_PROPERTY_CASES`
+/// overrides the default.
+pub fn cases(default: u64) -> u64 {
+    env::var("AOC_PROPERTY_CASES").ok()
+        .and_then(|cases| cases.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Checks `property` on inputs generated from seeds `0..cases`.
+///
+/// The property gets the input text and the parsed input and fails by
+/// panicking. The first failure is reported with its seed and input so that
+/// it can be saved as a regression fixture.
+pub fn check<S: Solution>(
+    size: usize,
+    params: &S::Params,
+    cases: u64,
+    mut property: impl FnMut(&str, &S::Input),
+) {
+    for seed in 0..cases {
+        let input = S::generate(&mut Rng::new(seed), size, params);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let parsed = S::parse(&input).unwrap_or_else(|e| panic!("{}", e));
+            property(&input, &parsed);
+        }));
+        if result.is_err() {
+            panic!("Property failed for day {}, seed {}, size {}, input:\n{}", S::DAY, seed, size, input);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Answer, ParseError, Source};
+
+    struct Digits;
+
+    impl Solution for Digits {
+        const DAY: u8 = 1;
+
+        type Input = Vec<u32>;
+        type Params = ();
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            let source = Source::new(Self::DAY, input);
+            input.split(' ').map(|x| source.parse(x)).collect()
+        }
+
+        fn part1(input: &Self::Input, _params: &()) -> Result<Answer, String> {
+            Ok(input.iter().sum::<u32>().into())
+        }
+
+        fn part2(_input: &Self::Input, _params: &()) -> Result<Answer, String> {
+            Ok(Answer::None)
+        }
+
+        fn generate(rng: &mut Rng, size: usize, _params: &()) -> String {
+            (0..size).map(|_| rng.below(10).to_string()).collect::<Vec<_>>().join(" ")
+        }
+    }
+
+    #[test]
+    fn test_check() {
+        let mut count = 0;
+        check::<Digits>(5, &(), 10, |input, digits| {
+            assert_eq!(input.len(), 9);
+            assert!(digits.iter().all(|&d| d < 10));
+            count += 1;
+        });
+        assert_eq!(count, 10);
+    }
+
+    #[test]
+    fn test_check_failure() {
+        let result = panic::catch_unwind(|| {
+            check::<Digits>(3, &(), 100, |_, digits| assert_ne!(digits[0], 7));
+        });
+        let message = result.unwrap_err().downcast::<String>().unwrap();
+        assert!(message.starts_with("Property failed for day 1, seed "), "{}", message);
+        assert!(message.contains("input:\n7 "), "{}", message);
+    }
+}
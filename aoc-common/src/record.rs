@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::image::{write_apng, write_gif, Animation};
+use crate::json::Json;
+use crate::visual::{Cell, Color, Frame, Picture, Settings, Sink, Viewport};
+
+/// Colours of cells in images.
+///
+/// Characters pick colours of cells without a colour, other cells are
+/// coloured by their `Color`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Palette {
+    chars: Vec<(char, [u8; 3])>,
+    colors: [[u8; 3]; 7],
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette {
+            chars: vec![(' ', [0, 0, 0]), ('.', [40, 40, 40])],
+            colors: [
+                [200, 200, 200],
+                [220, 50, 47],
+                [133, 153, 0],
+                [181, 137, 0],
+                [38, 139, 210],
+                [211, 54, 130],
+                [42, 161, 152],
+            ],
+        }
+    }
+}
+
+fn parse_rgb(rgb: &str) -> Option<[u8; 3]> {
+    let rgb = rgb.strip_prefix('#').unwrap_or(rgb);
+    if rgb.len() != 6 || !rgb.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&rgb[i..i + 2], 16).ok();
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
+
+impl Palette {
+    /// Parses default palette changes as "KEY=RRGGBB,...", where the key is
+    /// a character or a colour name.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut palette = Palette::default();
+        for entry in spec.split(',') {
+            let invalid = || format!("Invalid palette entry: {:?}", entry);
+            let (key, rgb) = entry.split_once('=').ok_or_else(invalid)?;
+            let rgb = parse_rgb(rgb).ok_or_else(invalid)?;
+            if let Some(&color) = Color::ALL.iter().find(|color| color.name() == key) {
+                palette.colors[color as usize] = rgb;
+            } else if let [ch] = key.chars().collect::<Vec<_>>()[..] {
+                palette.chars.retain(|&(c, _)| c != ch);
+                palette.chars.push((ch, rgb));
+            } else {
+                return Err(invalid());
+            }
+        }
+        Ok(palette)
+    }
+
+    pub fn rgb(&self, cell: Cell) -> [u8; 3] {
+        let by_char = self.chars.iter().find(|&&(ch, _)| ch == cell.ch);
+        match (cell.color, by_char) {
+            (Color::Default, Some(&(_, rgb))) => rgb,
+            (color, _) => self.colors[color as usize],
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+    Gif,
+    Apng,
+    Asciicast,
+}
+
+impl Format {
+    pub fn from_path(path: &Path) -> Result<Self, String> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("gif") => Ok(Format::Gif),
+            Some("png" | "apng") => Ok(Format::Apng),
+            Some("cast") => Ok(Format::Asciicast),
+            _ => Err(format!("Unknown recording format, expected .gif, .png or .cast: {}", path.display())),
+        }
+    }
+}
+
+/// Captures frames and saves them once the simulation finishes.
+pub struct Recorder {
+    path: PathBuf,
+    settings: Settings,
+    viewport: Viewport,
+    /// Frames produced so far.
+    frames: usize,
+    /// Recorded frames with their numbers.
+    pictures: Vec<(usize, Picture)>,
+}
+
+impl Recorder {
+    pub fn new(path: PathBuf, settings: &Settings) -> Self {
+        Recorder {
+            path,
+            settings: settings.clone(),
+            viewport: Viewport::new(settings.viewport),
+            frames: 0,
+            pictures: Vec::new(),
+        }
+    }
+
+    fn delay(&self) -> Duration {
+        Duration::from_secs_f64(1.0 / self.settings.fps)
+    }
+
+    /// Frames scaled to the same size, smaller ones are padded with blank cells.
+    fn animation(&self) -> Animation {
+        let scale = self.settings.scale;
+        let columns = self.pictures.iter().map(|(_, p)| p.cells.width()).max().unwrap_or(0);
+        let rows = self.pictures.iter().map(|(_, p)| p.cells.height()).max().unwrap_or(0);
+        let (width, height) = (columns * scale, rows * scale);
+
+        let mut palette = vec![self.settings.palette.rgb(' '.into())];
+        let mut indexes = HashMap::from([(palette[0], 0)]);
+        let frames = self.pictures.iter().map(|(_, picture)| {
+            let mut pixels = vec![0; width * height];
+            for (p, &cell) in picture.cells.iter() {
+                let rgb = self.settings.palette.rgb(cell);
+                let index = *indexes.entry(rgb).or_insert_with(|| {
+                    palette.push(rgb);
+                    (palette.len() - 1) as u8
+                });
+                for y in p.y * scale..(p.y + 1) * scale {
+                    pixels[y * width + p.x * scale..y * width + (p.x + 1) * scale].fill(index);
+                }
+            }
+            pixels
+        }).collect();
+
+        Animation { width, height, palette, frames, delay: self.delay() }
+    }
+
+    fn write_asciicast(&self, out: &mut impl Write) -> io::Result<()> {
+        let width = self.pictures.iter()
+            .map(|(_, p)| p.cells.width().max(p.status.chars().count() + 16))
+            .max()
+            .unwrap_or(0);
+        let height = self.pictures.iter().map(|(_, p)| p.cells.height() + 1).max().unwrap_or(0);
+        let header = Json::object([("version", 2u8.into()), ("width", width.into()), ("height", height.into())]);
+        writeln!(out, "{}", header)?;
+
+        let delay = self.delay().as_secs_f64();
+        for (i, (index, picture)) in self.pictures.iter().enumerate() {
+            let text = picture.ansi(*index).replace('\n', "\r\n");
+            writeln!(out, "{}", Json::Array(vec![(i as f64 * delay).into(), "o".into(), text.into()]))?;
+        }
+        Ok(())
+    }
+}
+
+impl Sink for Recorder {
+    fn show(&mut self, frame: &dyn Frame) {
+        let index = self.frames;
+        self.frames += 1;
+        if index.is_multiple_of(self.settings.skip) {
+            self.viewport.update(frame);
+            self.pictures.push((index, Picture::capture(frame, &self.viewport)));
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.pictures.len() >= self.settings.max_frames
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        if self.pictures.is_empty() {
+            return Err(io::Error::other("No frames to record"));
+        }
+        let mut out = BufWriter::new(File::create(&self.path)?);
+        match Format::from_path(&self.path).map_err(io::Error::other)? {
+            Format::Gif => write_gif(&self.animation(), &mut out)?,
+            Format::Apng => write_apng(&self.animation(), &mut out)?,
+            Format::Asciicast => self.write_asciicast(&mut out)?,
+        }
+        out.flush()?;
+        eprintln!("Recorded {} frames to {}", self.pictures.len(), self.path.display());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Point;
+
+    struct Blink(usize);
+
+    impl Frame for Blink {
+        fn size(&self) -> Point<usize> {
+            Point::new(2, 1)
+        }
+
+        fn cell(&self, p: Point<usize>) -> Cell {
+            if p.x == self.0 % 2 { Cell::new('@', Color::Red) } else { '.'.into() }
+        }
+
+        fn status(&self) -> String {
+            format!("blink {}", self.0)
+        }
+    }
+
+    fn record(settings: Settings, frames: usize) -> Recorder {
+        let mut recorder = Recorder::new(PathBuf::from("test.cast"), &settings);
+        for i in 0..frames {
+            if !recorder.is_done() {
+                recorder.show(&Blink(i));
+            }
+        }
+        recorder
+    }
+
+    #[test]
+    fn test_palette() {
+        let palette = Palette::parse("@=00ff00,red=#ff0000").unwrap();
+        assert_eq!(palette.rgb(Cell::new('@', Color::Default)), [0, 255, 0]);
+        assert_eq!(palette.rgb(Cell::new('@', Color::Red)), [255, 0, 0]);
+        assert_eq!(palette.rgb(' '.into()), [0, 0, 0]);
+        assert!(Palette::parse("@=00ff").is_err());
+        assert!(Palette::parse("ab=000000").is_err());
+    }
+
+    #[test]
+    fn test_recorder() {
+        let settings = Settings { skip: 2, max_frames: 3, scale: 2, ..Settings::default() };
+        let recorder = record(settings, 10);
+        let indexes: Vec<_> = recorder.pictures.iter().map(|(i, _)| *i).collect();
+        assert_eq!(indexes, [0, 2, 4]);
+
+        let animation = recorder.animation();
+        assert_eq!((animation.width, animation.height), (4, 2));
+        assert_eq!(animation.palette, [[0, 0, 0], [220, 50, 47], [40, 40, 40]]);
+        assert_eq!(animation.frames[0], [1, 1, 2, 2, 1, 1, 2, 2]);
+    }
+
+    #[test]
+    fn test_asciicast() {
+        let settings = Settings { fps: 2.0, ..Settings::default() };
+        let mut cast = Vec::new();
+        record(settings, 2).write_asciicast(&mut cast).unwrap();
+        let cast = String::from_utf8(cast).unwrap();
+        let lines: Vec<_> = cast.lines().collect();
+        assert_eq!(lines[0], r#"{"version":2,"width":23,"height":2}"#);
+        assert_eq!(lines[2], r#"[0.5,"o","\u001b[H\u001b[Jframe 1  blink 1\r\n.\u001b[31;1m@\u001b[0m\r\n"]"#);
+    }
+}
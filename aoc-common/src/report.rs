@@ -0,0 +1,146 @@
+use std::cell::RefCell;
+use std::io::{self, Read};
+use std::time::Duration;
+
+use crate::json::Json;
+use crate::Answer;
+
+thread_local! {
+    static WARNINGS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Reports a suspicious input or result, e.g. a fallback answer, without
+/// failing the part.
+pub fn warn(message: impl Into<String>) {
+    WARNINGS.with_borrow_mut(|warnings| warnings.push(message.into()));
+}
+
+/// Warnings reported on the current thread since the last call.
+pub fn take_warnings() -> Vec<String> {
+    WARNINGS.take()
+}
+
+const HASH_START: u64 = 0xcbf2_9ce4_8422_2325;
+
+fn hash_bytes(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+/// FNV-1a hash of the input, identifies the input in reports.
+pub fn input_hash(input: &str) -> u64 {
+    hash_bytes(HASH_START, input.as_bytes())
+}
+
+/// Reader computing `input_hash` of everything read through it.
+pub struct HashReader<R> {
+    inner: R,
+    hash: u64,
+}
+
+impl<R: Read> HashReader<R> {
+    pub fn new(inner: R) -> Self {
+        HashReader { inner, hash: HASH_START }
+    }
+
+    pub fn hash(&self) -> u64 {
+        self.hash
+    }
+}
+
+impl<R: Read> Read for HashReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.hash = hash_bytes(self.hash, &buf[..n]);
+        Ok(n)
+    }
+}
+
+/// Outcome of a single part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Result<Answer, String>,
+    pub elapsed: Duration,
+    pub input_hash: Option<u64>,
+    pub warnings: Vec<String>,
+}
+
+impl Report {
+    /// JSON object, a line in JSON Lines output.
+    ///
+    /// The answer type is "int", "string", "glyph", "none" or "error".
+    /// Integers which do not fit into a double exactly are strings.
+    pub fn to_json(&self) -> Json {
+        let (kind, answer) = match &self.answer {
+            Ok(Answer::Int(x)) if x.unsigned_abs() <= 1 << 53 => ("int", (*x).into()),
+            Ok(Answer::Int(x)) => ("int", x.to_string().into()),
+            Ok(Answer::Str(x)) => ("string", x.as_str().into()),
+            Ok(Answer::Glyph(x)) => ("glyph", x.as_str().into()),
+            Ok(Answer::None) => ("none", Json::Null),
+            Err(_) => ("error", Json::Null),
+        };
+        let mut json = Json::object([
+            ("day", self.day.into()),
+            ("part", self.part.into()),
+            ("type", kind.into()),
+            ("answer", answer),
+            ("elapsed", self.elapsed.as_secs_f64().into()),
+            ("input_hash", self.input_hash.map_or(Json::Null, |hash| format!("{:016x}", hash).into())),
+            ("warnings", Json::Array(self.warnings.iter().map(|w| w.as_str().into()).collect())),
+        ]);
+        if let (Err(e), Json::Object(entries)) = (&self.answer, &mut json) {
+            entries.push(("error".to_string(), e.as_str().into()));
+        }
+        json
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(answer: Result<Answer, String>) -> Report {
+        Report {
+            day: 10,
+            part: 2,
+            answer,
+            elapsed: Duration::from_millis(1500),
+            input_hash: Some(input_hash("")),
+            warnings: vec!["check \"this\"".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_warnings() {
+        warn("first");
+        warn("second".to_string());
+        assert_eq!(take_warnings(), ["first", "second"]);
+        assert_eq!(take_warnings(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_input_hash() {
+        assert_eq!(input_hash(""), 0xcbf29ce484222325);
+        assert_eq!(input_hash("a"), 0xaf63dc4c8601ec8c);
+        assert_ne!(input_hash("ab"), input_hash("ba"));
+
+        let mut reader = HashReader::new("1\n2\n".as_bytes());
+        io::copy(&mut reader, &mut io::sink()).unwrap();
+        assert_eq!(reader.hash(), input_hash("1\n2\n"));
+    }
+
+    #[test]
+    fn test_to_json() {
+        assert_eq!(
+            report(Ok(Answer::glyph("#.\n.#\n"))).to_json().to_string(),
+            r##"{"day":10,"part":2,"type":"glyph","answer":"#.\n.#\n","elapsed":1.5,"input_hash":"cbf29ce484222325","warnings":["check \"this\""]}"##);
+        let json = report(Ok(Answer::Int(1514285714288))).to_json();
+        assert_eq!(json.get("answer"), Some(&Json::Number(1514285714288.0)));
+        let json = report(Ok(Answer::Int(i64::MAX))).to_json();
+        assert_eq!(json.get("answer").and_then(Json::as_str), Some("9223372036854775807"));
+        let json = report(Err("No input".to_string())).to_json();
+        assert_eq!(json.get("type").and_then(Json::as_str), Some("error"));
+        assert_eq!(json.get("error").and_then(Json::as_str), Some("No input"));
+    }
+}
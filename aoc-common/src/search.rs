@@ -0,0 +1,268 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// How much work a search did.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Stats {
+    /// States taken from the frontier.
+    pub expanded: usize,
+    /// Most states waiting in the frontier at once.
+    pub max_frontier: usize,
+}
+
+impl Stats {
+    fn frontier(&mut self, len: usize) {
+        self.max_frontier = self.max_frontier.max(len);
+    }
+}
+
+/// Cheapest path to a goal.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Found<S, C> {
+    pub cost: C,
+    /// States from a start to the goal, both included.
+    pub path: Vec<S>,
+}
+
+impl<S, C> Found<S, C> {
+    pub fn goal(&self) -> &S {
+        self.path.last().expect("Path contains at least the start")
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Outcome<S, C> {
+    /// `None` if no goal is reachable.
+    pub found: Option<Found<S, C>>,
+    pub stats: Stats,
+}
+
+impl<S, C> Outcome<S, C> {
+    pub fn cost(&self) -> Option<&C> {
+        self.found.as_ref().map(|found| &found.cost)
+    }
+}
+
+struct Node<S, C> {
+    state: S,
+    cost: C,
+    parent: Option<usize>,
+}
+
+fn path<S: Clone, C: Copy>(nodes: &[Node<S, C>], mut index: usize) -> Found<S, C> {
+    let cost = nodes[index].cost;
+    let mut path = vec![nodes[index].state.clone()];
+    while let Some(parent) = nodes[index].parent {
+        path.push(nodes[parent].state.clone());
+        index = parent;
+    }
+    path.reverse();
+    Found { cost, path }
+}
+
+/// Breadth-first search for the fewest steps from any of the starts to a
+/// state satisfying `goal`.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut goal: impl FnMut(&S) -> bool,
+) -> Outcome<S, usize>
+    where S: Clone + Eq + Hash, I: IntoIterator<Item = S>
+{
+    let mut stats = Stats::default();
+    let mut nodes = Vec::new();
+    let mut reached = HashSet::new();
+    let mut queue = VecDeque::new();
+    for state in starts {
+        if reached.insert(state.clone()) {
+            queue.push_back(nodes.len());
+            nodes.push(Node { state, cost: 0, parent: None });
+        }
+    }
+    stats.frontier(queue.len());
+
+    while let Some(index) = queue.pop_front() {
+        stats.expanded += 1;
+        if goal(&nodes[index].state) {
+            return Outcome { found: Some(path(&nodes, index)), stats };
+        }
+        let cost = nodes[index].cost + 1;
+        for state in successors(&nodes[index].state) {
+            if reached.insert(state.clone()) {
+                queue.push_back(nodes.len());
+                nodes.push(Node { state, cost, parent: Some(index) });
+            }
+        }
+        stats.frontier(queue.len());
+    }
+
+    Outcome { found: None, stats }
+}
+
+/// Dijkstra's search for the cheapest path from any of the starts to a state
+/// satisfying `goal`.
+///
+/// `successors` gets a state with its cost and returns next states with
+/// costs of the steps.
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S, C) -> I,
+    goal: impl FnMut(&S) -> bool,
+) -> Outcome<S, C>
+    where S: Clone + Eq + Hash, C: Copy + Ord + Add<Output = C> + Default, I: IntoIterator<Item = (S, C)>
+{
+    astar(starts, successors, |_| C::default(), goal)
+}
+
+/// A* search, like `dijkstra` but guided by `heuristic`, which must not
+/// overestimate the remaining cost to a goal.
+pub fn astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S, C) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut goal: impl FnMut(&S) -> bool,
+) -> Outcome<S, C>
+    where S: Clone + Eq + Hash, C: Copy + Ord + Add<Output = C> + Default, I: IntoIterator<Item = (S, C)>
+{
+    let mut stats = Stats::default();
+    let mut nodes: Vec<Node<S, C>> = Vec::new();
+    // Index of the cheapest node for each reached state.
+    let mut reached = HashMap::<S, usize>::new();
+    let mut heap = BinaryHeap::new();
+    for state in starts {
+        if !reached.contains_key(&state) {
+            heap.push(Reverse((heuristic(&state), nodes.len())));
+            reached.insert(state.clone(), nodes.len());
+            nodes.push(Node { state, cost: C::default(), parent: None });
+        }
+    }
+    stats.frontier(heap.len());
+
+    while let Some(Reverse((_, index))) = heap.pop() {
+        let state = &nodes[index].state;
+        // Skip nodes superseded by a cheaper path.
+        if reached[state] != index {
+            continue;
+        }
+        stats.expanded += 1;
+        if goal(state) {
+            return Outcome { found: Some(path(&nodes, index)), stats };
+        }
+        let cost = nodes[index].cost;
+        for (state, step) in successors(state, cost) {
+            let cost = cost + step;
+            if reached.get(&state).is_some_and(|&i| nodes[i].cost <= cost) {
+                continue;
+            }
+            heap.push(Reverse((cost + heuristic(&state), nodes.len())));
+            reached.insert(state.clone(), nodes.len());
+            nodes.push(Node { state, cost, parent: Some(index) });
+        }
+        stats.frontier(heap.len());
+    }
+
+    Outcome { found: None, stats }
+}
+
+/// Expands the greatest states first, for optimisation problems which keep
+/// track of the best solution themselves.
+///
+/// `expand` returns successors of a state or `None` to stop the search.
+pub fn best_first<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut expand: impl FnMut(S) -> Option<I>,
+) -> Stats
+    where S: Ord, I: IntoIterator<Item = S>
+{
+    let mut stats = Stats::default();
+    let mut heap = BinaryHeap::new();
+    for state in starts {
+        heap.push(state);
+    }
+    stats.frontier(heap.len());
+    while let Some(state) = heap.pop() {
+        stats.expanded += 1;
+        let Some(next) = expand(state) else { break };
+        // Pushing one by one keeps the order of equal states predictable.
+        for state in next {
+            heap.push(state);
+        }
+        stats.frontier(heap.len());
+    }
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Directed graph as adjacency list with edge weights.
+    const GRAPH: [&[(usize, u32)]; 6] = [
+        &[(1, 7), (2, 9), (5, 14)],
+        &[(2, 10), (3, 15)],
+        &[(3, 11), (5, 2)],
+        &[(4, 6)],
+        &[],
+        &[(4, 9)],
+    ];
+
+    #[test]
+    fn test_bfs() {
+        let outcome = bfs([0], |&v| GRAPH[v].iter().map(|e| e.0), |&v| v == 4);
+        let found = outcome.found.unwrap();
+        assert_eq!((found.cost, found.path.as_slice()), (2, &[0, 5, 4][..]));
+        assert_eq!(*found.goal(), 4);
+        assert!(outcome.stats.expanded <= 6);
+
+        let outcome = bfs([4], |&v| GRAPH[v].iter().map(|e| e.0), |&v| v == 0);
+        assert_eq!(outcome.found, None);
+        assert_eq!(outcome.stats, Stats { expanded: 1, max_frontier: 1 });
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let outcome = dijkstra([0], |&v, _| GRAPH[v].iter().copied(), |&v| v == 4);
+        let found = outcome.found.unwrap();
+        assert_eq!(found.cost, 20);
+        assert_eq!(found.path, [0, 2, 5, 4]);
+        assert_eq!(outcome.stats.expanded, 6);
+
+        // Early exit at the closest goal.
+        let outcome = dijkstra([0], |&v, _| GRAPH[v].iter().copied(), |&v| v == 1 || v == 4);
+        assert_eq!(outcome.cost(), Some(&7));
+        assert_eq!(outcome.stats.expanded, 2);
+    }
+
+    #[test]
+    fn test_astar() {
+        // Open 10x10 grid.
+        let successors = |&(x, y): &(i32, i32), _| {
+            [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                .into_iter()
+                .filter(|&(x, y)| (0..10).contains(&x) && (0..10).contains(&y))
+                .map(|p| (p, 1))
+        };
+        let distance = |&(x, y): &(i32, i32)| (9 - x).abs() + y.abs();
+        let outcome = astar([(0, 0)], successors, distance, |&p| p == (9, 0));
+        let expected = dijkstra([(0, 0)], successors, |&p| p == (9, 0));
+        assert_eq!(outcome.cost(), Some(&9));
+        assert_eq!(outcome.cost(), expected.cost());
+        assert_eq!(outcome.found.unwrap().path, (0..10).map(|x| (x, 0)).collect::<Vec<_>>());
+        assert_eq!(outcome.stats.expanded, 10);
+        assert!(expected.stats.expanded > 40);
+    }
+
+    #[test]
+    fn test_best_first() {
+        let mut order = Vec::new();
+        let stats = best_first([1u32], |n| {
+            order.push(n);
+            if order.len() == 5 { return None; }
+            Some([n * 2, n * 3].into_iter().filter(|&m| m < 20))
+        });
+        assert_eq!(order, [1, 3, 9, 18, 6]);
+        assert_eq!(stats, Stats { expanded: 5, max_frontier: 3 });
+    }
+}
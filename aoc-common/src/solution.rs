@@ -0,0 +1,521 @@
+use std::any::Any;
+use std::env;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use crate::json::Json;
+use crate::report::{self, HashReader, Report};
+use crate::{read_input, visual, Answer, Config, Inputs, ParseError, Rng, Settings};
+
+/// Solution for a single day.
+///
+/// Input is parsed once and then shared by both parts. Puzzle constants, e.g.
+/// the ones which differ between the example and the real input, live in
+/// `Params` and can be changed with `--param` or a config file.
+///
+/// A part fails with an error for input it cannot solve, e.g. with codes
+/// unknown to the configured rules.
+///
+/// `parse_from` reads the input as the binaries do, by default whole for
+/// `parse`. Line-oriented days parse line by line there instead, so that
+/// they never hold the whole input, and `parse` wraps it.
+///
+/// `generate` produces random valid inputs for stress-testing, `size` scales
+/// the input roughly linearly (number of lines, grid side, etc.).
+pub trait Solution {
+    const DAY: u8;
+
+    type Input;
+    type Params: Config;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn parse_from(reader: impl BufRead) -> Result<Self::Input, ParseError> {
+        Self::parse(&read_input(Self::DAY, reader)?)
+    }
+    fn part1(input: &Self::Input, params: &Self::Params) -> Result<Answer, String>;
+    fn part2(input: &Self::Input, params: &Self::Params) -> Result<Answer, String>;
+    fn generate(rng: &mut Rng, size: usize, params: &Self::Params) -> String;
+}
+
+/// Parses the input and solves both parts.
+pub fn solve<S: Solution>(input: &str, params: &S::Params) -> Result<[Result<Answer, String>; 2], ParseError> {
+    let input = S::parse(input)?;
+    Ok([S::part1(&input, params), S::part2(&input, params)])
+}
+
+const USAGE: &str = "\
+Usage: adventNN [OPTIONS] [INPUT]
+
+Parameter options:
+  --param KEY=VALUE change puzzle parameter, e.g. rounds1=20
+  --config PATH     read parameters from a TOML file with [dayNN] tables
+
+Output options:
+  --json            print a JSON line for each part with the answer, time,
+                    input hash and warnings";
+
+/// Parses the input and solves both parts, skipping the ones without a puzzle.
+///
+/// The input is read to the end even if parsing stops early, so that its
+/// hash is complete for the reports.
+fn reports<S: Solution>(
+    input: &mut HashReader<impl Read>,
+    params: &S::Params,
+) -> Result<Vec<Report>, ParseError> {
+    report::take_warnings();
+    let mut reader = BufReader::new(&mut *input);
+    let parsed = S::parse_from(&mut reader);
+    // A read error is already the parse error.
+    let _ = io::copy(&mut reader, &mut io::sink());
+    let input_hash = input.hash();
+    let input = parsed?;
+    let parse_warnings = report::take_warnings();
+
+    let mut reports = Vec::new();
+    for (part, solve) in [(1, S::part1 as fn(&_, &_) -> _), (2, S::part2)] {
+        let start = Instant::now();
+        let answer = solve(&input, params);
+        let elapsed = start.elapsed();
+        if answer != Ok(Answer::None) {
+            let warnings = parse_warnings.iter().cloned().chain(report::take_warnings()).collect();
+            reports.push(Report { day: S::DAY, part, answer, elapsed, input_hash: Some(input_hash), warnings });
+        }
+    }
+    Ok(reports)
+}
+
+/// Solves both parts and prints the answers, or the input error.
+pub fn run<S: Solution>(input: impl Read, params: &S::Params) -> ExitCode {
+    let reports = match reports::<S>(&mut HashReader::new(input), params) {
+        Ok(reports) => reports,
+        Err(e) => {
+            eprint!("{}", e.diagnostic());
+            return ExitCode::FAILURE;
+        },
+    };
+
+    let mut code = ExitCode::SUCCESS;
+    for report in reports {
+        match report.answer {
+            Ok(Answer::Glyph(picture)) => println!("solution {}:\n{}", report.part, picture),
+            Ok(answer) => println!("solution {}: {}", report.part, answer),
+            Err(e) => {
+                eprintln!("error: part {}: {}", report.part, e);
+                code = ExitCode::FAILURE;
+            },
+        }
+        for warning in report.warnings {
+            eprintln!("warning: part {}: {}", report.part, warning);
+        }
+    }
+
+    code
+}
+
+/// Solves both parts and prints a JSON line for each, or for the input error.
+pub fn run_json<S: Solution>(input: impl Read, params: &S::Params) -> ExitCode {
+    let mut input = HashReader::new(input);
+    match reports::<S>(&mut input, params) {
+        Ok(reports) => {
+            for report in &reports {
+                println!("{}", report.to_json());
+            }
+            if reports.iter().all(|report| report.answer.is_ok()) {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        },
+        Err(e) => {
+            for part in [1, 2] {
+                let report = Report {
+                    day: S::DAY,
+                    part,
+                    answer: Err(e.to_string()),
+                    elapsed: Duration::ZERO,
+                    input_hash: Some(input.hash()),
+                    warnings: Vec::new(),
+                };
+                println!("{}", report.to_json());
+            }
+            ExitCode::FAILURE
+        },
+    }
+}
+
+/// Parameters from `--param` and `--config` options, other arguments are
+/// returned.
+fn parse_params<S: Solution>(args: Vec<String>) -> Result<(S::Params, Vec<String>), String> {
+    let mut settings = Settings::default();
+    let mut rest = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--param" => {
+                let param = args.next().ok_or("Missing value for --param")?;
+                settings.add_arg(&param, Some(S::DAY))?;
+            },
+            "--config" => {
+                let path = args.next().ok_or("Missing value for --config")?;
+                settings.load(Path::new(&path))?;
+            },
+            _ => rest.push(arg),
+        }
+    }
+    let mut params = S::Params::default();
+    settings.apply(S::DAY, |key, value| params.set(key, value))?;
+    params.validate().map_err(|e| format!("day {}: {}", S::DAY, e))?;
+    Ok((params, rest))
+}
+
+/// Entry point for a single day binary.
+///
+/// Input path can be passed as an argument, otherwise it is looked up as
+/// described in `Inputs`. Answers are printed as JSON with `--json`.
+/// Parameters are changed with `--param` and `--config`.
+/// Simulations are shown with `--render`, see `visual::USAGE`.
+pub fn main<S: Solution>() -> ExitCode {
+    let (json, args): (Vec<_>, Vec<_>) = env::args().skip(1).partition(|arg| arg == "--json");
+    let (params, args) = match parse_params::<S>(args) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        },
+    };
+    let (settings, args) = match visual::parse_args(args) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("error: {}\n\n{}\n\n{}", e, USAGE, visual::USAGE);
+            return ExitCode::from(2);
+        },
+    };
+    let path = match input_path(args) {
+        Ok(path) => path,
+        Err(e) => {
+            eprintln!("error: {}\n\n{}\n\n{}", e, USAGE, visual::USAGE);
+            return ExitCode::from(2);
+        },
+    };
+    match Inputs::from_env().with_path(path).open(S::DAY) {
+        Ok(input) => {
+            // Commands are read from stdin so start only after the input is loaded.
+            if let Some(settings) = settings {
+                visual::start(settings);
+            }
+            let code = if json.is_empty() {
+                run::<S>(input, &params)
+            } else {
+                run_json::<S>(input, &params)
+            };
+            if let Err(e) = visual::finish() {
+                eprintln!("error: Failed to save recording: {}", e);
+                return ExitCode::FAILURE;
+            }
+            code
+        },
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        },
+    }
+}
+
+/// Error in a mode of a day binary, see `main_with_mode`.
+#[derive(Debug)]
+pub enum ModeError {
+    /// Invalid value of the mode option.
+    Usage(String),
+    Input(ParseError),
+}
+
+impl From<ParseError> for ModeError {
+    fn from(e: ParseError) -> Self {
+        ModeError::Input(e)
+    }
+}
+
+/// The only argument left after the options, if any, is the input path, "-"
+/// for stdin.
+fn input_path(args: Vec<String>) -> Result<Option<String>, String> {
+    if let Some(arg) = args.iter().find(|arg| arg.starts_with('-') && arg.len() > 1) {
+        return Err(format!("Unknown option: {}", arg));
+    }
+    if let Some(arg) = args.get(1) {
+        return Err(format!("Unexpected argument: {}", arg));
+    }
+    Ok(args.into_iter().next())
+}
+
+/// Removes the `mode` option with its values from the arguments and returns
+/// the values, `None` without the option.
+fn take_mode(args: &mut Vec<String>, mode: &str, values: usize) -> Result<Option<Vec<String>>, String> {
+    let Some(start) = args.iter().position(|arg| arg == mode) else {
+        return Ok(None);
+    };
+    if args.len() <= start + values {
+        return Err(format!("Missing value for {}", mode));
+    }
+    Ok(Some(args.drain(start..=start + values).skip(1).collect()))
+}
+
+/// Entry point for a day binary with a mode printing something else than
+/// the answers, e.g. `--top N`.
+///
+/// Without the `mode` option this is `main`. Otherwise `values` arguments
+/// after the option are passed to `f` with the input reader and parameters,
+/// found as in `main`, and the text returned by `f` is printed, or a JSON
+/// line with it for `--json`.
+pub fn main_with_mode<S: Solution>(
+    mode: &str,
+    values: usize,
+    f: impl FnOnce(&[String], &mut dyn BufRead, &S::Params) -> Result<String, ModeError>,
+) -> ExitCode {
+    let usage = |e: String| {
+        eprintln!("error: {}\n\n{}", e, USAGE);
+        ExitCode::from(2)
+    };
+    let mut args: Vec<_> = env::args().skip(1).collect();
+    let values = match take_mode(&mut args, mode, values) {
+        Ok(Some(values)) => values,
+        Ok(None) => return main::<S>(),
+        Err(e) => return usage(e),
+    };
+    let (json, args): (Vec<_>, Vec<_>) = args.into_iter().partition(|arg| arg == "--json");
+    let (params, path) = match parse_params::<S>(args).and_then(|(params, args)| Ok((params, input_path(args)?))) {
+        Ok(parsed) => parsed,
+        Err(e) => return usage(e),
+    };
+    let mut input = match Inputs::from_env().with_path(path).open(S::DAY) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::FAILURE;
+        },
+    };
+
+    let output = f(&values, &mut input, &params);
+    if !json.is_empty() {
+        let (output, error) = match &output {
+            Ok(output) => (output.as_str().into(), Json::Null),
+            Err(ModeError::Usage(e)) => (Json::Null, e.as_str().into()),
+            Err(ModeError::Input(e)) => (Json::Null, e.to_string().into()),
+        };
+        let mode = mode.trim_start_matches('-');
+        let line = Json::object([("day", S::DAY.into()), ("mode", mode.into()), ("output", output), ("error", error)]);
+        println!("{}", line);
+    }
+    match output {
+        Ok(output) => {
+            if json.is_empty() {
+                print!("{}", output);
+            }
+            ExitCode::SUCCESS
+        },
+        Err(ModeError::Usage(e)) => usage(e),
+        Err(ModeError::Input(e)) => {
+            eprint!("{}", e.diagnostic());
+            ExitCode::FAILURE
+        },
+    }
+}
+
+/// Type-erased `Solution` so that different days can be driven uniformly,
+/// also from multiple threads.
+pub trait Puzzle: Sync {
+    fn day(&self) -> u8;
+    fn set_param(&mut self, key: &str, value: &str) -> Result<(), String>;
+    fn validate_params(&self) -> Result<(), String>;
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError>;
+    fn part1(&self, input: &dyn Any) -> Result<Answer, String>;
+    fn part2(&self, input: &dyn Any) -> Result<Answer, String>;
+    fn generate(&self, rng: &mut Rng, size: usize) -> String;
+}
+
+/// `Solution` with its parameters.
+pub struct Solver<S: Solution> {
+    pub params: S::Params,
+}
+
+impl<S: Solution> Solver<S> {
+    pub fn new() -> Self {
+        Solver { params: S::Params::default() }
+    }
+
+    pub fn with_params(params: S::Params) -> Self {
+        Solver { params }
+    }
+
+    fn input<'a>(&self, input: &'a dyn Any) -> &'a S::Input
+        where S::Input: 'static
+    {
+        input.downcast_ref::<S::Input>()
+            .unwrap_or_else(|| panic!("Input was not parsed for day {}", S::DAY))
+    }
+}
+
+impl<S: Solution> Default for Solver<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Solution> Puzzle for Solver<S>
+    where S::Input: 'static, S::Params: Sync
+{
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn set_param(&mut self, key: &str, value: &str) -> Result<(), String> {
+        self.params.set(key, value)
+    }
+
+    fn validate_params(&self) -> Result<(), String> {
+        self.params.validate()
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError> {
+        Ok(Box::new(S::parse(input)?))
+    }
+
+    fn part1(&self, input: &dyn Any) -> Result<Answer, String> {
+        S::part1(self.input(input), &self.params)
+    }
+
+    fn part2(&self, input: &dyn Any) -> Result<Answer, String> {
+        S::part2(self.input(input), &self.params)
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        S::generate(rng, size, &self.params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Source;
+
+    struct Sum;
+
+    #[derive(Default)]
+    struct Params {
+        factor: i64,
+    }
+
+    impl Config for Params {
+        fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+            match key {
+                "factor" => self.factor = crate::config::value(key, value)?,
+                _ => return Err(crate::config::unknown(key)),
+            }
+            Ok(())
+        }
+    }
+
+    impl Solution for Sum {
+        const DAY: u8 = 1;
+
+        type Input = Vec<i64>;
+        type Params = Params;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            let source = Source::new(Self::DAY, input);
+            input.split(' ').map(|x| source.parse(x)).collect()
+        }
+
+        fn part1(input: &Self::Input, _params: &Self::Params) -> Result<Answer, String> {
+            Ok(input.iter().sum::<i64>().into())
+        }
+
+        fn part2(input: &Self::Input, params: &Self::Params) -> Result<Answer, String> {
+            if params.factor == 0 {
+                report::warn("Zero factor");
+            }
+            if params.factor < 0 {
+                return Err("Negative factor".to_string());
+            }
+            Ok((input.iter().sum::<i64>() * params.factor).into())
+        }
+
+        fn generate(rng: &mut Rng, size: usize, _params: &Self::Params) -> String {
+            (0..size.max(1)).map(|_| rng.range(-9..=9).to_string()).collect::<Vec<_>>().join(" ")
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        let params = Params { factor: 3 };
+        assert_eq!(solve::<Sum>("1 2 3", &params), Ok([Ok(Answer::Int(6)), Ok(Answer::Int(18))]));
+        assert_eq!(solve::<Sum>("1 x 3", &params).unwrap_err().column, 3);
+    }
+
+    #[test]
+    fn test_puzzle() {
+        let mut puzzle: Box<dyn Puzzle> = Box::new(Solver::<Sum>::with_params(Params { factor: 2 }));
+        let input = puzzle.parse("4 5").unwrap();
+        assert_eq!(puzzle.day(), 1);
+        assert_eq!(puzzle.part1(input.as_ref()), Ok(Answer::Int(9)));
+        assert_eq!(puzzle.part2(input.as_ref()), Ok(Answer::Int(18)));
+        puzzle.set_param("factor", "-1").unwrap();
+        assert_eq!(puzzle.part2(input.as_ref()), Err("Negative factor".to_string()));
+        assert!(puzzle.set_param("factor", "x").is_err());
+        assert!(puzzle.set_param("size", "1").is_err());
+    }
+
+    #[test]
+    fn test_parse_params() {
+        let args = ["--param", "factor=3", "input.txt", "--json"].map(String::from).to_vec();
+        let (params, rest) = parse_params::<Sum>(args).unwrap();
+        assert_eq!(params.factor, 3);
+        assert_eq!(rest, ["input.txt", "--json"]);
+        assert!(parse_params::<Sum>(vec!["--param".to_string()]).is_err());
+        assert!(parse_params::<Sum>(["--param", "day1.factor=3"].map(String::from).to_vec()).is_err());
+    }
+
+    #[test]
+    fn test_take_mode() {
+        let mut args = ["input.txt", "--top", "3", "--json"].map(String::from).to_vec();
+        assert_eq!(take_mode(&mut args, "--route", 0), Ok(None));
+        assert_eq!(take_mode(&mut args, "--top", 1), Ok(Some(vec!["3".to_string()])));
+        assert_eq!(args, ["input.txt", "--json"]);
+        assert!(take_mode(&mut vec!["--top".to_string()], "--top", 1).is_err());
+    }
+
+    #[test]
+    fn test_input_path() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect();
+        assert_eq!(input_path(args(&[])), Ok(None));
+        assert_eq!(input_path(args(&["-"])), Ok(Some("-".to_string())));
+        assert_eq!(input_path(args(&["input.txt", "extra"])), Err("Unexpected argument: extra".to_string()));
+        assert_eq!(input_path(args(&["--top", "input.txt"])), Err("Unknown option: --top".to_string()));
+    }
+
+    #[test]
+    fn test_reports() {
+        let reader = |input: &'static str| HashReader::new(input.as_bytes());
+        let parts = reports::<Sum>(&mut reader("1 2"), &Params { factor: 0 }).unwrap();
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].answer, Ok(Answer::Int(3)));
+        assert_eq!(parts[1].answer, Ok(Answer::Int(0)));
+        assert_eq!(parts[0].input_hash, Some(report::input_hash("1 2")));
+        assert!(parts[0].warnings.is_empty());
+        assert_eq!(parts[1].warnings, ["Zero factor"]);
+        let mut input = reader("1 x");
+        assert!(reports::<Sum>(&mut input, &Params::default()).is_err());
+        assert_eq!(input.hash(), report::input_hash("1 x"));
+        let parts = reports::<Sum>(&mut reader("1 2"), &Params { factor: -1 }).unwrap();
+        assert_eq!(parts[1].answer, Err("Negative factor".to_string()));
+    }
+
+    #[test]
+    fn test_generate() {
+        let puzzle = Solver::<Sum>::new();
+        let input = puzzle.generate(&mut Rng::new(7), 5);
+        assert_eq!(input, puzzle.generate(&mut Rng::new(7), 5));
+        assert_eq!(Sum::parse(&input).unwrap().len(), 5);
+    }
+}
@@ -0,0 +1,92 @@
+use std::io::{self, BufRead};
+
+use crate::{ParseError, Source};
+
+/// Reads the input line by line and calls `f` with each line, without the
+/// line break, and its `Source` with the line number.
+///
+/// Only the current line is kept in memory. Errors report lines in the whole
+/// input, a read error (e.g. invalid UTF-8) points at the line which could
+/// not be read.
+pub fn read_lines<R: BufRead>(
+    day: u8,
+    mut reader: R,
+    mut f: impl FnMut(&Source, &str) -> Result<(), ParseError>,
+) -> Result<(), ParseError> {
+    let mut buffer = String::new();
+    for number in 1.. {
+        buffer.clear();
+        match reader.read_line(&mut buffer) {
+            Ok(0) => break,
+            Ok(_) => {},
+            Err(e) => return Err(read_error(day, number, e)),
+        }
+        let line = buffer.strip_suffix('\n').unwrap_or(&buffer);
+        f(&Source::at_line(day, line, number), line).map_err(|e| ParseError { line: number, ..e })?;
+    }
+    Ok(())
+}
+
+/// Reads the whole input, a read error points at the line which could not
+/// be read as in `read_lines`.
+pub fn read_input<R: BufRead>(day: u8, mut reader: R) -> Result<String, ParseError> {
+    let mut input = String::new();
+    for number in 1.. {
+        match reader.read_line(&mut input) {
+            Ok(0) => break,
+            Ok(_) => {},
+            Err(e) => return Err(read_error(day, number, e)),
+        }
+    }
+    Ok(input)
+}
+
+fn read_error(day: u8, line: usize, e: io::Error) -> ParseError {
+    ParseError {
+        day,
+        line,
+        column: 1,
+        width: 1,
+        snippet: String::new(),
+        message: format!("Failed to read input: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_lines() {
+        let mut lines = Vec::new();
+        let result = read_lines(1, "a\n\nbc\nd".as_bytes(), |source, line| {
+            lines.push((source.line, line.to_string()));
+            Ok(())
+        });
+        assert_eq!(result, Ok(()));
+        assert_eq!(lines, [(1, "a"), (2, ""), (3, "bc"), (4, "d")].map(|(n, line)| (n, line.to_string())));
+    }
+
+    #[test]
+    fn test_read_lines_error() {
+        let e = read_lines(4, "1\n2\n3 x\n4\n".as_bytes(), |source, line| {
+            for x in line.split(' ') {
+                source.parse::<u8>(x)?;
+            }
+            Ok(())
+        }).unwrap_err();
+        assert_eq!((e.day, e.line, e.column), (4, 3, 3));
+        assert_eq!(e.snippet, "3 x");
+
+        let e = read_lines(4, &b"1\n\xff\n"[..], |_, _| Ok(())).unwrap_err();
+        assert_eq!(e.line, 2);
+        assert!(e.message.starts_with("Failed to read input: "), "{}", e.message);
+    }
+
+    #[test]
+    fn test_read_input() {
+        assert_eq!(read_input(1, "a\n\nb".as_bytes()), Ok("a\n\nb".to_string()));
+        let e = read_input(1, &b"a\nb\n\xff\n"[..]).unwrap_err();
+        assert_eq!((e.day, e.line), (1, 3));
+    }
+}
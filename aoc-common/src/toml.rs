@@ -0,0 +1,175 @@
+use std::error;
+use std::fmt;
+
+/// Value in a TOML document.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Value {
+    Int(i64),
+    Str(String),
+    Bool(bool),
+}
+
+/// Table with key/value pairs in the order they appear in the document.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Table {
+    /// Table name, empty for keys before the first table header.
+    pub name: String,
+    pub entries: Vec<(String, Value)>,
+}
+
+impl Table {
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.entries.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+}
+
+/// Error in TOML document.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TomlError {
+    /// Line number, starting from 1.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for TomlError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl error::Error for TomlError {}
+
+/// Parses subset of TOML sufficient for simple configuration files: tables,
+/// comments, integers, booleans and (multi-line) basic strings.
+pub fn parse(text: &str) -> Result<Vec<Table>, TomlError> {
+    let mut tables = vec![Table::default()];
+    let mut lines = text.lines().enumerate();
+    while let Some((i, line)) = lines.next() {
+        let error = |message: &str| TomlError { line: i + 1, message: message.to_string() };
+        let line = strip_comment(line).trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[') {
+            let name = name.strip_suffix(']').ok_or_else(|| error("Expected \"]\""))?.trim();
+            if !is_key(name) {
+                return Err(error("Invalid table name"));
+            }
+            if tables.iter().any(|t| t.name == name) {
+                return Err(error("Duplicate table"));
+            }
+            tables.push(Table { name: name.to_string(), entries: Vec::new() });
+            continue;
+        }
+
+        let (key, value) = line.split_once('=').ok_or_else(|| error("Expected \"key = value\""))?;
+        let key = key.trim();
+        let value = value.trim();
+        if !is_key(key) {
+            return Err(error("Invalid key"));
+        }
+
+        let value = if let Some(rest) = value.strip_prefix("\"\"\"") {
+            // Newline right after the opening quotes is not part of the string.
+            let mut raw = rest.to_string();
+            while !raw.contains("\"\"\"") {
+                let (_, next) = lines.next().ok_or_else(|| error("Unterminated string"))?;
+                raw.push('\n');
+                raw.push_str(next);
+            }
+            let (raw, rest) = raw.split_once("\"\"\"").unwrap();
+            if !strip_comment(rest).trim().is_empty() {
+                return Err(error("Unexpected text after string"));
+            }
+            Value::Str(unescape(raw.strip_prefix('\n').unwrap_or(raw)).ok_or_else(|| error("Invalid escape"))?)
+        } else if let Some(rest) = value.strip_prefix('"') {
+            let raw = rest.strip_suffix('"').ok_or_else(|| error("Unterminated string"))?;
+            Value::Str(unescape(raw).ok_or_else(|| error("Invalid escape"))?)
+        } else if value == "true" || value == "false" {
+            Value::Bool(value == "true")
+        } else {
+            Value::Int(value.replace('_', "").parse().map_err(|_| error("Expected integer, string or boolean"))?)
+        };
+
+        let table = tables.last_mut().unwrap();
+        if table.get(key).is_some() {
+            return Err(error("Duplicate key"));
+        }
+        table.entries.push((key.to_string(), value));
+    }
+    Ok(tables)
+}
+
+fn is_key(key: &str) -> bool {
+    !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => {},
+        }
+    }
+    line
+}
+
+fn unescape(raw: &str) -> Option<String> {
+    let mut result = String::new();
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        result.push(match chars.next()? {
+            'n' => '\n',
+            't' => '\t',
+            '"' => '"',
+            '\\' => '\\',
+            _ => return None,
+        });
+    }
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let tables = parse("\
+# comment
+title = \"a # b\"
+
+[day10]
+part1 = 13_140 # comment
+part2 = \"\"\"
+##..
+#..#
+\"\"\"
+fast = false
+").unwrap();
+        assert_eq!(tables.len(), 2);
+        assert_eq!(tables[0].get("title"), Some(&Value::Str("a # b".to_string())));
+        assert_eq!(tables[1].name, "day10");
+        assert_eq!(tables[1].get("part1"), Some(&Value::Int(13140)));
+        assert_eq!(tables[1].get("part2"), Some(&Value::Str("##..\n#..#\n".to_string())));
+        assert_eq!(tables[1].get("fast"), Some(&Value::Bool(false)));
+        assert_eq!(tables[1].get("slow"), None);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(parse("[a]\nx = 1\nx = 2").unwrap_err(), TomlError { line: 3, message: "Duplicate key".to_string() });
+        assert_eq!(parse("x = \"\"\"\nabc").unwrap_err().line, 1);
+        assert_eq!(parse("\n\nx = y").unwrap_err().to_string(), "line 3: Expected integer, string or boolean");
+    }
+}
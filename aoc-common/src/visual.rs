@@ -0,0 +1,678 @@
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use crate::record::{Format, Palette, Recorder};
+use crate::{Direction, Grid, Point};
+
+pub const USAGE: &str = "\
+Visualization options:
+  --render          show the simulation in the terminal (days 17, 22, 23 and 24)
+  --record PATH     save frames as animated .gif or .png, or asciinema .cast
+  --fps N           frames per second (default: 20)
+  --paused          start paused, implies --render
+  --viewport WxH    visible part of a frame (default: 160x50)
+
+Recording options:
+  --skip N          keep every N-th frame (default: 1)
+  --scale N         pixels per cell in images (default: 4)
+  --palette SPEC    colours in images, e.g. \"#=ffffff,red=ff8000\", keys are
+                    characters of cells without colour or colour names
+  --max-frames N    stop recording after N frames (default: 1000)
+
+Controls, each followed by Enter:
+  (empty)           next frame and pause
+  p                 pause or resume
+  g N               seek to frame N
+  h|j|k|l [N]       scroll left, down, up or right
+  f                 follow the simulation again after scrolling
+  + or -            play faster or slower
+  q                 stop rendering and finish the simulation";
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static SINKS: Mutex<Vec<Box<dyn Sink>>> = Mutex::new(Vec::new());
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Color {
+    #[default]
+    Default,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Color {
+    pub const ALL: [Color; 7] = [
+        Color::Default, Color::Red, Color::Green, Color::Yellow, Color::Blue, Color::Magenta, Color::Cyan,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Color::Default => "default",
+            Color::Red => "red",
+            Color::Green => "green",
+            Color::Yellow => "yellow",
+            Color::Blue => "blue",
+            Color::Magenta => "magenta",
+            Color::Cyan => "cyan",
+        }
+    }
+
+    fn ansi(self) -> &'static str {
+        match self {
+            Color::Default => "\x1b[0m",
+            Color::Red => "\x1b[31;1m",
+            Color::Green => "\x1b[32;1m",
+            Color::Yellow => "\x1b[33;1m",
+            Color::Blue => "\x1b[34;1m",
+            Color::Magenta => "\x1b[35;1m",
+            Color::Cyan => "\x1b[36;1m",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Cell {
+    pub ch: char,
+    pub color: Color,
+}
+
+impl Cell {
+    pub const fn new(ch: char, color: Color) -> Self {
+        Cell { ch, color }
+    }
+}
+
+impl From<char> for Cell {
+    fn from(ch: char) -> Self {
+        Cell::new(ch, Color::Default)
+    }
+}
+
+/// Destination of frames.
+pub trait Sink: Send {
+    fn show(&mut self, frame: &dyn Frame);
+
+    /// No more frames are needed.
+    fn is_done(&self) -> bool {
+        false
+    }
+
+    /// Called once the simulation ends.
+    fn finish(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Single step of a simulation drawn as a grid of cells.
+pub trait Frame {
+    fn size(&self) -> Point<usize>;
+
+    /// Cell at a point inside `size()`.
+    fn cell(&self, p: Point<usize>) -> Cell;
+
+    /// Point the viewport keeps in view, usually the moving object.
+    fn focus(&self) -> Option<Point<usize>> {
+        None
+    }
+
+    /// Line shown above the frame.
+    fn status(&self) -> String {
+        String::new()
+    }
+}
+
+/// Visible part of a frame as it was drawn.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Picture {
+    pub cells: Grid<Cell>,
+    pub status: String,
+}
+
+impl Picture {
+    pub fn capture(frame: &dyn Frame, viewport: &Viewport) -> Self {
+        let rows: Vec<Vec<Point<usize>>> = viewport.rows(frame).map(|row| row.collect()).collect();
+        let width = rows.first().map_or(0, |row| row.len());
+        let cells = Grid::from_fn(width, rows.len(), |p| frame.cell(rows[p.y][p.x]));
+        Picture { cells, status: frame.status() }
+    }
+
+    /// Clears the terminal and draws the frame with its number and status.
+    pub fn ansi(&self, index: usize) -> String {
+        let mut text = "\x1b[H\x1b[J".to_string();
+        let separator = if self.status.is_empty() { "" } else { "  " };
+        let _ = writeln!(text, "frame {}{}{}", index, separator, self.status);
+        for row in self.cells.rows() {
+            let mut color = Color::Default;
+            for cell in row {
+                if cell.color != color {
+                    text.push_str(cell.color.ansi());
+                    color = cell.color;
+                }
+                text.push(cell.ch);
+            }
+            if color != Color::Default {
+                text.push_str(Color::Default.ansi());
+            }
+            text.push('\n');
+        }
+        text
+    }
+}
+
+/// Visible part of a frame.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Viewport {
+    pub origin: Point<usize>,
+    pub size: Point<usize>,
+    /// Scroll with the focus of frames.
+    pub follow: bool,
+}
+
+fn follow_axis(origin: usize, size: usize, focus: usize, len: usize) -> usize {
+    let margin = size / 4;
+    let origin = if focus < origin + margin {
+        focus.saturating_sub(margin)
+    } else if focus + margin >= origin + size {
+        (focus + margin + 1).saturating_sub(size)
+    } else {
+        origin
+    };
+    origin.min(len.saturating_sub(size))
+}
+
+impl Viewport {
+    pub fn new(size: Point<usize>) -> Self {
+        Viewport { origin: Point::new(0, 0), size, follow: true }
+    }
+
+    /// Scrolls the least to keep the focus away from the edges.
+    pub fn update(&mut self, frame: &dyn Frame) {
+        let len = frame.size();
+        let focus = match frame.focus() {
+            Some(focus) if self.follow => focus,
+            _ => self.origin,
+        };
+        self.origin = Point::new(
+            follow_axis(self.origin.x, self.size.x, focus.x, len.x),
+            follow_axis(self.origin.y, self.size.y, focus.y, len.y),
+        );
+    }
+
+    /// Scrolls manually, which stops following the focus.
+    pub fn scroll(&mut self, direction: Direction, n: usize, frame: &dyn Frame) {
+        let len = frame.size();
+        let Point { x, y } = self.origin;
+        self.origin = match direction {
+            Direction::Up => Point::new(x, y.saturating_sub(n)),
+            Direction::Down => Point::new(x, (y + n).min(len.y.saturating_sub(self.size.y))),
+            Direction::Left => Point::new(x.saturating_sub(n), y),
+            Direction::Right => Point::new((x + n).min(len.x.saturating_sub(self.size.x)), y),
+        };
+        self.follow = false;
+    }
+
+    /// Visible points of the frame by rows.
+    pub fn rows(&self, frame: &dyn Frame) -> impl Iterator<Item = impl Iterator<Item = Point<usize>>> {
+        let len = frame.size();
+        let Point { x: x1, y: y1 } = self.origin;
+        let x2 = (x1 + self.size.x).min(len.x);
+        let y2 = (y1 + self.size.y).min(len.y);
+        (y1..y2).map(move |y| (x1..x2).map(move |x| Point::new(x, y)))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Settings {
+    /// Show frames in the terminal.
+    pub render: bool,
+    /// Save frames to a file.
+    pub record: Option<PathBuf>,
+    pub fps: f64,
+    pub paused: bool,
+    pub viewport: Point<usize>,
+    /// Drawn frames kept for seeking back.
+    pub history: usize,
+    /// Record every n-th frame.
+    pub skip: usize,
+    /// Pixels per cell.
+    pub scale: usize,
+    pub palette: Palette,
+    pub max_frames: usize,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            render: false,
+            record: None,
+            fps: 20.0,
+            paused: false,
+            viewport: Point::new(160, 50),
+            history: 1000,
+            skip: 1,
+            scale: 4,
+            palette: Palette::default(),
+            max_frames: 1000,
+        }
+    }
+}
+
+fn parse_count(arg: &str, value: String) -> Result<usize, String> {
+    value.parse().ok()
+        .filter(|&n| n > 0)
+        .ok_or_else(|| format!("Invalid value for {}: {}", arg, value))
+}
+
+/// Separates visualization options from other arguments.
+pub fn parse_args(args: impl IntoIterator<Item = String>) -> Result<(Option<Settings>, Vec<String>), String> {
+    let mut settings = None;
+    let mut rest = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("Missing value for {}", arg));
+        match arg.as_str() {
+            "--render" => {
+                settings.get_or_insert_with(Settings::default).render = true;
+            },
+            "--record" => {
+                let path = PathBuf::from(value()?);
+                Format::from_path(&path)?;
+                settings.get_or_insert_with(Settings::default).record = Some(path);
+            },
+            "--fps" => {
+                let value = value()?;
+                let fps = value.parse::<f64>().ok()
+                    .filter(|&fps| fps > 0.0)
+                    .ok_or_else(|| format!("Invalid value for --fps: {}", value))?;
+                settings.get_or_insert_with(Settings::default).fps = fps;
+            },
+            "--paused" => {
+                let settings = settings.get_or_insert_with(Settings::default);
+                settings.paused = true;
+                settings.render = true;
+            },
+            "--viewport" => {
+                let value = value()?;
+                let size = value.split_once('x')
+                    .and_then(|(w, h)| Some(Point::new(w.parse().ok()?, h.parse().ok()?)))
+                    .filter(|size| size.x > 0 && size.y > 0)
+                    .ok_or_else(|| format!("Invalid value for --viewport: {}", value))?;
+                settings.get_or_insert_with(Settings::default).viewport = size;
+            },
+            "--skip" => {
+                settings.get_or_insert_with(Settings::default).skip = parse_count(&arg, value()?)?;
+            },
+            "--scale" => {
+                settings.get_or_insert_with(Settings::default).scale = parse_count(&arg, value()?)?;
+            },
+            "--palette" => {
+                settings.get_or_insert_with(Settings::default).palette = Palette::parse(&value()?)?;
+            },
+            "--max-frames" => {
+                settings.get_or_insert_with(Settings::default).max_frames = parse_count(&arg, value()?)?;
+            },
+            _ => rest.push(arg),
+        }
+    }
+    if settings.as_ref().is_some_and(|s| !s.render && s.record.is_none()) {
+        return Err("Visualization options require --render or --record".to_string());
+    }
+    Ok((settings, rest))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Command {
+    Step,
+    Pause,
+    Seek(usize),
+    Scroll(Direction, usize),
+    Follow,
+    Faster,
+    Slower,
+    Quit,
+}
+
+impl Command {
+    pub fn parse(line: &str) -> Option<Self> {
+        let mut words = line.split_whitespace();
+        let command = words.next().unwrap_or("");
+        let arg = words.next().map(|n| n.parse::<usize>().ok());
+        if words.next().is_some() {
+            return None;
+        }
+        let scroll = |direction| match arg {
+            None => Some(Command::Scroll(direction, 8)),
+            Some(n) => Some(Command::Scroll(direction, n?)),
+        };
+        match (command, arg) {
+            ("", None) => Some(Command::Step),
+            ("p", None) => Some(Command::Pause),
+            ("g", Some(n)) => Some(Command::Seek(n?)),
+            ("h", _) => scroll(Direction::Left),
+            ("j", _) => scroll(Direction::Down),
+            ("k", _) => scroll(Direction::Up),
+            ("l", _) => scroll(Direction::Right),
+            ("f", None) => Some(Command::Follow),
+            ("+", None) => Some(Command::Faster),
+            ("-", None) => Some(Command::Slower),
+            ("q", None) => Some(Command::Quit),
+            _ => None,
+        }
+    }
+}
+
+/// Draws frames as they are produced and reacts to commands in between.
+pub struct Player<W: Write> {
+    settings: Settings,
+    out: W,
+    commands: Option<Receiver<Command>>,
+    viewport: Viewport,
+    /// Frames produced so far.
+    frames: usize,
+    /// Drawn frames with their numbers.
+    history: VecDeque<(usize, String)>,
+    /// Frame to fast-forward to.
+    seek: Option<usize>,
+    stopped: bool,
+}
+
+impl<W: Write> Player<W> {
+    /// Without `commands` the player never pauses.
+    pub fn new(settings: Settings, out: W, commands: Option<Receiver<Command>>) -> Self {
+        let viewport = Viewport::new(settings.viewport);
+        let settings = Settings { paused: settings.paused && commands.is_some(), ..settings };
+        Player {
+            settings,
+            viewport,
+            out,
+            commands,
+            frames: 0,
+            history: VecDeque::new(),
+            seek: None,
+            stopped: false,
+        }
+    }
+
+    pub fn into_output(self) -> W {
+        self.out
+    }
+
+    fn draw(&self, index: usize, frame: &dyn Frame) -> String {
+        Picture::capture(frame, &self.viewport).ansi(index)
+    }
+
+    fn delay(&self) -> Duration {
+        Duration::from_secs_f64(1.0 / self.settings.fps)
+    }
+
+    fn wait(&mut self) -> Option<Command> {
+        let Some(commands) = &self.commands else {
+            thread::sleep(self.delay());
+            return None;
+        };
+        let command = if self.settings.paused {
+            commands.recv().map_err(|_| RecvTimeoutError::Disconnected)
+        } else {
+            commands.recv_timeout(self.delay())
+        };
+        match command {
+            Ok(command) => Some(command),
+            Err(RecvTimeoutError::Timeout) => None,
+            Err(RecvTimeoutError::Disconnected) => {
+                // Nobody can resume anymore.
+                self.commands = None;
+                self.settings.paused = false;
+                None
+            },
+        }
+    }
+
+    /// Scrolling applies only to the current frame, older ones are kept as drawn.
+    fn redraw(&mut self, index: usize, frame: &dyn Frame) {
+        let text = self.draw(index, frame);
+        if let Some(last) = self.history.back_mut() {
+            last.1 = text;
+        }
+    }
+}
+
+impl<W: Write + Send> Sink for Player<W> {
+    /// Returns when the simulation should go on.
+    fn show(&mut self, frame: &dyn Frame) {
+        let index = self.frames;
+        self.frames += 1;
+        if self.stopped {
+            return;
+        }
+        match self.seek {
+            Some(target) if index < target => return,
+            Some(_) => {
+                self.seek = None;
+                self.settings.paused = true;
+            },
+            None => {},
+        }
+
+        self.viewport.update(frame);
+        self.history.push_back((index, self.draw(index, frame)));
+        if self.history.len() > self.settings.history.max(1) {
+            self.history.pop_front();
+        }
+
+        // Position in history, the last one is the current frame.
+        let mut shown = self.history.len() - 1;
+        loop {
+            let live = shown + 1 == self.history.len();
+            let _ = self.out.write_all(self.history[shown].1.as_bytes());
+            let _ = self.out.flush();
+
+            match self.wait() {
+                None | Some(Command::Step) if !live => shown += 1,
+                None => return,
+                Some(Command::Step) => {
+                    self.settings.paused = true;
+                    return;
+                },
+                Some(Command::Pause) => {
+                    self.settings.paused = !self.settings.paused;
+                    if live && !self.settings.paused {
+                        return;
+                    }
+                },
+                Some(Command::Seek(target)) if target > index => {
+                    self.seek = Some(target);
+                    return;
+                },
+                Some(Command::Seek(target)) => {
+                    shown = self.history.iter().rposition(|(i, _)| *i <= target).unwrap_or(0);
+                    self.settings.paused = true;
+                },
+                Some(Command::Scroll(direction, n)) => {
+                    self.viewport.scroll(direction, n, frame);
+                    self.redraw(index, frame);
+                },
+                Some(Command::Follow) => {
+                    self.viewport.follow = true;
+                    self.viewport.update(frame);
+                    self.redraw(index, frame);
+                },
+                Some(Command::Faster) => self.settings.fps *= 2.0,
+                Some(Command::Slower) => self.settings.fps /= 2.0,
+                Some(Command::Quit) => {
+                    self.stopped = true;
+                    return;
+                },
+            }
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.stopped
+    }
+}
+
+fn read_commands() -> Receiver<Command> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for line in io::stdin().lines() {
+            let Ok(line) = line else { break };
+            if let Some(command) = Command::parse(&line) {
+                if sender.send(command).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+    receiver
+}
+
+/// Starts drawing frames passed to `show` in the terminal, with commands
+/// read from the standard input, or recording them.
+pub fn start(settings: Settings) {
+    let mut sinks = SINKS.lock().unwrap();
+    if let Some(path) = &settings.record {
+        sinks.push(Box::new(Recorder::new(path.clone(), &settings)));
+    }
+    if settings.render {
+        sinks.push(Box::new(Player::new(settings, io::stdout(), Some(read_commands()))));
+    }
+    ENABLED.store(!sinks.is_empty(), Ordering::Relaxed);
+}
+
+/// Whether frames should be passed to `show`, so that simulations do not
+/// need to build them otherwise.
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+pub fn show(frame: &dyn Frame) {
+    let mut sinks = SINKS.lock().unwrap();
+    for sink in sinks.iter_mut().filter(|sink| !sink.is_done()) {
+        sink.show(frame);
+    }
+    if sinks.iter().all(|sink| sink.is_done()) {
+        ENABLED.store(false, Ordering::Relaxed);
+    }
+}
+
+/// Stops showing frames and saves the recording.
+pub fn finish() -> io::Result<()> {
+    ENABLED.store(false, Ordering::Relaxed);
+    let sinks = std::mem::take(&mut *SINKS.lock().unwrap());
+    sinks.into_iter().try_for_each(|mut sink| sink.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counter moving down a column.
+    struct Column(usize);
+
+    impl Frame for Column {
+        fn size(&self) -> Point<usize> {
+            Point::new(2, 10)
+        }
+
+        fn cell(&self, p: Point<usize>) -> Cell {
+            if p.y == self.0 && p.x == 0 { Cell::new('@', Color::Red) } else { '.'.into() }
+        }
+
+        fn focus(&self) -> Option<Point<usize>> {
+            Some(Point::new(0, self.0))
+        }
+    }
+
+    fn play(settings: Settings, commands: &[Command], frames: usize) -> Vec<String> {
+        let (sender, receiver) = mpsc::channel();
+        for &command in commands {
+            sender.send(command).unwrap();
+        }
+        drop(sender);
+        let settings = Settings { render: true, fps: 1e6, viewport: Point::new(2, 4), ..settings };
+        let mut player = Player::new(settings, Vec::new(), Some(receiver));
+        for i in 0..frames {
+            player.show(&Column(i));
+        }
+        String::from_utf8(player.into_output()).unwrap()
+            .split("\x1b[H\x1b[J")
+            .skip(1)
+            .map(|s| s.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_viewport() {
+        let mut viewport = Viewport::new(Point::new(2, 4));
+        viewport.update(&Column(2));
+        assert_eq!(viewport.origin, Point::new(0, 0));
+        viewport.update(&Column(3));
+        assert_eq!(viewport.origin, Point::new(0, 1));
+        viewport.update(&Column(9));
+        assert_eq!(viewport.origin, Point::new(0, 6));
+        viewport.scroll(Direction::Up, 100, &Column(9));
+        assert_eq!(viewport.origin, Point::new(0, 0));
+        viewport.update(&Column(9));
+        assert_eq!(viewport.origin, Point::new(0, 0));
+        let rows: Vec<Vec<_>> = viewport.rows(&Column(0)).map(|row| row.collect()).collect();
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[3], [Point::new(0, 3), Point::new(1, 3)]);
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = |s: &str| s.split(' ').map(|x| x.to_string()).collect::<Vec<_>>();
+        assert_eq!(parse_args(args("input.txt")), Ok((None, args("input.txt"))));
+        let (settings, rest) = parse_args(args("--render --fps 5 input.txt --viewport 20x10")).unwrap();
+        let settings = settings.unwrap();
+        assert_eq!((settings.fps, settings.viewport), (5.0, Point::new(20, 10)));
+        assert_eq!(rest, ["input.txt"]);
+        assert!(parse_args(args("--render --fps 0")).is_err());
+        assert!(parse_args(args("--render --viewport 20")).is_err());
+        assert!(parse_args(args("--fps 5")).is_err());
+
+        let (settings, _) = parse_args(args("--record out.gif --skip 2 --palette @=ff0000")).unwrap();
+        let settings = settings.unwrap();
+        assert_eq!((settings.render, settings.skip), (false, 2));
+        assert_eq!(settings.record, Some(PathBuf::from("out.gif")));
+        assert!(parse_args(args("--record out.txt")).is_err());
+    }
+
+    #[test]
+    fn test_command() {
+        assert_eq!(Command::parse(""), Some(Command::Step));
+        assert_eq!(Command::parse("g 12"), Some(Command::Seek(12)));
+        assert_eq!(Command::parse("g"), None);
+        assert_eq!(Command::parse("j"), Some(Command::Scroll(Direction::Down, 8)));
+        assert_eq!(Command::parse("h 3"), Some(Command::Scroll(Direction::Left, 3)));
+        assert_eq!(Command::parse("x"), None);
+    }
+
+    #[test]
+    fn test_player() {
+        let frames = play(Settings::default(), &[], 3);
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[1], "frame 1\n..\n\x1b[31;1m@\x1b[0m.\n..\n..\n");
+
+        // Seeking forward skips drawing and pauses at the target.
+        let frames = play(Settings::default(), &[Command::Seek(5), Command::Quit], 10);
+        assert_eq!(frames.len(), 2);
+        assert!(frames[1].starts_with("frame 5\n"));
+
+        // Seeking back replays the history while the simulation waits.
+        let commands = [Command::Step, Command::Step, Command::Seek(0), Command::Step, Command::Step];
+        let frames = play(Settings::default(), &commands, 3);
+        let numbers: Vec<_> = frames.iter().map(|f| f.lines().next().unwrap()).collect();
+        assert_eq!(numbers, ["frame 0", "frame 1", "frame 2", "frame 0", "frame 1", "frame 2"]);
+    }
+}
@@ -0,0 +1,334 @@
+use std::fs;
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+use std::process::{self, ExitCode};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use aoc_common::json::Json;
+use aoc_common::{repository_dir, Puzzle};
+
+use crate::{catch, load_input, Options};
+
+pub struct BenchOptions {
+    pub iterations: usize,
+    pub warmup: usize,
+    pub history: Option<PathBuf>,
+    /// Slowdown of median in percents reported as regression.
+    pub threshold: f64,
+    pub label: Option<String>,
+    /// Label of the run to compare with instead of the last one.
+    pub baseline: Option<String>,
+    pub save: bool,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        BenchOptions {
+            iterations: 10,
+            warmup: 2,
+            history: None,
+            threshold: 10.0,
+            label: None,
+            baseline: None,
+            save: true,
+        }
+    }
+}
+
+/// Timing statistics in nanoseconds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub mean: f64,
+    pub median: f64,
+    pub stddev: f64,
+}
+
+impl Stats {
+    pub fn from(samples: &[Duration]) -> Self {
+        let mut ns: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        ns.sort_by(|a, b| a.total_cmp(b));
+        let n = ns.len() as f64;
+        let mean = ns.iter().sum::<f64>() / n;
+        let median = if ns.len().is_multiple_of(2) {
+            (ns[ns.len() / 2 - 1] + ns[ns.len() / 2]) / 2.0
+        } else {
+            ns[ns.len() / 2]
+        };
+        let variance = ns.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+        Stats { mean, median, stddev: variance.sqrt() }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Timing {
+    pub day: usize,
+    /// "parse", "part1" or "part2"
+    pub phase: String,
+    pub stats: Stats,
+}
+
+/// Benchmark run stored in history.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Run {
+    pub label: String,
+    pub timestamp: u64,
+    pub iterations: usize,
+    pub timings: Vec<Timing>,
+}
+
+impl Run {
+    fn to_json(&self) -> Json {
+        let timings = self.timings.iter().map(|t| Json::object([
+            ("day", t.day.into()),
+            ("phase", t.phase.as_str().into()),
+            ("mean_ns", t.stats.mean.round().into()),
+            ("median_ns", t.stats.median.round().into()),
+            ("stddev_ns", t.stats.stddev.round().into()),
+        ])).collect();
+        Json::object([
+            ("label", self.label.as_str().into()),
+            ("timestamp", self.timestamp.into()),
+            ("iterations", self.iterations.into()),
+            ("timings", Json::Array(timings)),
+        ])
+    }
+
+    fn from_json(json: &Json) -> Option<Self> {
+        let number = |json: &Json, key| json.get(key).and_then(Json::as_f64);
+        let timings = json.get("timings")?.as_array()?.iter().map(|t| Some(Timing {
+            day: number(t, "day")? as usize,
+            phase: t.get("phase")?.as_str()?.to_string(),
+            stats: Stats {
+                mean: number(t, "mean_ns")?,
+                median: number(t, "median_ns")?,
+                stddev: number(t, "stddev_ns")?,
+            },
+        })).collect::<Option<_>>()?;
+        Some(Run {
+            label: json.get("label")?.as_str()?.to_string(),
+            timestamp: number(json, "timestamp")? as u64,
+            iterations: number(json, "iterations")? as usize,
+            timings,
+        })
+    }
+
+    fn find(&self, day: usize, phase: &str) -> Option<&Stats> {
+        self.timings.iter().find(|t| t.day == day && t.phase == phase).map(|t| &t.stats)
+    }
+}
+
+pub fn load_history(path: &Path) -> Result<Vec<Run>, String> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("{}: {}", path.display(), e)),
+    };
+    let json = Json::parse(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+    json.as_array()
+        .ok_or_else(|| format!("{}: Expected array of runs", path.display()))?
+        .iter()
+        .map(|run| Run::from_json(run).ok_or_else(|| format!("{}: Invalid run: {}", path.display(), run)))
+        .collect()
+}
+
+/// Stores history with a run on each line so it can be diffed easily.
+pub fn save_history(path: &Path, history: &[Run]) -> Result<(), String> {
+    let runs: Vec<String> = history.iter().map(|run| run.to_json().to_string()).collect();
+    fs::write(path, format!("[\n{}\n]\n", runs.join(",\n")))
+        .map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Relative change of median in percents.
+pub fn change(previous: &Stats, current: &Stats) -> f64 {
+    (current.median - previous.median) / previous.median.max(1.0) * 100.0
+}
+
+fn measure(options: &BenchOptions, mut f: impl FnMut()) -> Stats {
+    for _ in 0..options.warmup {
+        f();
+    }
+    let samples: Vec<Duration> = (0..options.iterations).map(|_| {
+        let start = Instant::now();
+        f();
+        start.elapsed()
+    }).collect();
+    Stats::from(&samples)
+}
+
+/// Times parsing and selected parts.
+pub fn bench_day(puzzle: &dyn Puzzle, input: &str, options: &Options) -> Result<Vec<Timing>, String> {
+    let day = puzzle.day() as usize;
+    let parsed = puzzle.parse(input).map_err(|e| e.to_string())?;
+    let mut timings = vec![Timing {
+        day,
+        phase: "parse".to_string(),
+        stats: measure(&options.bench, || { black_box(puzzle.parse(black_box(input)).ok()); }),
+    }];
+    for &part in &options.parts {
+        let solve = || if part == 1 {
+            puzzle.part1(parsed.as_ref())
+        } else {
+            puzzle.part2(parsed.as_ref())
+        };
+        solve().map_err(|e| format!("part {}: {}", part, e))?;
+        let stats = measure(&options.bench, || { black_box(solve().ok()); });
+        timings.push(Timing { day, phase: format!("part{}", part), stats });
+    }
+    Ok(timings)
+}
+
+fn git_commit() -> Option<String> {
+    let output = process::Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .current_dir(repository_dir())
+        .output()
+        .ok()?;
+    let commit = String::from_utf8(output.stdout).ok()?;
+    Some(commit.trim().to_string()).filter(|c| output.status.success() && !c.is_empty())
+}
+
+fn format_ns(ns: f64) -> String {
+    format!("{:.3?}", Duration::from_nanos(ns.round() as u64))
+}
+
+pub fn bench(options: &Options) -> ExitCode {
+    let path = options.bench.history.clone()
+        .unwrap_or_else(|| repository_dir().join("bench_history.json"));
+    let mut history = match load_history(&path) {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::FAILURE;
+        },
+    };
+    let previous = match &options.bench.baseline {
+        Some(label) => match history.iter().rev().find(|run| run.label == *label) {
+            Some(run) => Some(run.clone()),
+            None => {
+                eprintln!("error: No run labeled {:?} in {}", label, path.display());
+                return ExitCode::FAILURE;
+            },
+        },
+        None => history.last().cloned(),
+    };
+
+    println!("{:>3}  {:<5}  {:>12} {:>12} {:>12}  {:>8}", "Day", "Phase", "Mean", "Median", "Stddev", "Change");
+    let puzzles = options.puzzles();
+    let mut timings = Vec::new();
+    let mut failed = 0;
+    let mut regressions = 0;
+    for &day in &options.days {
+        let puzzle = puzzles[day - 1].as_ref();
+        let input = match load_input(puzzle, options) {
+            Ok(input) => input,
+            Err(e) => {
+                println!("{:>3}  skipped: {}", day, e);
+                continue;
+            },
+        };
+        let day_timings = match catch(|| bench_day(puzzle, &input, options)) {
+            (Ok(Ok(day_timings)), _) => day_timings,
+            (Ok(Err(e)), _) | (Err(e), _) => {
+                println!("{:>3}  FAILED: {}", day, e);
+                failed += 1;
+                continue;
+            },
+        };
+        for timing in day_timings {
+            let stats = &timing.stats;
+            let change = previous.as_ref()
+                .and_then(|run| run.find(day, &timing.phase))
+                .map(|previous| change(previous, stats));
+            let note = match change {
+                Some(change) if change > options.bench.threshold => {
+                    regressions += 1;
+                    format!("{:>+7.1}%  REGRESSION", change)
+                },
+                Some(change) => format!("{:>+7.1}%", change),
+                None => format!("{:>8}", "-"),
+            };
+            println!("{:>3}  {:<5}  {:>12} {:>12} {:>12}  {}",
+                day, timing.phase, format_ns(stats.mean), format_ns(stats.median), format_ns(stats.stddev), note);
+            timings.push(timing);
+        }
+    }
+
+    if let Some(previous) = &previous {
+        println!("\nCompared with {:?}", previous.label);
+    }
+
+    if options.bench.save && !timings.is_empty() {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+        let label = options.bench.label.clone().or_else(git_commit).unwrap_or_else(|| "unknown".to_string());
+        history.push(Run { label, timestamp, iterations: options.bench.iterations, timings });
+        if let Err(e) = save_history(&path, &history) {
+            eprintln!("error: {}", e);
+            return ExitCode::FAILURE;
+        }
+        println!("Saved to {}", path.display());
+    }
+
+    if regressions > 0 {
+        eprintln!("Regressions over {}%: {}", options.bench.threshold, regressions);
+    }
+    if failed > 0 {
+        eprintln!("Failed days: {}", failed);
+    }
+    if regressions > 0 || failed > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::days;
+    use crate::Command;
+
+    fn stats(median: f64) -> Stats {
+        Stats { mean: median, median, stddev: 0.0 }
+    }
+
+    #[test]
+    fn test_stats() {
+        let samples = [3, 1, 2, 10].map(Duration::from_nanos);
+        assert_eq!(Stats::from(&samples), Stats { mean: 4.0, median: 2.5, stddev: 3.5355339059327378 });
+        assert_eq!(Stats::from(&samples[..3]).median, 2.0);
+    }
+
+    #[test]
+    fn test_change() {
+        assert_eq!(change(&stats(100.0), &stats(125.0)), 25.0);
+        assert_eq!(change(&stats(100.0), &stats(90.0)), -10.0);
+    }
+
+    #[test]
+    fn test_history() {
+        let path = std::env::temp_dir().join(format!("aoc-bench-{}.json", process::id()));
+        let run = Run {
+            label: "abc123".to_string(),
+            timestamp: 1671926400,
+            iterations: 5,
+            timings: vec![Timing { day: 1, phase: "part1".to_string(), stats: stats(1500.0) }],
+        };
+        assert_eq!(load_history(&path), Ok(vec![]));
+        save_history(&path, &[run.clone(), run.clone()]).unwrap();
+        assert_eq!(load_history(&path), Ok(vec![run.clone(), run]));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_bench_day() {
+        let options = Options {
+            parts: vec![2],
+            bench: BenchOptions { iterations: 3, warmup: 0, ..Default::default() },
+            ..Options::new(Command::Bench)
+        };
+        let input = aoc_common::example!("../advent01/test.txt");
+        let timings = bench_day(days()[0].as_ref(), input, &options).unwrap();
+        let phases: Vec<_> = timings.iter().map(|t| t.phase.as_str()).collect();
+        assert_eq!(phases, ["parse", "part2"]);
+    }
+}
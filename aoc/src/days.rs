@@ -0,0 +1,45 @@
+use aoc_common::{Puzzle, Settings, Solver};
+
+pub const DAY_COUNT: usize = 25;
+
+/// Solutions for all days with default parameters, ordered by day.
+pub fn days() -> Vec<Box<dyn Puzzle>> {
+    vec![
+        Box::new(Solver::<advent2022_01::Day01>::new()),
+        Box::new(Solver::<advent2022_02::Day02>::new()),
+        Box::new(Solver::<advent2022_03::Day03>::new()),
+        Box::new(Solver::<advent2022_04::Day04>::new()),
+        Box::new(Solver::<advent2022_05::Day05>::new()),
+        Box::new(Solver::<advent2022_06::Day06>::new()),
+        Box::new(Solver::<advent2022_07::Day07>::new()),
+        Box::new(Solver::<advent2022_08::Day08>::new()),
+        Box::new(Solver::<advent2022_09::Day09>::new()),
+        Box::new(Solver::<advent2022_10::Day10>::new()),
+        Box::new(Solver::<advent2022_11::Day11>::new()),
+        Box::new(Solver::<advent2022_12::Day12>::new()),
+        Box::new(Solver::<advent2022_13::Day13>::new()),
+        Box::new(Solver::<advent2022_14::Day14>::new()),
+        Box::new(Solver::<advent2022_15::Day15>::new()),
+        Box::new(Solver::<advent2022_16::Day16>::new()),
+        Box::new(Solver::<advent2022_17::Day17>::new()),
+        Box::new(Solver::<advent2022_18::Day18>::new()),
+        Box::new(Solver::<advent2022_19::Day19>::new()),
+        Box::new(Solver::<advent2022_20::Day20>::new()),
+        Box::new(Solver::<advent2022_21::Day21>::new()),
+        Box::new(Solver::<advent2022_22::Day22>::new()),
+        Box::new(Solver::<advent2022_23::Day23>::new()),
+        Box::new(Solver::<advent2022_24::Day24>::new()),
+        Box::new(Solver::<advent2022_25::Day25>::new()),
+    ]
+}
+
+/// Solutions for all days with parameters changed by the settings.
+pub fn configured_days(settings: &Settings) -> Result<Vec<Box<dyn Puzzle>>, String> {
+    let mut days = days();
+    for puzzle in &mut days {
+        let day = puzzle.day();
+        settings.apply(day, |key, value| puzzle.set_param(key, value))?;
+        puzzle.validate_params().map_err(|e| format!("day {}: {}", day, e))?;
+    }
+    Ok(days)
+}
@@ -0,0 +1,109 @@
+use std::process::ExitCode;
+
+use aoc_common::{parallel, Puzzle, Rng};
+
+use crate::{catch, solve_input, Options};
+
+pub struct FuzzOptions {
+    pub seed: u64,
+    pub size: usize,
+    pub iterations: usize,
+}
+
+impl Default for FuzzOptions {
+    fn default() -> Self {
+        FuzzOptions {
+            seed: 0,
+            size: 100,
+            iterations: 10,
+        }
+    }
+}
+
+fn generate_input(puzzle: &dyn Puzzle, seed: u64, size: usize) -> Result<String, String> {
+    catch(|| puzzle.generate(&mut Rng::new(seed), size)).0
+}
+
+/// Errors of selected parts for an input generated from the seed.
+pub fn fuzz_day(puzzle: &dyn Puzzle, seed: u64, options: &Options) -> Vec<String> {
+    let input = match generate_input(puzzle, seed, options.fuzz.size) {
+        Ok(input) => input,
+        Err(e) => return vec![format!("generator: {}", e)],
+    };
+    solve_input(puzzle, &input, options).into_iter()
+        .filter_map(|row| row.answer.err().map(|e| format!("part {}: {}", row.part, e)))
+        .collect()
+}
+
+/// Prints generated input for the selected day.
+pub fn generate(options: &Options) -> ExitCode {
+    let puzzle = &options.puzzles()[options.days[0] - 1];
+    match generate_input(puzzle.as_ref(), options.fuzz.seed, options.fuzz.size) {
+        Ok(input) => {
+            print!("{}", input);
+            ExitCode::SUCCESS
+        },
+        Err(e) => {
+            eprintln!("error: Failed to generate input: {}", e);
+            ExitCode::FAILURE
+        },
+    }
+}
+
+/// Solves generated inputs and reports the seeds which fail.
+pub fn fuzz(options: &Options) -> ExitCode {
+    let puzzles = options.puzzles();
+    let mut failed = 0;
+    for &day in &options.days {
+        let puzzle = puzzles[day - 1].as_ref();
+        let mut day_failed = 0;
+        let seeds: Vec<u64> = (options.fuzz.seed..).take(options.fuzz.iterations).collect();
+        let errors = parallel::map(&seeds, |&seed| fuzz_day(puzzle, seed, options));
+        for (seed, errors) in seeds.into_iter().zip(errors) {
+            for e in &errors {
+                println!("day {:>2}  seed {:<6} {}", day, seed, e);
+            }
+            if !errors.is_empty() {
+                eprintln!("  reproduce: aoc generate --day {} --seed {} --size {}", day, seed, options.fuzz.size);
+                day_failed += 1;
+            }
+        }
+        println!("day {:>2}  {} of {} inputs passed", day, options.fuzz.iterations - day_failed, options.fuzz.iterations);
+        failed += day_failed;
+    }
+
+    if failed > 0 {
+        eprintln!("Failed inputs: {}", failed);
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::days;
+    use crate::Command;
+
+    #[test]
+    fn test_generate() {
+        for puzzle in days() {
+            let input = generate_input(puzzle.as_ref(), 1, 20).unwrap();
+            assert_eq!(generate_input(puzzle.as_ref(), 1, 20).as_ref(), Ok(&input));
+            assert!(puzzle.parse(&input).is_ok(), "day {}:\n{}", puzzle.day(), input);
+        }
+    }
+
+    #[test]
+    fn test_fuzz_day() {
+        let options = Options {
+            days: vec![5],
+            fuzz: FuzzOptions { size: 20, ..FuzzOptions::default() },
+            ..Options::new(Command::Fuzz)
+        };
+        for seed in 0..10 {
+            assert_eq!(fuzz_day(days()[4].as_ref(), seed, &options), Vec::<String>::new());
+        }
+    }
+}
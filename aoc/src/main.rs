@@ -0,0 +1,477 @@
+mod bench;
+mod days;
+mod fuzz;
+mod verify;
+
+use std::env;
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use aoc_common::report::{self, Report};
+use aoc_common::{parallel, Answer, InputError, Inputs, Puzzle, Settings};
+use days::{configured_days, DAY_COUNT};
+
+const USAGE: &str = "\
+Usage: aoc run [--day N] [--part P] [--input PATH] [--threads N] [--json]
+       aoc verify [--day N] [--part P] [--input PATH] [--answers PATH]
+                  [--threads N]
+       aoc bench [--day N] [--part P] [--input PATH] [--iterations N]
+                 [--warmup N] [--history PATH] [--threshold PCT]
+                 [--label NAME] [--baseline NAME] [--no-save] [--threads N]
+       aoc generate --day N [--seed S] [--size N]
+       aoc fuzz [--day N] [--part P] [--seed S] [--size N] [--iterations N]
+                [--threads N]
+
+All commands also take [--param dayN.KEY=VALUE]... [--config PATH].
+
+Commands:
+  run       run solutions for all days (or a single day/part) and print
+            a table with the answers and timings
+  verify    compare answers with the recorded ones and print a matrix of
+            passed, failed and skipped parts (skipped if input is missing)
+  bench     time parsing and each part separately, compare the medians
+            with the previous run in the history and fail on regressions
+  generate  print a random valid input for the day
+  fuzz      solve random inputs (seeds S, S+1, ...) and report the ones
+            which fail to parse or panic
+
+Options:
+  --day N         run only given day (1-25)
+  --part P        run only given part (1 or 2)
+  --input PATH    read input from PATH, \"-\" for stdin (requires --day)
+  --answers PATH  recorded answers (default: answers.toml)
+  --iterations N  timed iterations for each phase, or inputs to fuzz
+                  for each day (default: 10)
+  --warmup N      untimed iterations before measuring (default: 2)
+  --history PATH  benchmark history (default: bench_history.json)
+  --threshold PCT slowdown of median reported as regression (default: 10)
+  --label NAME    name of the run in history (default: git commit)
+  --baseline NAME compare with the last run of given name
+  --no-save       do not append the run to history
+  --seed S        seed of the first generated input (default: 0)
+  --size N        size of generated inputs, e.g. number of lines or
+                  grid side (default: 100)
+  --threads N     worker threads for solving days and independent parts
+                  of solutions in parallel (default: number of CPUs)
+  --single-thread same as --threads 1, e.g. to compare benchmarks
+  --json          print JSON Lines instead of a table, an object for each
+                  part with day, part, type, answer, elapsed (seconds),
+                  input_hash, warnings and error
+  --param dayN.KEY=VALUE
+                  change puzzle parameter, e.g. day11.rounds2=20
+  --config PATH   read puzzle parameters from a TOML file with a table
+                  for each day, e.g. [day11] with rounds2 = 20
+
+Environment:
+  AOC_INPUT      input path, \"{day}\" is replaced with two-digit day
+  AOC_CACHE_DIR  directory with cached inputs (default: inputs)
+  AOC_URL        server to download missing inputs from
+  AOC_SESSION    session token for the server";
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Run,
+    Verify,
+    Bench,
+    Generate,
+    Fuzz,
+}
+
+struct Options {
+    command: Command,
+    days: Vec<usize>,
+    parts: Vec<u8>,
+    input: Option<String>,
+    answers: Option<PathBuf>,
+    bench: bench::BenchOptions,
+    fuzz: fuzz::FuzzOptions,
+    /// Worker threads, zero for one for each CPU.
+    threads: usize,
+    json: bool,
+    /// Puzzle parameters, the last value of each one wins.
+    settings: Settings,
+}
+
+impl Options {
+    fn new(command: Command) -> Self {
+        Options {
+            command,
+            days: (1..=DAY_COUNT).collect(),
+            parts: vec![1, 2],
+            input: None,
+            answers: None,
+            bench: bench::BenchOptions::default(),
+            fuzz: fuzz::FuzzOptions::default(),
+            threads: 0,
+            json: false,
+            settings: Settings::default(),
+        }
+    }
+
+    /// Solutions for all days with the puzzle parameters.
+    fn puzzles(&self) -> Vec<Box<dyn Puzzle>> {
+        configured_days(&self.settings).expect("Parameters are checked when parsing arguments")
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(arg: &str, value: Option<String>) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("Missing value for {}", arg))?;
+    value.parse::<T>().map_err(|_| format!("Invalid value for {}: {}", arg, value))
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let command = match args.next().as_deref() {
+        Some("run") => Command::Run,
+        Some("verify") => Command::Verify,
+        Some("bench") => Command::Bench,
+        Some("generate") => Command::Generate,
+        Some("fuzz") => Command::Fuzz,
+        Some(command) => return Err(format!("Unknown command: {}", command)),
+        None => return Err("Missing command".to_string()),
+    };
+
+    let mut options = Options::new(command);
+    let mut has_day = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" => {
+                let day: usize = parse_number(&arg, args.next())?;
+                if day == 0 || day > DAY_COUNT {
+                    return Err(format!("Day must be between 1 and {}", DAY_COUNT));
+                }
+                options.days = vec![day];
+                has_day = true;
+            },
+            "--part" => {
+                let part: u8 = parse_number(&arg, args.next())?;
+                if part != 1 && part != 2 {
+                    return Err("Part must be 1 or 2".to_string());
+                }
+                options.parts = vec![part];
+            },
+            "--input" if !matches!(options.command, Command::Generate | Command::Fuzz) => {
+                let path = args.next().ok_or("Missing value for --input")?;
+                options.input = Some(path);
+            },
+            "--answers" if options.command == Command::Verify => {
+                let path = args.next().ok_or("Missing value for --answers")?;
+                options.answers = Some(PathBuf::from(path));
+            },
+            "--iterations" if options.command == Command::Bench => {
+                options.bench.iterations = parse_number(&arg, args.next())?;
+                if options.bench.iterations == 0 {
+                    return Err("Iterations must be at least 1".to_string());
+                }
+            },
+            "--warmup" if options.command == Command::Bench => {
+                options.bench.warmup = parse_number(&arg, args.next())?;
+            },
+            "--history" if options.command == Command::Bench => {
+                let path = args.next().ok_or("Missing value for --history")?;
+                options.bench.history = Some(PathBuf::from(path));
+            },
+            "--threshold" if options.command == Command::Bench => {
+                options.bench.threshold = parse_number(&arg, args.next())?;
+            },
+            "--label" if options.command == Command::Bench => {
+                options.bench.label = Some(args.next().ok_or("Missing value for --label")?);
+            },
+            "--baseline" if options.command == Command::Bench => {
+                options.bench.baseline = Some(args.next().ok_or("Missing value for --baseline")?);
+            },
+            "--no-save" if options.command == Command::Bench => {
+                options.bench.save = false;
+            },
+            "--iterations" if options.command == Command::Fuzz => {
+                options.fuzz.iterations = parse_number(&arg, args.next())?;
+            },
+            "--seed" if matches!(options.command, Command::Generate | Command::Fuzz) => {
+                options.fuzz.seed = parse_number(&arg, args.next())?;
+            },
+            "--size" if matches!(options.command, Command::Generate | Command::Fuzz) => {
+                options.fuzz.size = parse_number(&arg, args.next())?;
+            },
+            "--threads" if options.command != Command::Generate => {
+                options.threads = parse_number(&arg, args.next())?;
+                if options.threads == 0 {
+                    return Err("Threads must be at least 1".to_string());
+                }
+            },
+            "--single-thread" if options.command != Command::Generate => {
+                options.threads = 1;
+            },
+            "--json" if options.command == Command::Run => {
+                options.json = true;
+            },
+            "--param" => {
+                let param = args.next().ok_or("Missing value for --param")?;
+                options.settings.add_arg(&param, None)?;
+            },
+            "--config" => {
+                let path = args.next().ok_or("Missing value for --config")?;
+                options.settings.load(Path::new(&path))?;
+            },
+            _ => return Err(format!("Unexpected argument: {}", arg)),
+        }
+    }
+
+    if options.input.is_some() && !has_day {
+        return Err("Option --input requires --day".to_string());
+    }
+    if options.command == Command::Generate && !has_day {
+        return Err("Command generate requires --day".to_string());
+    }
+    configured_days(&options.settings)?;
+
+    Ok(options)
+}
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        msg.to_string()
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        "panicked".to_string()
+    }
+}
+
+fn catch<T>(f: impl FnOnce() -> T) -> (Result<T, String>, Duration) {
+    let start = Instant::now();
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(f));
+    (result.map_err(panic_message), start.elapsed())
+}
+
+fn failed_rows(day: u8, options: &Options, error: String, input_hash: Option<u64>) -> Vec<Report> {
+    options.parts.iter().map(|&part| Report {
+        day,
+        part,
+        answer: Err(error.clone()),
+        elapsed: Duration::ZERO,
+        input_hash,
+        warnings: Vec::new(),
+    }).collect()
+}
+
+fn load_input(puzzle: &dyn Puzzle, options: &Options) -> Result<String, InputError> {
+    Inputs::from_env().with_path(options.input.clone()).load(puzzle.day())
+}
+
+/// Solves selected parts, including the ones without a puzzle (`Answer::None`).
+fn solve_input(puzzle: &dyn Puzzle, input: &str, options: &Options) -> Vec<Report> {
+    let day = puzzle.day();
+    let input_hash = Some(report::input_hash(input));
+    report::take_warnings();
+    let input = match catch(|| puzzle.parse(input)) {
+        (Ok(Ok(input)), _) => input,
+        (Ok(Err(e)), _) => return failed_rows(day, options, e.to_string(), input_hash),
+        (Err(e), _) => return failed_rows(day, options, e, input_hash),
+    };
+    let parse_warnings = report::take_warnings();
+
+    options.parts.iter().map(|&part| {
+        let (answer, elapsed) = catch(|| {
+            if part == 1 {
+                puzzle.part1(input.as_ref())
+            } else {
+                puzzle.part2(input.as_ref())
+            }
+        });
+        let warnings = parse_warnings.iter().cloned().chain(report::take_warnings()).collect();
+        Report {day, part, answer: answer.flatten(), elapsed, input_hash, warnings}
+    }).collect()
+}
+
+fn run_day(puzzle: &dyn Puzzle, options: &Options) -> Vec<Report> {
+    let input = match load_input(puzzle, options) {
+        Ok(input) => input,
+        Err(e) => return failed_rows(puzzle.day(), options, e.to_string(), None),
+    };
+    solve_input(puzzle, &input, options).into_iter()
+        .filter(|row| row.answer != Ok(Answer::None))
+        .collect()
+}
+
+fn print_row(row: &Report) {
+    let time = format!("{:.3?}", row.elapsed);
+    match &row.answer {
+        Ok(Answer::Glyph(picture)) => {
+            println!("{:>3}  {:>4}  {:<20} {:>12}", row.day, row.part, "", time);
+            for line in picture.trim_end().lines() {
+                println!("           {}", line);
+            }
+        },
+        Ok(answer) => println!("{:>3}  {:>4}  {:<20} {:>12}", row.day, row.part, answer.to_string(), time),
+        Err(e) => println!("{:>3}  {:>4}  FAILED: {}", row.day, row.part, e),
+    }
+    for warning in &row.warnings {
+        println!("           warning: {}", warning);
+    }
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        },
+    };
+
+    // Panics are reported in the table instead.
+    panic::set_hook(Box::new(|_| {}));
+    parallel::set_threads(options.threads);
+
+    match options.command {
+        Command::Run => {},
+        Command::Verify => return verify::verify(&options),
+        Command::Bench => return bench::bench(&options),
+        Command::Generate => return fuzz::generate(&options),
+        Command::Fuzz => return fuzz::fuzz(&options),
+    }
+
+    if !options.json {
+        println!("{:>3}  {:>4}  {:<20} {:>12}", "Day", "Part", "Answer", "Time");
+    }
+    let mut failed = 0;
+    let mut total = Duration::ZERO;
+    let puzzles = options.puzzles();
+    let rows = parallel::map(&options.days, |&day| run_day(puzzles[day - 1].as_ref(), &options));
+    for rows in rows {
+        for row in rows {
+            if options.json {
+                println!("{}", row.to_json());
+            } else {
+                print_row(&row);
+            }
+            total += row.elapsed;
+            failed += row.answer.is_err() as usize;
+        }
+    }
+    if !options.json {
+        println!("Total time: {:.3?}", total);
+    }
+
+    if failed > 0 {
+        eprintln!("Failed parts: {}", failed);
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::days;
+
+    fn args(args: &str) -> impl Iterator<Item = String> + '_ {
+        args.split(' ').map(|x| x.to_string())
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let options = parse_args(args("run")).unwrap();
+        assert_eq!(options.days.len(), 25);
+        assert_eq!(options.parts, [1, 2]);
+
+        let options = parse_args(args("run --day 17 --part 2 --input x")).unwrap();
+        assert_eq!(options.days, [17]);
+        assert_eq!(options.parts, [2]);
+        assert_eq!(options.input.as_deref(), Some("x"));
+
+        assert!(parse_args(args("run --day 26")).is_err());
+        assert!(parse_args(args("run --part 3")).is_err());
+        assert!(parse_args(args("run --input x")).is_err());
+        assert!(parse_args(args("test")).is_err());
+
+        let options = parse_args(args("verify --answers a.toml")).unwrap();
+        assert_eq!(options.command, Command::Verify);
+        assert_eq!(options.answers, Some(PathBuf::from("a.toml")));
+        assert!(parse_args(args("run --answers a.toml")).is_err());
+
+        let options = parse_args(args("bench --day 16 --iterations 5 --threshold 2.5 --no-save")).unwrap();
+        assert_eq!(options.command, Command::Bench);
+        assert_eq!(options.bench.iterations, 5);
+        assert_eq!(options.bench.threshold, 2.5);
+        assert!(!options.bench.save);
+        assert!(parse_args(args("bench --iterations 0")).is_err());
+
+        let options = parse_args(args("generate --day 5 --seed 42 --size 7")).unwrap();
+        assert_eq!(options.command, Command::Generate);
+        assert_eq!((options.fuzz.seed, options.fuzz.size), (42, 7));
+        assert!(parse_args(args("generate --seed 42")).is_err());
+        assert!(parse_args(args("run --seed 42")).is_err());
+
+        assert!(parse_args(args("run --json")).unwrap().json);
+        assert!(parse_args(args("verify --json")).is_err());
+
+        let options = parse_args(args("fuzz --iterations 3")).unwrap();
+        assert_eq!(options.fuzz.iterations, 3);
+        assert_eq!(options.days.len(), 25);
+
+        assert_eq!(parse_args(args("run")).unwrap().threads, 0);
+        assert_eq!(parse_args(args("run --threads 4")).unwrap().threads, 4);
+        assert_eq!(parse_args(args("bench --single-thread")).unwrap().threads, 1);
+        assert!(parse_args(args("run --threads 0")).is_err());
+        assert!(parse_args(args("generate --day 1 --threads 2")).is_err());
+
+        let options = parse_args(args("run --day 11 --param day11.rounds2=20")).unwrap();
+        let rows = run_day(options.puzzles()[10].as_ref(), &Options {
+            input: Some("../advent11/test.txt".to_string()),
+            ..options
+        });
+        assert_eq!(rows[1].answer, Ok(Answer::Int(103 * 99)));
+        assert!(parse_args(args("run --param day11.rounds=20")).is_err());
+        assert!(parse_args(args("run --param day1.rounds1=20")).is_err());
+        assert!(parse_args(args("run --param rounds1=20")).is_err());
+        assert!(parse_args(args("run --param day2.rules=lizard-spock")).is_ok());
+        assert!(parse_args(args("run --param day2.shapes=a,b")).is_err());
+        assert!(parse_args(args("run --config missing.toml")).is_err());
+    }
+
+    #[test]
+    fn test_run_day() {
+        let options = Options {
+            days: vec![1],
+            parts: vec![1, 2],
+            input: Some("../advent01/test.txt".to_string()),
+            ..Options::new(Command::Run)
+        };
+        let rows = run_day(days()[0].as_ref(), &options);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].answer, Ok(Answer::Int(24000)));
+        assert_eq!(rows[1].answer, Ok(Answer::Int(45000)));
+        let input_hash = report::input_hash(include_str!("../../advent01/test.txt"));
+        assert_eq!(rows[0].input_hash, Some(input_hash));
+        assert_eq!(rows[0].to_json().get("type").and_then(|t| t.as_str()), Some("int"));
+    }
+
+    #[test]
+    fn test_run_day_missing_input() {
+        let options = Options {
+            days: vec![25],
+            parts: vec![1, 2],
+            input: Some("missing".to_string()),
+            ..Options::new(Command::Run)
+        };
+        let rows = run_day(days()[24].as_ref(), &options);
+        assert_eq!(rows.len(), 2);
+        assert!(rows.iter().all(|row| row.answer.is_err()));
+    }
+
+    #[test]
+    fn test_run_day_parse_error() {
+        let options = Options {
+            days: vec![25],
+            parts: vec![1],
+            input: Some("../advent01/test.txt".to_string()),
+            ..Options::new(Command::Run)
+        };
+        let rows = run_day(days()[24].as_ref(), &options);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].answer, Err("day 25, line 3, column 1: Unexpected digit: '3'".to_string()));
+    }
+}
@@ -0,0 +1,152 @@
+use std::process::ExitCode;
+
+use aoc_common::{parallel, Answer, Answers, Puzzle};
+
+use crate::{load_input, solve_input, Options};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail(String),
+    /// Cannot be verified, e.g. because the input is missing.
+    Skip(String),
+    /// The part has no puzzle to solve.
+    None,
+}
+
+impl Verdict {
+    fn cell(&self) -> &'static str {
+        match self {
+            Verdict::Pass => "pass",
+            Verdict::Fail(_) => "FAIL",
+            Verdict::Skip(_) => "skip",
+            Verdict::None => "-",
+        }
+    }
+}
+
+fn describe(answer: &Answer) -> String {
+    match answer {
+        Answer::Glyph(picture) => format!("\n{}", picture.trim_end()),
+        answer => answer.to_string(),
+    }
+}
+
+fn judge(answer: Result<Answer, String>, recorded: Option<&Answer>) -> Verdict {
+    match (answer, recorded) {
+        (Ok(Answer::None), _) => Verdict::None,
+        (Err(e), _) => Verdict::Fail(e),
+        (Ok(answer), Some(expected)) if answer == *expected => Verdict::Pass,
+        (Ok(answer), Some(expected)) => Verdict::Fail(
+            format!("expected {}, got {}", describe(expected), describe(&answer))),
+        (Ok(answer), None) => Verdict::Skip(format!("no recorded answer, got {}", describe(&answer))),
+    }
+}
+
+/// Verdicts for selected parts of the day.
+pub fn verify_day(puzzle: &dyn Puzzle, options: &Options, answers: &Answers) -> Vec<(u8, Verdict)> {
+    let input = match load_input(puzzle, options) {
+        Ok(input) => input,
+        Err(e) => {
+            let reason = format!("missing input: {}", e);
+            return options.parts.iter().map(|&part| (part, Verdict::Skip(reason.clone()))).collect();
+        },
+    };
+    solve_input(puzzle, &input, options).into_iter()
+        .map(|row| (row.part, judge(row.answer, answers.get(puzzle.day(), row.part))))
+        .collect()
+}
+
+pub fn verify(options: &Options) -> ExitCode {
+    let path = options.answers.clone().unwrap_or_else(Answers::default_path);
+    let answers = match Answers::load(&path) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::FAILURE;
+        },
+    };
+
+    let header: String = options.parts.iter().map(|part| format!("  Part {}", part)).collect();
+    println!("Day{}", header);
+    let puzzles = options.puzzles();
+    let verdicts = parallel::map(&options.days, |&day| verify_day(puzzles[day - 1].as_ref(), options, &answers));
+    let mut notes = Vec::new();
+    let mut counts = [0; 3];
+    for (&day, verdicts) in options.days.iter().zip(verdicts) {
+        let cells: String = verdicts.iter().map(|(_, v)| format!("  {:<6}", v.cell())).collect();
+        println!("{:>3}{}", day, cells.trim_end());
+        for (part, verdict) in verdicts {
+            match verdict {
+                Verdict::Pass => counts[0] += 1,
+                Verdict::Fail(e) => {
+                    counts[1] += 1;
+                    notes.push(format!("day {} part {}: FAIL: {}", day, part, e));
+                },
+                Verdict::Skip(reason) => {
+                    counts[2] += 1;
+                    // Missing inputs are expected, only list unrecorded answers.
+                    if !reason.starts_with("missing input") {
+                        notes.push(format!("day {} part {}: {}", day, part, reason));
+                    }
+                },
+                Verdict::None => {},
+            }
+        }
+    }
+
+    if !notes.is_empty() {
+        println!();
+        for note in notes {
+            println!("{}", note);
+        }
+    }
+    println!("\nPassed: {}, failed: {}, skipped: {}", counts[0], counts[1], counts[2]);
+
+    if counts[1] > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::days;
+    use crate::Command;
+
+    fn options(day: usize, input: &str) -> Options {
+        Options {
+            days: vec![day],
+            input: Some(input.to_string()),
+            ..Options::new(Command::Verify)
+        }
+    }
+
+    #[test]
+    fn test_judge() {
+        let expected = Answer::Int(3);
+        assert_eq!(judge(Ok(Answer::Int(3)), Some(&expected)), Verdict::Pass);
+        assert_eq!(judge(Ok(Answer::Int(4)), Some(&expected)), Verdict::Fail("expected 3, got 4".to_string()));
+        assert_eq!(judge(Err("panicked".to_string()), Some(&expected)), Verdict::Fail("panicked".to_string()));
+        assert_eq!(judge(Ok(Answer::None), None), Verdict::None);
+        assert!(matches!(judge(Ok(Answer::Int(4)), None), Verdict::Skip(_)));
+    }
+
+    #[test]
+    fn test_verify_day() {
+        let answers = Answers::parse("[day1]\npart1 = 24000\npart2 = 45001").unwrap();
+        let verdicts = verify_day(days()[0].as_ref(), &options(1, "../advent01/test.txt"), &answers);
+        assert_eq!(verdicts, [
+            (1, Verdict::Pass),
+            (2, Verdict::Fail("expected 45001, got 45000".to_string())),
+        ]);
+    }
+
+    #[test]
+    fn test_verify_day_missing_input() {
+        let verdicts = verify_day(days()[0].as_ref(), &options(1, "missing"), &Answers::default());
+        assert!(verdicts.iter().all(|(_, v)| matches!(v, Verdict::Skip(_))));
+    }
+}
@@ -0,0 +1,68 @@
+use aoc_common::{solve, Answer, Solution};
+
+fn check<S: Solution>(input: &str, params: S::Params, expected: [Answer; 2]) {
+    assert_eq!(solve::<S>(input, &params), Ok(expected.map(Ok)));
+}
+
+macro_rules! examples {
+    ( $( $test:ident: $day:ty, $input:expr, $params:expr => $part1:expr, $part2:expr; )* ) => {
+        $(
+            #[test]
+            fn $test() {
+                check::<$day>($input, $params, [Answer::from($part1), Answer::from($part2)]);
+            }
+        )*
+    };
+}
+
+macro_rules! example {
+    ( $dir:literal ) => {
+        include_str!(concat!("../../", $dir, "/test.txt"))
+    };
+    ( $dir:literal, $file:literal ) => {
+        include_str!(concat!("../../", $dir, "/", $file))
+    };
+}
+
+const CRT: &str = "\
+##..##..##..##..##..##..##..##..##..##..\n\
+###...###...###...###...###...###...###.\n\
+####....####....####....####....####....\n\
+#####.....#####.....#####.....#####.....\n\
+######......######......######......####\n\
+#######.......#######.......#######.....\n";
+
+examples! {
+    day01: advent2022_01::Day01, example!("advent01"), () => 24000, 45000;
+    day02: advent2022_02::Day02, example!("advent02"), Default::default() => 15, 12;
+    day03: advent2022_03::Day03, example!("advent03"), Default::default() => 157, 70;
+    day04: advent2022_04::Day04, example!("advent04"), () => 2, 4;
+    day05: advent2022_05::Day05, example!("advent05"), Default::default() => "CMZ", "MCD";
+    day06: advent2022_06::Day06, "mjqjpqmgbljsphdztnvjfqwrcgsmlb", () => 7, 19;
+    day06_2: advent2022_06::Day06, "bvwbjplbgvbhsrlpgdmjqwftvncz", () => 5, 23;
+    day06_3: advent2022_06::Day06, "nppdvjthqldpwncqszvftbrmjlhg", () => 6, 23;
+    day06_4: advent2022_06::Day06, "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", () => 10, 29;
+    day06_5: advent2022_06::Day06, "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", () => 11, 26;
+    day07: advent2022_07::Day07, example!("advent07"), Default::default() => 95437, 24933642;
+    day08: advent2022_08::Day08, example!("advent08"), () => 21, 8;
+    day09: advent2022_09::Day09, example!("advent09"), () => 13, 1;
+    day09_2: advent2022_09::Day09, example!("advent09", "test2.txt"), () => 88, 36;
+    day10: advent2022_10::Day10, example!("advent10"), Default::default() => 13140, Answer::glyph(CRT);
+    day11: advent2022_11::Day11, example!("advent11"), Default::default() => 10605, 2713310158u64;
+    day12: advent2022_12::Day12, example!("advent12"), () => 31, 29;
+    day13: advent2022_13::Day13, example!("advent13"), () => 13, 140;
+    day14: advent2022_14::Day14, example!("advent14"), () => 24, 93;
+    day15: advent2022_15::Day15, example!("advent15"),
+        advent2022_15::Params { row: 10, max: 20 } => 26, 56000011;
+    day16: advent2022_16::Day16, example!("advent16"), Default::default() => 1651, 1707;
+    day17: advent2022_17::Day17, example!("advent17"), Default::default() => 3068, 1514285714288u64;
+    day18: advent2022_18::Day18, example!("advent18"), () => 64, 58;
+    day19: advent2022_19::Day19, example!("advent19"), Default::default() => 33, 56 * 62;
+    day20: advent2022_20::Day20, example!("advent20"), Default::default() => 3, 1623178306;
+    day21: advent2022_21::Day21, example!("advent21"), Default::default() => 152, 301;
+    day22: advent2022_22::Day22, example!("advent22"), () => 6032, 5031;
+    day23: advent2022_23::Day23, example!("advent23"), () => 110, 20;
+    day23_2: advent2022_23::Day23, example!("advent23", "test_simple.txt"), () => 25, 4;
+    day24: advent2022_24::Day24, example!("advent24"), () => 18, 54;
+    day25: advent2022_25::Day25, example!("advent25"), () => "2=-1=0", Answer::None;
+}